@@ -1,5 +1,5 @@
-extern crate criterion;
 extern crate actix_router;
+extern crate criterion;
 extern crate path_router;
 extern crate path_table;
 extern crate path_tree;
@@ -14,7 +14,7 @@ use path_table::PathTable;
 use path_tree::PathTree;
 use route_recognizer::Router as RRRouter;
 
-#[path = "../tests/fixtures/github.rs"]
+#[path = "../src/fixtures/github.rs"]
 mod github;
 
 use github::*;
@@ -192,6 +192,19 @@ fn bench_path_find(c: &mut Criterion) {
                 }
             })
         })
+        .with_function("path_router_frozen_recognize", |b| {
+            let mut router = Router::<usize>::default();
+            for (i, r) in ROUTES_WITH_COLON.iter().enumerate() {
+                router.add(r, i).unwrap();
+            }
+            let router = router.freeze();
+            b.iter(|| {
+                for (i, r) in ROUTES_URLS.iter().enumerate() {
+                    let m = router.recognize(r).unwrap();
+                    assert_eq!(m.data, &i);
+                }
+            })
+        })
         .sample_size(50),
     );
 }
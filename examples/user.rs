@@ -3,56 +3,72 @@ extern crate path_router;
 
 use hyper::rt::Future;
 use hyper::service::service_fn_ok;
-use hyper::{Body, Request, Response, Server};
-use path_router::{Match, Router};
-use std::collections::BTreeMap;
+use hyper::{Body, Request, Response, Server, StatusCode};
+use path_router::hyper::{Allowed, RequestRouter};
+use path_router::{Method, Params};
 use std::sync::Arc;
 
-type Handler = fn(Request<Body>, BTreeMap<String, String>) -> Body;
+type Handler = fn(Request<Body>) -> Response<Body>;
 
-fn handler_get_user_info(_req: Request<Body>, params: BTreeMap<String, String>) -> Body {
-    let uid = params["id"].clone();
-    Body::from(uid)
+fn params(req: &Request<Body>) -> &Params {
+    req.extensions().get::<Params>().unwrap()
 }
 
-fn handler_add_user(_req: Request<Body>, _params: BTreeMap<String, String>) -> Body {
-    Body::from("ok")
+fn handler_get_user_info(req: Request<Body>) -> Response<Body> {
+    let uid = params(&req)["id"].to_string();
+    Response::new(Body::from(uid))
 }
 
-fn handler_get_user_attributes(_req: Request<Body>, params: BTreeMap<String, String>) -> Body {
-    let result = params["attrs"].split("/").collect::<Vec<&str>>().join(" ");
-    Body::from(result)
+fn handler_add_user(_req: Request<Body>) -> Response<Body> {
+    Response::new(Body::from("ok"))
 }
 
-fn handler_notfound(_req: Request<Body>) -> Body {
-    Body::from("notfound")
+fn handler_get_user_attributes(req: Request<Body>) -> Response<Body> {
+    let result = params(&req)["attrs"]
+        .split("/")
+        .collect::<Vec<&str>>()
+        .join(" ");
+    Response::new(Body::from(result))
+}
+
+fn handler_notfound(req: Request<Body>) -> Response<Body> {
+    match req.extensions().get::<Allowed>() {
+        Some(Allowed(allowed)) => {
+            let allowed = allowed
+                .iter()
+                .map(Method::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .header("Allow", allowed)
+                .body(Body::empty())
+                .unwrap()
+        }
+        None => Response::new(Body::from("notfound")),
+    }
 }
 
 fn main() {
     let addr = ([127, 0, 0, 1], 3000).into();
 
-    let mut router = Router::<Handler>::default();
+    let mut router = RequestRouter::<Handler>::new();
     // curl localhost:3000/user/123
-    router.add("/GET/user/:id", handler_get_user_info).unwrap();
+    router.get("/user/:id", handler_get_user_info).unwrap();
     // curl -X POST localhost:3000/user
-    router.add("/POST/user", handler_add_user).unwrap();
+    router.post("/user", handler_add_user).unwrap();
     // curl localhost:3000/user/123/name/gender
     router
-        .add("/GET/user/:id/*attrs", handler_get_user_attributes)
+        .get("/user/:id/*attrs", handler_get_user_attributes)
         .unwrap();
+    router.not_found(handler_notfound);
 
     let router = Arc::new(router);
 
     let new_svc = move || {
         let router = Arc::clone(&router);
 
-        service_fn_ok(move |req| {
-            let path = format!("/{}{}", req.method().as_str(), req.uri().path());
-            match router.recognize(path.as_str()) {
-                Some(Match { data, params }) => Response::new(data(req, params)),
-                None => Response::new(handler_notfound(req)),
-            }
-        })
+        service_fn_ok(move |req| router.dispatch(req))
     };
 
     let server = Server::bind(&addr)
@@ -0,0 +1,274 @@
+//! the `routes!` macro of path-router, see `routes!`
+
+extern crate proc_macro;
+
+use path_router::{Method, MethodRouter};
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+/// build a `MethodRouter` from a route table of methods, routes and handlers
+///
+/// every entry reads `METHOD "route" => handler` and entries are separated
+/// by `,`, a `,` inside the parameters of a closure or the generics of a
+/// path like `handler::<A, B>` doesn't end a handler; the routes follow the
+/// rules of `Router::add` and are added in order; an invalid route or a route conflicting with an earlier one fails
+/// the build with the error of `add`; the handlers share a type, different
+/// functions and closures capturing nothing become function pointers
+///
+/// # Example
+///
+/// ```
+/// use path_router::Method;
+/// use path_router_macros::routes;
+///
+/// fn get_user(id: &str) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// fn add_user(_: &str) -> String {
+///     String::from("added")
+/// }
+///
+/// let router = routes! {
+///     GET "/user/:id" => get_user,
+///     POST "/user" => add_user,
+///     PURGE "/user/:id" => |_: &str| String::from("purged"),
+/// };
+///
+/// let m = router.recognize(&Method::Get, "/user/42").unwrap();
+/// assert_eq!((m.data)(&m.params["id"]), "user 42");
+/// let purge = Method::Other(String::from("PURGE"));
+/// assert!(router.recognize(&purge, "/user/42").is_some());
+/// ```
+///
+/// an invalid route doesn't compile
+///
+/// ```compile_fail
+/// use path_router_macros::routes;
+/// let router = routes! {
+///     GET "/user/:id" => 1,
+///     GET "/user/:name" => 2,
+/// };
+/// ```
+#[proc_macro]
+pub fn routes(input: TokenStream) -> TokenStream {
+    match parse(input) {
+        Ok(entries) => expand(entries),
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+struct Entry {
+    method: Method,
+    route: String,
+    handler: TokenStream,
+}
+
+type ParseError = (Span, String);
+
+fn parse(input: TokenStream) -> Result<Vec<Entry>, ParseError> {
+    let mut tokens = input.into_iter();
+    let mut entries = vec![];
+    let mut check = MethodRouter::new();
+    while let Some(token) = tokens.next() {
+        let method = match token {
+            TokenTree::Ident(ref ident) => ident.to_string(),
+            _ => return Err((token.span(), String::from("expected a method like GET"))),
+        };
+        let method = method
+            .parse::<Method>()
+            .map_err(|e| (token.span(), e.to_string()))?;
+
+        let literal = match tokens.next() {
+            Some(TokenTree::Literal(literal)) => literal,
+            other => {
+                return Err((
+                    span_of(other, token.span()),
+                    String::from("expected a route"),
+                ))
+            }
+        };
+        let route = string_literal(&literal)?;
+
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Punct(ref a)), Some(TokenTree::Punct(ref b)))
+                if a.as_char() == '=' && b.as_char() == '>' => {}
+            (other, _) => {
+                return Err((
+                    span_of(other, literal.span()),
+                    String::from("expected `=>`"),
+                ))
+            }
+        }
+
+        // a `,` ends the handler unless it's inside the `|..|` parameters of
+        // a closure or the `<..>` generics of a path like `handler::<A, B>`
+        let mut handler: Vec<TokenTree> = vec![];
+        let mut closure_params = false;
+        let mut generics = 0usize;
+        for token in tokens.by_ref() {
+            if let TokenTree::Punct(ref p) = token {
+                let joined = match handler.last() {
+                    Some(TokenTree::Punct(last)) => last.spacing() == Spacing::Joint,
+                    _ => false,
+                };
+                match p.as_char() {
+                    ',' if !closure_params && generics == 0 => break,
+                    '|' if closure_params => closure_params = false,
+                    '|' if starts_closure(&handler) => closure_params = true,
+                    '<' => generics += 1,
+                    // `->` and `=>` aren't closing brackets
+                    '>' if !(joined && ends_arrow(&handler)) => {
+                        generics = generics.saturating_sub(1)
+                    }
+                    _ => {}
+                }
+            }
+            handler.push(token);
+        }
+        if handler.is_empty() {
+            return Err((literal.span(), String::from("expected a handler")));
+        }
+
+        if let Err(e) = check.add(method.clone(), &route, ()) {
+            return Err((literal.span(), format!("{} {}: {}", method, route, e)));
+        }
+        entries.push(Entry {
+            method,
+            route,
+            handler: TokenStream::from_iter(handler),
+        });
+    }
+    Ok(entries)
+}
+
+// a `|` here opens the parameters of a closure: at the start of the handler
+// or after `move`
+fn starts_closure(handler: &[TokenTree]) -> bool {
+    handler.iter().all(|t| match t {
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            ident == "move" || ident == "async"
+        }
+        _ => false,
+    })
+}
+
+// the handler so far ends with the `-` of `->` or the `=` of `=>`
+fn ends_arrow(handler: &[TokenTree]) -> bool {
+    match handler.last() {
+        Some(TokenTree::Punct(p)) => p.as_char() == '-' || p.as_char() == '=',
+        _ => false,
+    }
+}
+
+fn span_of(token: Option<TokenTree>, default: Span) -> Span {
+    token.map_or(default, |t| t.span())
+}
+
+// the text of a plain or raw string literal
+fn string_literal(literal: &Literal) -> Result<String, ParseError> {
+    let error = || (literal.span(), String::from("expected a string literal"));
+    let text = literal.to_string();
+    if let Some(raw) = text.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        return raw
+            .get(hashes + 1..raw.len() - hashes - 1)
+            .map(String::from)
+            .ok_or_else(error);
+    }
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|t| t.strip_suffix('"'))
+        .ok_or_else(error)?;
+    let mut result = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some(c @ ('\\' | '"' | '\'')) => result.push(c),
+            _ => return Err((literal.span(), String::from("unsupported escape in route"))),
+        }
+    }
+    Ok(result)
+}
+
+fn method_tokens(method: &Method) -> String {
+    let variant = match method {
+        Method::Get => "Get",
+        Method::Post => "Post",
+        Method::Put => "Put",
+        Method::Delete => "Delete",
+        Method::Head => "Head",
+        Method::Options => "Options",
+        Method::Connect => "Connect",
+        Method::Patch => "Patch",
+        Method::Trace => "Trace",
+        Method::Other(m) => {
+            return format!(
+                "::path_router::Method::Other(::core::convert::Into::into({:?}))",
+                m
+            )
+        }
+    };
+    format!("::path_router::Method::{}", variant)
+}
+
+// the handlers go in one array first so they coerce to one type
+fn expand(entries: Vec<Entry>) -> TokenStream {
+    let mut handlers = TokenStream::new();
+    let mut routes = String::new();
+    let count = entries.len();
+    for entry in entries {
+        handlers.extend(entry.handler);
+        handlers.extend(Some(TokenTree::Punct(Punct::new(',', Spacing::Alone))));
+        routes.push_str(&format!(
+            "({}, {:?}),",
+            method_tokens(&entry.method),
+            entry.route
+        ));
+    }
+    let mut body = code("let handlers = ");
+    body.extend(Some(TokenTree::Group(Group::new(
+        Delimiter::Bracket,
+        handlers,
+    ))));
+    body.extend(code(&format!(
+        "; let routes: [(::path_router::Method, &str); {}] = [{}];
+        let mut router = ::path_router::MethodRouter::new();
+        let entries = ::core::iter::Iterator::zip(
+            ::core::iter::IntoIterator::into_iter(routes),
+            ::core::iter::IntoIterator::into_iter(handlers),
+        );
+        for ((method, route), handler) in entries {{
+            router.add(method, route, handler).expect(\"checked by routes!\");
+        }}
+        router",
+        count, routes
+    )));
+    TokenStream::from(TokenTree::Group(Group::new(Delimiter::Brace, body)))
+}
+
+fn code(text: &str) -> TokenStream {
+    text.parse().expect("valid tokens")
+}
+
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut args = Literal::string(message);
+    args.set_span(span);
+    let mut group = Group::new(
+        Delimiter::Parenthesis,
+        TokenStream::from(TokenTree::Literal(args)),
+    );
+    group.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    TokenStream::from_iter(vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ])
+}
@@ -0,0 +1,56 @@
+use path_router::{Method, MethodRouter};
+use path_router_macros::routes;
+
+fn list(_: &str) -> usize {
+    1
+}
+
+fn show(id: &str) -> usize {
+    id.len()
+}
+
+#[test]
+fn route_table() {
+    let router: MethodRouter<fn(&str) -> usize> = routes! {
+        GET "/repos" => list,
+        GET r"/repos/:owner/:name" => show,
+        DELETE "/repos/:owner/:name" => |_: &str| 0,
+        PURGE "/cache/*path" => |path: &str| path.matches('/').count(),
+    };
+    assert_eq!(
+        router.list_routes(),
+        [
+            (
+                Method::Other(String::from("PURGE")),
+                String::from("/cache/*path")
+            ),
+            (Method::Get, String::from("/repos")),
+            (Method::Get, String::from("/repos/:owner/:name")),
+            (Method::Delete, String::from("/repos/:owner/:name")),
+        ]
+    );
+    let m = router.recognize(&Method::Get, "/repos/bob/app").unwrap();
+    assert_eq!((m.data)(&m.params["name"]), 3);
+    let purge = Method::Other(String::from("PURGE"));
+    let m = router.recognize(&purge, "/cache/a/b/c").unwrap();
+    assert_eq!((m.data)(&m.params["path"]), 2);
+
+    // commas inside closure parameters and generics don't end a handler
+    fn pick<A: From<B>, B: Default>(_: &str, _: &str) -> A {
+        A::from(B::default())
+    }
+    let router: MethodRouter<fn(&str, &str) -> usize> = routes! {
+        GET "/a/:x" => |req: &str, params: &str| req.len() + params.len(),
+        GET "/b/:x" => pick::<usize, u8>,
+        GET "/c/:x" => |_: &str, _: &str| -> usize { 7 },
+        GET "/d/:x" => |a: &str, b: &str| if a.len() > b.len() { 1 } else { 2 },
+    };
+    let data = |path| *router.recognize(&Method::Get, path).unwrap().data;
+    assert_eq!(data("/a/x")("ab", "c"), 3);
+    assert_eq!(data("/b/x")("ab", "c"), 0);
+    assert_eq!(data("/c/x")("ab", "c"), 7);
+    assert_eq!(data("/d/x")("ab", "c"), 1);
+
+    let empty: MethodRouter<usize> = routes! {};
+    assert!(empty.list_routes().is_empty());
+}
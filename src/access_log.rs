@@ -0,0 +1,235 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Resolution, RouteInfo, Router};
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// coverage of a router against the requests of an access log
+///
+/// created by `Router::check_access_log`, `Display` renders it as a text
+/// report
+#[derive(Debug, Default, PartialEq)]
+pub struct LogReport {
+    /// number of non-empty lines in the log
+    pub lines: usize,
+    /// lines which are not in common or combined log format
+    pub malformed: Vec<usize>,
+    /// number of requests recognized by the router
+    pub matched: usize,
+    /// hits of every route in `list_routes` order, including unused ones
+    pub routes: Vec<(String, usize)>,
+    /// paths missed by the router with their counts, most frequent first
+    pub misses: Vec<(String, usize)>,
+}
+
+impl LogReport {
+    /// number of requests parsed from the log
+    pub fn requests(&self) -> usize {
+        self.lines - self.malformed.len()
+    }
+
+    /// routes never hit by the log
+    pub fn unused_routes(&self) -> Vec<&str> {
+        self.routes
+            .iter()
+            .filter(|(_, hits)| *hits == 0)
+            .map(|(route, _)| route.as_str())
+            .collect()
+    }
+}
+
+impl fmt::Display for LogReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "requests: {}, matched: {}, missed: {}, malformed lines: {}",
+            self.requests(),
+            self.matched,
+            self.requests() - self.matched,
+            self.malformed.len()
+        )?;
+        let used = self.routes.iter().filter(|(_, hits)| *hits > 0).count();
+        writeln!(f, "routes: {}, used: {}", self.routes.len(), used)?;
+        for (route, hits) in self.routes.iter() {
+            writeln!(f, "  {:>8}  {}", hits, route)?;
+        }
+        if !self.misses.is_empty() {
+            writeln!(f, "misses:")?;
+            for (path, count) in self.misses.iter() {
+                writeln!(f, "  {:>8}  {}", count, path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// extract the path of the request from a line in common or combined log
+/// format
+///
+/// the query and the scheme and authority of absolute targets are dropped
+pub fn log_line_path(line: &str) -> Option<&str> {
+    // 127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /a.gif HTTP/1.0" 200 2326
+    let start = line.find('"')? + 1;
+    let end = start + line[start..].find('"')?;
+    let mut request = line[start..end].split(' ');
+    let _method = request.next().filter(|m| !m.is_empty())?;
+    let target = request.next()?;
+
+    let target = match target.find("://") {
+        Some(i) => {
+            let rest = &target[i + 3..];
+            &rest[rest.find('/').unwrap_or(rest.len())..]
+        }
+        None => target,
+    };
+    let target = match target.find(['?', '#']) {
+        Some(i) => &target[..i],
+        None => target,
+    };
+    if target.is_empty() || target.starts_with('/') {
+        Some(target)
+    } else {
+        None
+    }
+}
+
+impl<T> Router<T> {
+    /// classify the requests of an access log against the router
+    ///
+    /// lines are in common or combined log format, the request method is
+    /// ignored, malformed lines are reported by their line numbers; paths
+    /// are resolved like `Router::resolve`, a path taken by a fallback or
+    /// redirected is a miss
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", 1).unwrap();
+    /// router.add("/about", 2).unwrap();
+    ///
+    /// let log = r#"127.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "GET /user/1?full=1 HTTP/1.0" 200 26
+    /// 127.0.0.1 - - [10/Oct/2000:13:55:37 -0700] "GET /users HTTP/1.0" 404 0"#;
+    /// let report = router.check_access_log(log);
+    /// assert_eq!(report.matched, 1);
+    /// assert_eq!(report.unused_routes(), vec!["/about"]);
+    /// assert_eq!(report.misses, vec![(String::from("/users"), 1)]);
+    /// ```
+    pub fn check_access_log(&self, log: &str) -> LogReport {
+        let nodes = self.list_sub_nodes(&[]);
+        // routes by their info, which a match points to
+        let index = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (_, n))| (&n.info as *const RouteInfo, i))
+            .collect::<BTreeMap<_, _>>();
+        let mut hits = vec![0; nodes.len()];
+        let mut misses = BTreeMap::<&str, usize>::new();
+        let mut report = LogReport::default();
+
+        for (i, line) in log.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            report.lines += 1;
+
+            let path = match log_line_path(line) {
+                Some(path) => path,
+                None => {
+                    report.malformed.push(i + 1);
+                    continue;
+                }
+            };
+
+            let found = match self.resolve(path) {
+                Resolution::Hit(m) => index.get(&(m.route as *const RouteInfo)).copied(),
+                _ => None,
+            };
+            match found {
+                Some(idx) => {
+                    report.matched += 1;
+                    hits[idx] += 1;
+                }
+                None => *misses.entry(path).or_insert(0) += 1,
+            }
+        }
+
+        report.routes = nodes
+            .into_iter()
+            .zip(hits)
+            .map(|((route, _), hits)| (route, hits))
+            .collect();
+        let mut misses = misses
+            .into_iter()
+            .map(|(path, count)| (String::from(path), count))
+            .collect::<Vec<_>>();
+        misses.sort_by_key(|m| core::cmp::Reverse(m.1));
+        report.misses = misses;
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DotSegments;
+
+    #[test]
+    fn parse_log_lines() {
+        let combined = r#"10.0.0.1 - - [10/Oct/2000:13:55:36 -0700] "POST http://example.com/a/b?x=1 HTTP/1.1" 200 2326 "http://ref/" "curl/7.0""#;
+        assert_eq!(log_line_path(combined), Some("/a/b"));
+        let common =
+            r#"10.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /a%20b#top HTTP/1.0" 200 2326"#;
+        assert_eq!(log_line_path(common), Some("/a%20b"));
+        assert_eq!(log_line_path(r#"10.0.0.1 - - [x] "-" 400 0"#), None);
+        assert_eq!(log_line_path("garbage"), None);
+    }
+
+    #[test]
+    fn access_log_report() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/user/:id", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+
+        let log = r#"1.1.1.1 - - [t] "GET / HTTP/1.1" 200 1
+1.1.1.1 - - [t] "GET /user/1 HTTP/1.1" 200 1
+1.1.1.1 - - [t] "GET /user/2 HTTP/1.1" 200 1
+
+1.1.1.1 - - [t] "GET /nope HTTP/1.1" 404 1
+broken line
+1.1.1.1 - - [t] "GET /nope HTTP/1.1" 404 1
+1.1.1.1 - - [t] "GET /user/1/x HTTP/1.1" 404 1"#;
+        let report = router.check_access_log(log);
+        assert_eq!(report.lines, 7);
+        assert_eq!(report.malformed, vec![6]);
+        assert_eq!(report.matched, 3);
+        assert_eq!(
+            report.routes,
+            vec![
+                (String::from("/"), 1),
+                (String::from("/files/*path"), 0),
+                (String::from("/user/:id"), 2),
+            ]
+        );
+        assert_eq!(
+            report.misses,
+            vec![(String::from("/nope"), 2), (String::from("/user/1/x"), 1)]
+        );
+        assert!(report
+            .to_string()
+            .starts_with("requests: 6, matched: 3, missed: 3, malformed lines: 1\n"));
+
+        router.options_mut().strict_slash = true;
+        router.options_mut().dot_segments = DotSegments::Reject;
+        router.options_mut().fullwidth = true;
+        let log = r#"1.1.1.1 - - [t] "GET /user/1/ HTTP/1.1" 200 1
+1.1.1.1 - - [t] "GET /files/../user/1 HTTP/1.1" 200 1
+1.1.1.1 - - [t] "GET /user／3 HTTP/1.1" 200 1"#;
+        let report = router.check_access_log(log);
+        assert_eq!(report.matched, 1);
+        assert_eq!(report.routes[2], (String::from("/user/:id"), 1));
+        assert_eq!(report.misses.len(), 2);
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{route_params, Landing, Match, Router, Trail};
+
+impl<T> Router<T> {
+    /// every route taking a path, in the order `recognize` tries them, so the
+    /// first one is the match of `recognize` unless a `TieBreaker` picks
+    /// another
+    ///
+    /// a route going on after a catchall shows up once for every way its
+    /// catchall can split the path; fallbacks aren't routes and routes turned
+    /// down by their guard don't take the path, so they're left out
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/api/users/me", 1).unwrap();
+    /// router.add("/api/users/:id", 2).unwrap();
+    /// router.add("/api/*rest", 3).unwrap();
+    ///
+    /// let all = router.recognize_all("/api/users/me");
+    /// assert_eq!(all.iter().map(|m| *m.data).collect::<Vec<_>>(), [1, 2, 3]);
+    /// assert_eq!(all[1].params["id"], "me");
+    /// assert_eq!(all[2].params["rest"], "users/me");
+    /// ```
+    pub fn recognize_all<'a>(&'a self, path: &str) -> Vec<Match<'a, &'a T>> {
+        let normalized = match self.options.normalize_path(path) {
+            Ok(path) => path,
+            Err(_) => return vec![],
+        };
+        let mut trail = Trail::collecting();
+        self.find(&normalized, &mut trail);
+        trail
+            .all
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(node, values)| {
+                let node = node.data_node()?;
+                if !matches!(
+                    self.landing(path, &normalized, Some(node), false),
+                    Landing::Route
+                ) {
+                    return None;
+                }
+                Some(Match {
+                    data: node.data.as_ref()?,
+                    params: route_params(&node.params, &node.info, values),
+                    route: &node.info,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Pick, TieBreaker};
+
+    fn all(router: &Router<usize>, path: &str) -> Vec<(usize, Vec<String>)> {
+        router
+            .recognize_all(path)
+            .into_iter()
+            .map(|m| {
+                let params = m.params.iter().map(|(n, v)| format!("{}={}", n, v));
+                (*m.data, params.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recognize_all() {
+        let mut router = Router::default();
+        router.add("/api/users/me", 1).unwrap();
+        router.add("/api/users/:id", 2).unwrap();
+        router.add("/api/users/:n<u32>", 3).unwrap();
+        router.add("/api/users/:file.:ext", 4).unwrap();
+        router.add("/api/*rest", 5).unwrap();
+        router.add("/r/*a/:b?", 6).unwrap();
+        router.set_fallback("/api", 7).unwrap();
+
+        let s = |v: &[&str]| v.iter().map(|s| String::from(*s)).collect::<Vec<_>>();
+        assert_eq!(
+            all(&router, "/api/users/7"),
+            [
+                (3, s(&["n=7"])),
+                (2, s(&["id=7"])),
+                (5, s(&["rest=users/7"])),
+            ]
+        );
+        assert_eq!(
+            all(&router, "/api/users/a.b"),
+            [
+                (4, s(&["file=a", "ext=b"])),
+                (2, s(&["id=a.b"])),
+                (5, s(&["rest=users/a.b"])),
+            ]
+        );
+        assert_eq!(
+            all(&router, "/r/1/2"),
+            [(6, s(&["a=1", "b=2"])), (6, s(&["a=1/2"]))]
+        );
+        for path in ["/api/users/7", "/api/users/me", "/r/1/2"] {
+            assert_eq!(
+                router.recognize(path).unwrap().params,
+                router.recognize_all(path)[0].params
+            );
+        }
+        assert!(all(&router, "/other").is_empty());
+        assert!(all(&router, "/api").is_empty());
+        assert_eq!(all(&router, "/r/1/"), [(6, s(&["a=1/"]))]);
+    }
+
+    #[test]
+    fn tied_routes() {
+        let mut router = Router::default();
+        router.options_mut().tie_breaker = Some(TieBreaker::new(|_| Pick::Second));
+        router.add("/n/:a<u32>", 1).unwrap();
+        router.add("/n/:b<u16>", 2).unwrap();
+        router.add("/n/:c", 3).unwrap();
+
+        assert_eq!(*router.recognize("/n/7").unwrap().data, 2);
+        let data = router.recognize_all("/n/7").into_iter().map(|m| *m.data);
+        assert_eq!(data.collect::<Vec<_>>(), [1, 2, 3]);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::merge::render_route;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Constraint, Router, Segment};
+use alloc::sync::Arc;
+use core::fmt;
+
+/// a route making the routes beneath an existing prefix much more ambiguous
+///
+/// reported to `Options::on_ambiguity` when the route adds parameters or a
+/// catchall beneath a prefix whose routes have few of them, like `/:x/:y/:z`
+/// added to a mostly static tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ambiguity {
+    pub route: String,
+    /// the existing prefix where the route branches off into a new parameter
+    /// or catchall
+    pub prefix: String,
+    /// parameters and catchalls of the route after the prefix
+    pub wildcards: usize,
+    /// number of routes beneath the prefix before adding the route
+    pub routes: usize,
+    /// mean number of parameters and catchalls of those routes after the prefix
+    pub mean_wildcards: f64,
+}
+
+/// callback receiving ambiguity warnings, see `Ambiguity`
+#[derive(Clone)]
+pub struct AmbiguityHook(Arc<dyn Fn(&Ambiguity) + Send + Sync>);
+
+impl AmbiguityHook {
+    pub fn new<F: Fn(&Ambiguity) + Send + Sync + 'static>(f: F) -> AmbiguityHook {
+        AmbiguityHook(Arc::new(f))
+    }
+
+    pub(crate) fn call(&self, ambiguity: &Ambiguity) {
+        (self.0)(ambiguity)
+    }
+}
+
+impl fmt::Debug for AmbiguityHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AmbiguityHook")
+    }
+}
+
+impl<T> Router<T> {
+    // the warning for a route about to be added, if it's much more ambiguous
+    // than the routes beneath the prefix where it branches off into a new
+    // wildcard node
+    pub(crate) fn ambiguity(
+        &self,
+        route: &[(Segment<String>, Option<Constraint>)],
+    ) -> Option<Ambiguity> {
+        let collation = self.options.collation;
+        let mut node = self;
+        let mut i = 0;
+        while i < route.len() {
+            let (segment, constraint) = &route[i];
+            let next = match segment {
+                Segment::Static(text) => node.static_child(text, collation).filter(|n| {
+                    let tail = &route[i + 1..];
+                    n.tail.len() <= tail.len()
+                        && n.tail.iter().zip(tail).all(|(k, (s, _))| match s {
+                            Segment::Static(t) => collation.compare(k, t).is_eq(),
+                            _ => false,
+                        })
+                }),
+                Segment::Param(_) => node
+                    .param_children
+                    .iter()
+                    .find(|n| n.constraint == *constraint),
+                Segment::CatchAll(_) => node.catch_all_child.as_deref(),
+            };
+            match next {
+                Some(n) => {
+                    i += 1 + n.tail.len();
+                    node = n;
+                }
+                // a new static node makes a new subtree, nothing to compare
+                None if matches!(segment, Segment::Static(_)) => return None,
+                None => break,
+            }
+        }
+        if i == route.len() {
+            return None; // no new wildcard node
+        }
+
+        let wildcards = route[i..]
+            .iter()
+            .filter(|(s, _)| !matches!(s, Segment::Static(_)))
+            .count();
+        let mut existing = vec![];
+        node.parsed_routes(vec![], &mut existing);
+        let total = existing
+            .iter()
+            .map(|((segments, _), _)| {
+                segments
+                    .iter()
+                    .filter(|(s, _)| !matches!(s, Segment::Static(_)))
+                    .count()
+            })
+            .sum::<usize>();
+        if existing.is_empty() {
+            return None;
+        }
+        let mean_wildcards = total as f64 / existing.len() as f64;
+        if wildcards < 2 || wildcards as f64 <= mean_wildcards + 1.0 {
+            return None;
+        }
+        Some(Ambiguity {
+            route: render_route(&(route.to_vec(), false)),
+            prefix: render_route(&(route[..i].to_vec(), false)),
+            wildcards,
+            routes: existing.len(),
+            mean_wildcards,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Options;
+    use std::sync::Mutex;
+
+    #[test]
+    fn warn_ambiguous_routes() {
+        let warnings = Arc::new(Mutex::new(vec![]));
+        let sink = Arc::clone(&warnings);
+        let mut router = Router::with_options(Options {
+            on_ambiguity: Some(AmbiguityHook::new(move |a| {
+                sink.lock().unwrap().push(a.clone())
+            })),
+            ..Options::default()
+        });
+        router.add("/", 0).unwrap();
+        router.add("/about", 1).unwrap();
+        router.add("/users/:id", 2).unwrap();
+        router.add("/users/:id/posts/:post", 3).unwrap();
+        router.add("/:page", 4).unwrap();
+        router.add("/users/:id/posts/:post/:comment", 5).unwrap();
+        router.add("/docs/:a/:b/:c", 6).unwrap();
+        assert!(warnings.lock().unwrap().is_empty());
+
+        router.add("/about/:x/:y", 7).unwrap();
+        router.add("/:page/:y/:z", 8).unwrap();
+        assert!(router.add("/about/:z/:y", 9).is_err());
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            vec![
+                Ambiguity {
+                    route: String::from("/about/:x/:y"),
+                    prefix: String::from("/about"),
+                    wildcards: 2,
+                    routes: 1,
+                    mean_wildcards: 0.0,
+                },
+                Ambiguity {
+                    route: String::from("/:page/:y/:z"),
+                    prefix: String::from("/:page"),
+                    wildcards: 2,
+                    routes: 1,
+                    mean_wildcards: 0.0,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Match, Router};
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// how a `BoundedRouter` picks the route to evict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// the least recently matched route, a route never matched counts as
+    /// matched when it was added
+    Lru,
+}
+
+type OnEvict<T> = Box<dyn FnMut(&str, T) + Send + Sync>;
+
+/// a router holding at most `max_routes` dynamic routes, see `Router::bounded`
+///
+/// adding a dynamic route past the capacity evicts one picked by the
+/// `EvictionPolicy`, never the route being added, and hands its route and
+/// data to the `on_evict` callback; pinned routes are never evicted and
+/// don't count; adding a route again replaces its data, and pins it if it's
+/// added pinned; an eviction rebuilds the trie, so it costs as much as adding
+/// every route again
+///
+/// # Example
+///
+/// ```
+/// use path_router::{EvictionPolicy, Router};
+/// use std::sync::{Arc, Mutex};
+///
+/// let mut router = Router::bounded(2, EvictionPolicy::Lru);
+/// let evicted = Arc::new(Mutex::new(vec![]));
+/// let log = evicted.clone();
+/// router.on_evict(move |route, data| log.lock().unwrap().push((route.to_string(), data)));
+///
+/// router.add_pinned("/health", 0).unwrap();
+/// router.add("/a/:id", 1).unwrap();
+/// router.add("/b/:id", 2).unwrap();
+/// router.recognize("/a/1").unwrap();
+/// router.add("/c/:id", 3).unwrap();
+///
+/// assert_eq!(*evicted.lock().unwrap(), [(String::from("/b/:id"), 2)]);
+/// assert!(router.recognize("/b/1").is_none());
+/// assert_eq!(router.len(), 3);
+/// ```
+pub struct BoundedRouter<T> {
+    router: Router<usize>, // the index of the slot of every route
+    slots: Vec<Slot<T>>,
+    max_routes: usize,
+    policy: EvictionPolicy,
+    tick: AtomicU64,
+    on_evict: Option<OnEvict<T>>,
+}
+
+struct Slot<T> {
+    route: String,
+    data: T,
+    pinned: bool,
+    used: AtomicU64,
+}
+
+impl<T> Router<T> {
+    /// a router holding at most `max_routes` dynamic routes, see
+    /// `BoundedRouter`
+    ///
+    /// panics if `max_routes` is 0, the route being added is never evicted so
+    /// at least one must fit
+    pub fn bounded(max_routes: usize, policy: EvictionPolicy) -> BoundedRouter<T> {
+        assert!(max_routes > 0, "a bounded router holds at least one route");
+        BoundedRouter {
+            router: Router::default(),
+            slots: vec![],
+            max_routes,
+            policy,
+            tick: AtomicU64::new(0),
+            on_evict: None,
+        }
+    }
+}
+
+impl<T> BoundedRouter<T> {
+    /// add a dynamic route like `Router::add`, evicting one past the capacity
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.insert(route, data, false)
+    }
+
+    /// add a route never evicted
+    pub fn add_pinned(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.insert(route, data, true)
+    }
+
+    fn insert(&mut self, route: &str, data: T, pinned: bool) -> Result<&mut T, Error> {
+        let next = self.slots.len();
+        let idx = *self.router.entry(route)?.or_insert(next);
+        if idx < next {
+            // a route added again keeps its slot, it isn't one more route
+            let used = self.next_tick();
+            let slot = &mut self.slots[idx];
+            slot.data = data;
+            slot.pinned |= pinned;
+            slot.used.store(used, Ordering::Relaxed);
+            return Ok(&mut slot.data);
+        }
+        self.slots.push(Slot {
+            route: String::from(route),
+            data,
+            pinned,
+            used: AtomicU64::new(self.next_tick()),
+        });
+        let dynamic = self.slots.iter().filter(|s| !s.pinned).count();
+        if !pinned && dynamic > self.max_routes {
+            self.evict();
+        }
+        let slot = self.slots.last_mut().expect("route just added");
+        Ok(&mut slot.data)
+    }
+
+    fn evict(&mut self) {
+        let candidates = self.slots[..self.slots.len() - 1].iter().enumerate();
+        let victim = match self.policy {
+            EvictionPolicy::Lru => candidates
+                .filter(|(_, s)| !s.pinned)
+                .min_by_key(|(_, s)| s.used.load(Ordering::Relaxed))
+                .map(|(i, _)| i),
+        };
+        let victim = match victim {
+            Some(victim) => self.slots.remove(victim),
+            None => return,
+        };
+        let mut router = Router::with_options(self.router.options().clone());
+        for (i, slot) in self.slots.iter().enumerate() {
+            router.add(&slot.route, i).expect("route added before");
+        }
+        self.router = router;
+        if let Some(ref mut on_evict) = self.on_evict {
+            on_evict(&victim.route, victim.data);
+        }
+    }
+
+    /// recognize a path like `Router::recognize`, the matched route counts as
+    /// recently used
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        let m = self.router.recognize(path)?;
+        let slot = &self.slots[*m.data];
+        slot.used.store(self.next_tick(), Ordering::Relaxed);
+        Some(Match {
+            data: &slot.data,
+            params: m.params,
+            route: m.route,
+        })
+    }
+
+    /// call `f` with the route and the data of every evicted route
+    pub fn on_evict<F: FnMut(&str, T) + Send + Sync + 'static>(&mut self, f: F) {
+        self.on_evict = Some(Box::new(f));
+    }
+
+    /// number of routes, pinned ones included
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn max_routes(&self) -> usize {
+        self.max_routes
+    }
+
+    fn next_tick(&self) -> u64 {
+        self.tick.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BoundedRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let routes = self
+            .slots
+            .iter()
+            .map(|s| (&s.route, &s.data, s.pinned))
+            .collect::<Vec<_>>();
+        f.debug_struct("BoundedRouter")
+            .field("routes", &routes)
+            .field("max_routes", &self.max_routes)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use core::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn lru_eviction() {
+        let mut router = Router::bounded(2, EvictionPolicy::Lru);
+        let evictions = Arc::new(AtomicUsize::new(0));
+        let count = evictions.clone();
+        router.on_evict(move |_, data: usize| {
+            count.fetch_add(data, Ordering::Relaxed);
+        });
+
+        router.add("/a", 1).unwrap();
+        router.add("/b/:id", 10).unwrap();
+        router.add_pinned("/pinned", 1000).unwrap();
+        assert!(router.add("/b/:name", 0).is_err());
+        assert_eq!(router.recognize("/b/x").unwrap().params["id"], "x");
+
+        *router.add("/c", 100).unwrap() += 1;
+        assert_eq!(evictions.load(Ordering::Relaxed), 1);
+        assert!(router.recognize("/a").is_none());
+        assert_eq!(*router.recognize("/c").unwrap().data, 101);
+
+        router.recognize("/b/y").unwrap();
+        router.add("/d", 5).unwrap();
+        assert_eq!(evictions.load(Ordering::Relaxed), 102);
+        assert_eq!(*router.recognize("/pinned").unwrap().data, 1000);
+        assert_eq!(router.len(), 3);
+
+        let mut one = Router::bounded(1, EvictionPolicy::Lru);
+        one.add("/a", 1).unwrap();
+        one.add("/b", 2).unwrap();
+        assert!(one.recognize("/a").is_none());
+        assert_eq!(*one.recognize("/b").unwrap().data, 2);
+    }
+
+    #[test]
+    fn added_again() {
+        let mut router = Router::bounded(2, EvictionPolicy::Lru);
+        let evictions = Arc::new(AtomicUsize::new(0));
+        let count = evictions.clone();
+        router.on_evict(move |_, _: usize| {
+            count.fetch_add(1, Ordering::Relaxed);
+        });
+        router.add("/a", 1).unwrap();
+        router.add("/b", 2).unwrap();
+        router.recognize("/a").unwrap();
+        assert_eq!(*router.add("/a", 3).unwrap(), 3);
+        assert_eq!(evictions.load(Ordering::Relaxed), 0);
+        assert_eq!(*router.recognize("/a").unwrap().data, 3);
+        assert_eq!(*router.recognize("/b").unwrap().data, 2);
+        assert_eq!(router.len(), 2);
+
+        let mut router = Router::bounded(3, EvictionPolicy::Lru);
+        router.add("/a", 1).unwrap();
+        router.add("/a", 2).unwrap();
+        assert_eq!(
+            format!("{:?}", router),
+            r#"BoundedRouter { routes: [("/a", 2, false)], max_routes: 3, policy: Lru }"#
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity() {
+        Router::<usize>::bounded(0, EvictionPolicy::Lru);
+    }
+}
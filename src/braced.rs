@@ -0,0 +1,170 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{BraceSyntax, Error, Options, ParamType, Reason, Router};
+
+impl<T> Router<T> {
+    /// set a route written with braces like actix and tide, `/user/{id}`
+    ///
+    /// `{name}` is a parameter, `{name:*}` or `{name:.*}` a catchall, a type
+    /// or a `SegmentMatcher` after the colon like `{id:u32}` constrains the
+    /// parameter like `:id<u32>`, and with feature `regex` any other pattern
+    /// like `{id:\d+}` of a whole segment is a regex, compiled once when the
+    /// route is added; parameters with a regex or a type are tried before
+    /// plain ones whatever the order of adding; braces may mix with literals
+    /// in a segment like `v{major}.{minor}`, the other rules are the ones of
+    /// `Router::add`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add_braced("/user/{id:u32}", 1).unwrap();
+    /// router.add_braced("/files/{path:*}", 2).unwrap();
+    /// router.add_braced("/api/v{major}.{minor}", 3).unwrap();
+    ///
+    /// assert_eq!(router.list_routes(), ["/api/v:major.:minor", "/files/*path", "/user/:id<u32>"]);
+    /// assert_eq!(router.recognize("/files/a/b").unwrap().params["path"], "a/b");
+    /// assert!(router.recognize("/user/x").is_none());
+    /// ```
+    pub fn add_braced(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        let parsed = self.parse_route_with(&BraceSyntax, route, None)?;
+        let node = self.add_parsed(route, parsed)?;
+        node.set_data(data);
+        match node.data {
+            Some(ref mut d) => Ok(d),
+            None => panic!("impossible"),
+        }
+    }
+}
+
+// the route in the syntax of `add` with the regexes of its parameters by name
+pub(crate) fn unbrace(
+    pattern: &str,
+    options: &Options,
+) -> Result<(String, Vec<(String, String)>), Error> {
+    let mut route = String::new();
+    let mut regexes = vec![];
+    let mut segments = pattern.split('/');
+    if segments.next() != Some("") {
+        return Err(Error::new(Reason::NoLeadingSlash));
+    }
+    for (i, segment) in segments.enumerate() {
+        let fault = |reason| Error::new(reason).at(i);
+        route.push('/');
+        let mut rest = segment;
+        let whole = segment.starts_with('{') && braced_len(segment) == Some(segment.len());
+        while !rest.is_empty() {
+            let open = rest.find('{').unwrap_or(rest.len());
+            let literal = &rest[..open];
+            if literal.contains([':', '*', '}']) {
+                return Err(fault(Reason::InvalidFormat));
+            }
+            route.push_str(literal);
+            rest = &rest[open..];
+            if rest.is_empty() {
+                break;
+            }
+            let end = braced_len(rest).ok_or_else(|| fault(Reason::InvalidFormat))?;
+            let inner = &rest[1..end - 1];
+            rest = &rest[end..];
+            let (name, kind) = match inner.find(':') {
+                Some(i) => (&inner[..i], Some(&inner[i + 1..])),
+                None => (inner, None),
+            };
+            match kind {
+                None => route.push_str(&format!(":{}", name)),
+                Some("*") | Some(".*") | Some(".+") if whole => {
+                    route.push_str(&format!("*{}", name))
+                }
+                Some(ty) if is_param_kind(ty, options) => {
+                    route.push_str(&format!(":{}<{}>", name, ty))
+                }
+                Some(re) if whole => {
+                    route.push_str(&format!(":{}", name));
+                    regexes.push((String::from(name), String::from(re)));
+                }
+                // a catchall or a regex takes a whole segment
+                Some(_) => return Err(fault(Reason::InvalidTemplate)),
+            }
+        }
+    }
+    Ok((route, regexes))
+}
+
+// whether a name like `u32` is a type or a matcher of parameters
+fn is_param_kind(name: &str, options: &Options) -> bool {
+    ParamType::from_name(name).is_ok() || options.segment_matchers.iter().any(|m| m.name() == name)
+}
+
+// the length of the brace group starting the text, braces nest in regexes
+// like `{year:\d{4}}`
+fn braced_len(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(i + 1),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braced_routes() {
+        let mut router = Router::default();
+        router.add_braced("/", 0).unwrap();
+        router.add_braced("/users/{id}/repos/{name}", 1).unwrap();
+        router.add_braced("/static/{tail:.*}", 2).unwrap();
+        router.add_braced("/d/{date:yyyy-mm-dd}", 3).unwrap();
+        router.add_braced("/f/{name}.{ext}", 4).unwrap();
+        assert_eq!(
+            router.list_routes(),
+            [
+                "/",
+                "/d/:date<yyyy-mm-dd>",
+                "/f/:name.:ext",
+                "/static/*tail",
+                "/users/:id/repos/:name",
+            ]
+        );
+        let m = router.recognize("/f/a.tar").unwrap();
+        assert_eq!((&m.params["name"][..], &m.params["ext"][..]), ("a", "tar"));
+
+        for route in [
+            "users/{id}",
+            "/users/{id",
+            "/users/id}",
+            "/users/:id",
+            "/a/{x:*}.txt",
+            "/a/{x:*}/{y:*}",
+            "/a/v{x:\\d+}",
+        ] {
+            assert!(router.add_braced(route, 9).is_err(), "{}", route);
+        }
+        #[cfg(feature = "regex")]
+        {
+            router.add_braced("/y/{year:\\d{4}}", 5).unwrap();
+            assert_eq!(*router.recognize("/y/2024").unwrap().data, 5);
+            assert!(router.recognize("/y/24").is_none());
+
+            router.add_braced("/p/{slug}/{page}", 6).unwrap();
+            router.add_braced("/p/{id:\\d+}/{page:[a-z]+}", 7).unwrap();
+            router.add_braced("/p/{id:\\d+}/{n:\\d+}", 8).unwrap();
+            let m = router.recognize("/p/12/intro").unwrap();
+            assert_eq!((*m.data, &m.params["id"][..]), (7, "12"));
+            assert_eq!(*router.recognize("/p/12/3").unwrap().data, 8);
+            assert_eq!(*router.recognize("/p/12/A").unwrap().data, 6);
+            assert_eq!(*router.recognize("/p/x/intro").unwrap().data, 6);
+            assert!(router.add_braced("/q/{id:(}", 9).is_err());
+        }
+        #[cfg(not(feature = "regex"))]
+        assert!(router.add_braced("/y/{year:\\d{4}}", 5).is_err());
+    }
+}
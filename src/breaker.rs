@@ -0,0 +1,193 @@
+use core::fmt;
+use core::sync::atomic::{AtomicU32, AtomicU64, AtomicU8, Ordering};
+
+/// state of a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// requests pass
+    Closed,
+    /// requests are refused
+    Open,
+    /// trial requests pass to tell whether to close again
+    HalfOpen,
+}
+
+/// the circuit breaker state of a route, shared through
+/// `RouteInfo::breaker` so a match carries it
+///
+/// the cell only holds the state, its counters and when it opened, the
+/// breaker policy is the caller's; every operation is a single atomic one,
+/// times are whatever clock the caller counts in
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Breaker, BreakerState, RouteInfo, Router};
+/// use std::sync::Arc;
+///
+/// let mut router = Router::default();
+/// let info = RouteInfo {
+///     breaker: Some(Arc::new(Breaker::new())),
+///     ..RouteInfo::default()
+/// };
+/// router.add_with_info("/upstream/:id", info, 1).unwrap();
+///
+/// let breaker = router.recognize("/upstream/1").unwrap().route.breaker.clone().unwrap();
+/// if breaker.record_failure() >= 1 {
+///     breaker.open(1_000);
+/// }
+/// let m = router.recognize("/upstream/2").unwrap();
+/// assert_eq!(m.route.breaker.as_ref().unwrap().state(), BreakerState::Open);
+/// assert!(breaker.transition(BreakerState::Open, BreakerState::HalfOpen));
+/// ```
+#[derive(Default)]
+pub struct Breaker {
+    state: AtomicU8,
+    failures: AtomicU32,
+    successes: AtomicU32,
+    opened_at: AtomicU64,
+}
+
+impl BreakerState {
+    fn from_u8(state: u8) -> BreakerState {
+        match state {
+            1 => BreakerState::Open,
+            2 => BreakerState::HalfOpen,
+            _ => BreakerState::Closed,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::Open => 1,
+            BreakerState::HalfOpen => 2,
+        }
+    }
+}
+
+impl Breaker {
+    /// a closed breaker with no counts
+    pub fn new() -> Breaker {
+        Breaker::default()
+    }
+
+    pub fn state(&self) -> BreakerState {
+        BreakerState::from_u8(self.state.load(Ordering::Acquire))
+    }
+
+    /// switch from `from` to `to`, false if the state isn't `from`, so only
+    /// one of racing callers makes a transition; the counters are reset
+    pub fn transition(&self, from: BreakerState, to: BreakerState) -> bool {
+        let switched = self
+            .state
+            .compare_exchange(
+                from.to_u8(),
+                to.to_u8(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_ok();
+        if switched {
+            self.reset_counts();
+        }
+        switched
+    }
+
+    /// open the breaker at time `now` whatever its state, the counters are
+    /// reset
+    pub fn open(&self, now: u64) {
+        self.opened_at.store(now, Ordering::Release);
+        self.state
+            .store(BreakerState::Open.to_u8(), Ordering::Release);
+        self.reset_counts();
+    }
+
+    /// close the breaker whatever its state, the counters are reset
+    pub fn close(&self) {
+        self.state
+            .store(BreakerState::Closed.to_u8(), Ordering::Release);
+        self.reset_counts();
+    }
+
+    /// when the breaker last opened with `open`
+    pub fn opened_at(&self) -> u64 {
+        self.opened_at.load(Ordering::Acquire)
+    }
+
+    /// count a failure, the failures counted since the last reset
+    pub fn record_failure(&self) -> u32 {
+        self.failures
+            .fetch_add(1, Ordering::AcqRel)
+            .saturating_add(1)
+    }
+
+    /// count a success, the successes counted since the last reset
+    pub fn record_success(&self) -> u32 {
+        self.successes
+            .fetch_add(1, Ordering::AcqRel)
+            .saturating_add(1)
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures.load(Ordering::Acquire)
+    }
+
+    pub fn successes(&self) -> u32 {
+        self.successes.load(Ordering::Acquire)
+    }
+
+    pub fn reset_counts(&self) {
+        self.failures.store(0, Ordering::Release);
+        self.successes.store(0, Ordering::Release);
+    }
+}
+
+/// breakers are equal when they are the same cell
+impl PartialEq for Breaker {
+    fn eq(&self, other: &Breaker) -> bool {
+        core::ptr::eq(self, other)
+    }
+}
+
+impl fmt::Debug for Breaker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Breaker")
+            .field("state", &self.state())
+            .field("failures", &self.failures())
+            .field("successes", &self.successes())
+            .field("opened_at", &self.opened_at())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaker_states() {
+        let breaker = Breaker::new();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        assert_eq!((breaker.record_failure(), breaker.record_failure()), (1, 2));
+        assert_eq!(breaker.record_success(), 1);
+
+        assert!(!breaker.transition(BreakerState::Open, BreakerState::HalfOpen));
+        breaker.open(42);
+        assert_eq!(
+            (breaker.state(), breaker.opened_at()),
+            (BreakerState::Open, 42)
+        );
+        assert_eq!((breaker.failures(), breaker.successes()), (0, 0));
+
+        assert!(breaker.transition(BreakerState::Open, BreakerState::HalfOpen));
+        assert!(!breaker.transition(BreakerState::Open, BreakerState::HalfOpen));
+        breaker.record_success();
+        breaker.close();
+        assert_eq!(
+            (breaker.state(), breaker.successes()),
+            (BreakerState::Closed, 0)
+        );
+        assert_ne!(breaker, Breaker::new());
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, FrozenRouter, Lint, LintKind, Options, RouteInfo, Router};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+/// routes collected to be added at once, a router is only built when every
+/// route can be added and none is shadowed
+///
+/// # Example
+///
+/// ```
+/// use path_router::{BuildProblem, RouterBuilder};
+/// let router = RouterBuilder::new()
+///     .route("/users/:id", 1)
+///     .route("/users/me", 2)
+///     .build()
+///     .unwrap();
+/// assert_eq!(*router.recognize("/users/me").unwrap().data, 2);
+///
+/// let err = RouterBuilder::new()
+///     .route("/users/:id", 1)
+///     .route("users", 2)
+///     .route("/users/:uid", 3)
+///     .route("/n/:id<u32>", 4)
+///     .route("/n/:small<u8>", 5)
+///     .build()
+///     .unwrap_err();
+/// assert_eq!(err.problems.len(), 3);
+/// assert!(matches!(err.problems[0], BuildProblem::Invalid { index: 1, .. }));
+/// ```
+pub struct RouterBuilder<T> {
+    options: Options,
+    routes: Vec<(String, RouteInfo, T)>,
+}
+
+/// problems of the routes of a `RouterBuilder`
+#[derive(Debug)]
+pub struct BuildError {
+    /// invalid and conflicting routes in the order they were given, then the
+    /// shadowed ones
+    pub problems: Vec<BuildProblem>,
+}
+
+#[derive(Debug)]
+pub enum BuildProblem {
+    /// the route at `index` can't be added: its format is invalid or it
+    /// collides with an earlier route
+    Invalid {
+        index: usize,
+        route: String,
+        error: Error,
+    },
+    /// a route which never matches, see `Router::lint`
+    Shadowed(Lint),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} invalid routes", self.problems.len())?;
+        for p in self.problems.iter() {
+            match p {
+                BuildProblem::Invalid { error, .. } => write!(f, "\n  {}", error)?,
+                BuildProblem::Shadowed(lint) => write!(f, "\n  {}", lint)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for BuildError {}
+
+impl<T> Default for RouterBuilder<T> {
+    fn default() -> RouterBuilder<T> {
+        RouterBuilder::with_options(Options::default())
+    }
+}
+
+impl<T> RouterBuilder<T> {
+    pub fn new() -> RouterBuilder<T> {
+        RouterBuilder::default()
+    }
+
+    /// a builder of a router with options
+    pub fn with_options(options: Options) -> RouterBuilder<T> {
+        RouterBuilder {
+            options,
+            routes: vec![],
+        }
+    }
+
+    /// collect a route, it's checked by `build`
+    pub fn route(self, route: &str, data: T) -> Self {
+        self.route_with_info(route, RouteInfo::default(), data)
+    }
+
+    /// collect a route with information like `Router::add_with_info`
+    pub fn route_with_info(mut self, route: &str, info: RouteInfo, data: T) -> Self {
+        self.routes.push((String::from(route), info, data));
+        self
+    }
+
+    /// add the routes in order and check them all, the router is handed
+    /// back only without problems
+    pub fn build(self) -> Result<Router<T>, BuildError> {
+        let mut router = Router::with_options(self.options);
+        let mut problems = vec![];
+        for (index, (route, info, data)) in self.routes.into_iter().enumerate() {
+            if let Err(error) = router.add_with_info(&route, info, data) {
+                problems.push(BuildProblem::Invalid {
+                    index,
+                    route,
+                    error,
+                });
+            }
+        }
+        let shadowed = router
+            .lint()
+            .into_iter()
+            .filter(|l| matches!(l.kind, LintKind::Unreachable { .. }));
+        problems.extend(shadowed.map(BuildProblem::Shadowed));
+        match problems.is_empty() {
+            true => Ok(router),
+            false => Err(BuildError { problems }),
+        }
+    }
+
+    /// `build` the router and freeze it, see `Router::freeze`
+    pub fn build_frozen(self) -> Result<FrozenRouter<T>, BuildError> {
+        self.build().map(Router::freeze)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Reason;
+
+    #[test]
+    fn build_routers() {
+        let builder = (0..10).fold(RouterBuilder::new(), |b, i| {
+            b.route(&format!("/items/{}/:id", i), i)
+        });
+        let router = builder.route("/", 10).build_frozen().unwrap();
+        assert_eq!(*router.recognize("/items/7/x").unwrap().data, 7);
+        assert_eq!(*router.recognize("/").unwrap().data, 10);
+
+        let err = RouterBuilder::new()
+            .route("/a/:x", 1)
+            .route("/a/", 2)
+            .route("/a/:y", 3)
+            .route("/b/:n<u32>", 4)
+            .route("/b/:m<u16>/c", 5)
+            .route("/b/:k<u8>", 6)
+            .route("/b/:id-x", 7)
+            .build()
+            .unwrap_err();
+        let invalid = err
+            .problems
+            .iter()
+            .filter_map(|p| match p {
+                BuildProblem::Invalid { index, .. } => Some(*index),
+                BuildProblem::Shadowed(_) => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(invalid, [1, 2, 6]);
+        assert!(matches!(
+            err.problems[1],
+            BuildProblem::Invalid {
+                error: Error {
+                    reason: Reason::Conflict { ref existing },
+                    ..
+                },
+                ..
+            } if existing == "/a/:x"
+        ));
+        assert!(matches!(
+            err.problems[3],
+            BuildProblem::Shadowed(Lint { ref route, .. }) if route == "/b/:k<u8>"
+        ));
+        assert_eq!(err.problems.len(), 4);
+        assert!(err
+            .to_string()
+            .starts_with("4 invalid routes\n  invalid route /a/: trailing '/'\n"));
+    }
+}
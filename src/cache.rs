@@ -0,0 +1,334 @@
+use crate::{route_params, Error, Landing, Match, Params, Recorder, Router, Step, Trail};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// a router remembering the resolutions of the paths it recognized last
+///
+/// a cached path skips the search of the trie, its match is rebuilt by
+/// following the recorded steps; paths are cached as given, before
+/// normalization, misses are cached too; the least recently used path is
+/// dropped once `capacity` paths are cached and the whole cache is dropped
+/// on any change of the router
+///
+/// with a `Recorder` set, every recognition adds 1 to the counter
+/// `path_router.cache.hits` or `path_router.cache.misses` and sets the gauge
+/// `path_router.cache.entries` to the number of cached paths
+///
+/// # Example
+///
+/// ```
+/// use path_router::{CachedRouter, Router};
+/// let mut router = CachedRouter::new(Router::default(), 2);
+/// router.add("/user/:id", 1).unwrap();
+///
+/// let m = router.recognize("/user/7").unwrap();
+/// assert_eq!((*m.data, &m.params["id"][..]), (1, "7"));
+/// assert_eq!(router.cached(), 1);
+///
+/// router.router_mut().add("/user/7", 2).unwrap();
+/// assert_eq!(router.cached(), 0);
+/// assert_eq!(*router.recognize("/user/7").unwrap().data, 2);
+/// ```
+pub struct CachedRouter<T> {
+    router: Router<T>,
+    cache: Mutex<Lru>,
+    recorder: Option<Arc<dyn Recorder>>,
+}
+
+// where a path lands, enough to rebuild its match without a search
+#[derive(Debug, Clone)]
+enum Hit {
+    Route {
+        steps: Vec<Step>,
+        optional: bool, // the data is in the optional child of the node
+        params: Params,
+    },
+    Fallback(Vec<Step>),
+    Miss,
+}
+
+#[derive(Debug)]
+struct Lru {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (u64, Hit)>,
+    order: BTreeMap<u64, String>, // paths by their last use
+}
+
+impl Lru {
+    fn get(&mut self, path: &str) -> Option<Hit> {
+        let entry = self.entries.get_mut(path)?;
+        self.order.remove(&entry.0);
+        self.tick += 1;
+        entry.0 = self.tick;
+        self.order.insert(self.tick, String::from(path));
+        Some(entry.1.clone())
+    }
+
+    // a path missed by two threads at once is inserted twice, the second
+    // time it's updated in place
+    fn insert(&mut self, path: &str, hit: Hit) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some(entry) = self.entries.get_mut(path) {
+            self.order.remove(&entry.0);
+            *entry = (self.tick, hit);
+            self.order.insert(self.tick, String::from(path));
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.order.insert(self.tick, String::from(path));
+        self.entries.insert(String::from(path), (self.tick, hit));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+impl<T> CachedRouter<T> {
+    /// wrap a router with a cache of at most `capacity` paths
+    pub fn new(router: Router<T>, capacity: usize) -> CachedRouter<T> {
+        CachedRouter {
+            router,
+            cache: Mutex::new(Lru {
+                capacity,
+                tick: 0,
+                entries: HashMap::new(),
+                order: BTreeMap::new(),
+            }),
+            recorder: None,
+        }
+    }
+
+    /// report cache hits, misses and entries to `recorder`
+    pub fn set_recorder(&mut self, recorder: Arc<dyn Recorder>) {
+        self.recorder = Some(recorder);
+    }
+
+    /// recognize a path like `Router::recognize`
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        let hit = self.lock().get(path);
+        let hit = match hit {
+            Some(hit) => {
+                self.record("path_router.cache.hits");
+                hit
+            }
+            None => {
+                let hit = self.router.resolve_hit(path);
+                self.lock().insert(path, hit.clone());
+                self.record("path_router.cache.misses");
+                hit
+            }
+        };
+        match hit {
+            Hit::Route {
+                steps,
+                optional,
+                params,
+            } => {
+                let mut node = *self.router.nodes(&steps).last()?;
+                if optional {
+                    node = node.optional_child()?;
+                }
+                Some(Match {
+                    data: node.data.as_ref()?,
+                    params,
+                    route: &node.info,
+                })
+            }
+            Hit::Fallback(steps) => {
+                let node = *self.router.nodes(&steps).last()?;
+                Some(Match {
+                    data: node.fallback.as_ref()?,
+                    params: Params::new(),
+                    route: &node.info,
+                })
+            }
+            Hit::Miss => None,
+        }
+    }
+
+    /// add a route like `Router::add`, the cache is dropped
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.router_mut().add(route, data)
+    }
+
+    pub fn router(&self) -> &Router<T> {
+        &self.router
+    }
+
+    /// the router to change, the cache is dropped
+    pub fn router_mut(&mut self) -> &mut Router<T> {
+        self.clear();
+        &mut self.router
+    }
+
+    pub fn into_inner(self) -> Router<T> {
+        self.router
+    }
+
+    /// number of cached paths
+    pub fn cached(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn record(&self, counter: &str) {
+        if let Some(ref recorder) = self.recorder {
+            recorder.counter(counter, 1);
+            recorder.gauge("path_router.cache.entries", self.cached() as f64);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        // the cache is consistent between calls, a panic can't leave it torn
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CachedRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedRouter")
+            .field("router", &self.router)
+            .field("cache", &self.cache)
+            .finish()
+    }
+}
+
+impl<T> Router<T> {
+    // search a path and record where it lands
+    fn resolve_hit(&self, path: &str) -> Hit {
+        let normalized = match self.options.normalize_path(path) {
+            Ok(path) => path,
+            Err(_) => return Hit::Miss,
+        };
+        let mut trail = Trail::new(true);
+        let node = self.find(&normalized, &mut trail);
+        let route = node.and_then(Router::data_node);
+        match (
+            self.landing(path, &normalized, route, trail.fallback.is_some()),
+            route,
+        ) {
+            (Landing::Route, Some(data_node)) => Hit::Route {
+                steps: trail.steps.unwrap_or_default(),
+                optional: node.is_some_and(|n| n.data.is_none()),
+                params: route_params(&data_node.params, &data_node.info, trail.values),
+            },
+            (Landing::Fallback, _) => Hit::Fallback(trail.fallback_steps),
+            _ => Hit::Miss,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cached_router() {
+        let mut router = Router::default();
+        router.add("/a/b/c", 1).unwrap();
+        router.add("/user/:id<u32>", 2).unwrap();
+        router.add("/user/:name/:tab?", 3).unwrap();
+        router.add("/files/*path", 4).unwrap();
+        router.set_fallback("/user", 5).unwrap();
+        let paths = [
+            "/a/b/c",
+            "/user/1",
+            "/user/bob",
+            "/user/bob/x",
+            "/files/a/b",
+            "/user/1/2/3",
+            "/x",
+        ];
+        let expected = paths
+            .iter()
+            .map(|p| router.recognize(p).map(|m| (*m.data, m.params)))
+            .collect::<Vec<_>>();
+
+        let router = CachedRouter::new(router, 4);
+        for _ in 0..2 {
+            let found = paths
+                .iter()
+                .map(|p| router.recognize(p).map(|m| (*m.data, m.params)))
+                .collect::<Vec<_>>();
+            assert_eq!(found, expected);
+        }
+        assert_eq!(router.cached(), 4);
+
+        // the least recently used path goes first
+        router.recognize("/files/a/b");
+        router.recognize("/a/b/c");
+        let lru = router.lock();
+        assert!(lru.entries.contains_key("/files/a/b"));
+        assert!(!lru.entries.contains_key("/user/bob/x"));
+        drop(lru);
+
+        // a path inserted again, as by two threads missing it at once,
+        // keeps one place in the order and evicts nothing
+        let mut lru = router.lock();
+        lru.insert("/a/b/c", Hit::Miss);
+        assert_eq!((lru.entries.len(), lru.order.len()), (4, 4));
+        lru.insert("/x", Hit::Miss);
+        assert!(lru.entries.contains_key("/a/b/c"));
+        assert_eq!((lru.entries.len(), lru.order.len()), (4, 4));
+        drop(lru);
+
+        let mut router = router;
+        router.add("/x", 6).unwrap();
+        assert_eq!(router.cached(), 0);
+        assert_eq!(*router.recognize("/x").unwrap().data, 6);
+
+        let router = CachedRouter::new(router.into_inner(), 0);
+        assert_eq!(*router.recognize("/user/7").unwrap().data, 2);
+        assert_eq!(router.cached(), 0);
+    }
+
+    #[derive(Default)]
+    struct Log(Mutex<Vec<String>>);
+
+    impl Recorder for Log {
+        fn counter(&self, name: &str, value: u64) {
+            self.0.lock().unwrap().push(format!("{} +{}", name, value));
+        }
+
+        fn gauge(&self, name: &str, value: f64) {
+            self.0.lock().unwrap().push(format!("{} ={}", name, value));
+        }
+    }
+
+    #[test]
+    fn cache_metrics() {
+        let mut router = Router::default();
+        router.add("/a", 1).unwrap();
+        let mut router = CachedRouter::new(router, 4);
+        let log = Arc::new(Log::default());
+        router.set_recorder(log.clone());
+        router.recognize("/a");
+        router.recognize("/a");
+        router.recognize("/b");
+        assert_eq!(
+            *log.0.lock().unwrap(),
+            [
+                "path_router.cache.misses +1",
+                "path_router.cache.entries =1",
+                "path_router.cache.hits +1",
+                "path_router.cache.entries =1",
+                "path_router.cache.misses +1",
+                "path_router.cache.entries =2",
+            ]
+        );
+    }
+}
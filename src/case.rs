@@ -0,0 +1,132 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{next_segment, NodeRef, Options, Router};
+
+impl<T> Router<T> {
+    // the path with the static segments cased like the first route taking it
+    // when ASCII case is ignored, the parameters are kept as written; a path
+    // a route takes as written, e.g. turned down by a guard, has no other
+    // casing
+    pub(crate) fn case_corrected(&self, path: &str) -> Option<String> {
+        case_corrected(&self.options, self, path)
+    }
+}
+
+// `Router::case_corrected` below any root, the frozen router's too
+pub(crate) fn case_corrected<'a, T: 'a, N: NodeRef<'a, T>>(
+    options: &Options,
+    root: N,
+    path: &str,
+) -> Option<String> {
+    let mut out = vec![];
+    if !probe_case(options, root, &[], path, 1, &mut out) {
+        return None;
+    }
+    let corrected = format!("/{}", out.join("/"));
+    match corrected == path.trim_end_matches('/') || corrected == path {
+        true => None,
+        false => Some(corrected),
+    }
+}
+
+// like `descend` with static segments compared ignoring ASCII case, the
+// segments of the corrected path go to `out`
+fn probe_case<'a, T: 'a, N: NodeRef<'a, T>>(
+    options: &Options,
+    node: N,
+    pending: &[String],
+    path: &str,
+    start: usize,
+    out: &mut Vec<String>,
+) -> bool {
+    let (offset, raw, next) = match next_segment(path, start) {
+        Some(next) => next,
+        None => return pending.is_empty() && node.data_node().is_some(),
+    };
+    let segment = match options.decode(raw) {
+        Some(segment) => segment,
+        None => return false,
+    };
+
+    let mark = out.len();
+    if let Some((first, rest)) = pending.split_first() {
+        if !first.eq_ignore_ascii_case(&segment) {
+            return false;
+        }
+        out.push(first.clone());
+        return probe_case(options, node, rest, path, next, out) || {
+            out.truncate(mark);
+            false
+        };
+    }
+
+    for child in (0..node.statics()).map(|idx| node.nth_static(idx)) {
+        if child.key().eq_ignore_ascii_case(&segment) {
+            out.push(String::from(child.key()));
+            if probe_case(options, child, child.tail(), path, next, out) {
+                return true;
+            }
+            out.truncate(mark);
+        }
+    }
+
+    let params = (0..node.params()).map(|idx| node.nth_param(idx));
+    for child in params.filter(|c| c.accepts(&segment)) {
+        out.push(String::from(raw));
+        if probe_case(options, child, &[], path, next, out) {
+            return true;
+        }
+        out.truncate(mark);
+    }
+
+    let child = match node.catch_all() {
+        Some(child) => child,
+        None => return false,
+    };
+    if child.statics() + child.params() > 0 {
+        for (i, _) in path[offset..].rmatch_indices('/') {
+            let end = offset + i;
+            out.push(String::from(&path[offset..end]));
+            if options.takes_catch_all(i) && probe_case(options, child, &[], path, end + 1, out) {
+                return true;
+            }
+            out.truncate(mark);
+        }
+    }
+    if child.data_node().is_some() && options.takes_catch_all(path.len() - offset) {
+        out.push(String::from(&path[offset..]));
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_corrections() {
+        let mut router = Router::default();
+        router.add("/about/us", 1).unwrap();
+        router.add("/Users/:name/Repos", 2).unwrap();
+        router.add("/files/*path/RAW", 3).unwrap();
+        router.add("/docs/*path", 4).unwrap();
+        router.add("/v1/Items/all", 5).unwrap();
+
+        let corrected = |path| router.case_corrected(path);
+        assert_eq!(corrected("/About/Us").as_deref(), Some("/about/us"));
+        assert_eq!(corrected("/about/us"), None);
+        assert_eq!(
+            corrected("/users/BoB/repos").as_deref(),
+            Some("/Users/BoB/Repos")
+        );
+        assert_eq!(
+            corrected("/Files/A/b/raw").as_deref(),
+            Some("/files/A/b/RAW")
+        );
+        assert_eq!(corrected("/DOCS/A/B/").as_deref(), Some("/docs/A/B/"));
+        assert_eq!(corrected("/V1/items/ALL").as_deref(), Some("/v1/Items/all"));
+        assert_eq!(corrected("/v1/items/none"), None);
+        assert_eq!(corrected("/other"), None);
+    }
+}
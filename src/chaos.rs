@@ -0,0 +1,130 @@
+use crate::{Match, Router};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// deterministic injection of routing failures for resilience tests
+///
+/// every lookup draws a number from a generator seeded by `seed`, a fraction
+/// `miss_rate` of lookups is answered with an injected miss and a fraction
+/// `error_rate` with an injected error, the same seed gives the same sequence
+#[derive(Debug)]
+pub struct Chaos {
+    seed: u64,
+    miss_rate: f64,
+    error_rate: f64,
+    count: AtomicU64,
+}
+
+/// result of a lookup under chaos, injected outcomes are tagged
+#[derive(Debug)]
+pub enum ChaosOutcome<M> {
+    /// the lookup ran normally
+    Routed(Option<M>),
+    /// the lookup was answered as if no route matched
+    InjectedMiss,
+    /// the lookup was answered with a routing error
+    InjectedError,
+}
+
+impl<M> ChaosOutcome<M> {
+    pub fn is_injected(&self) -> bool {
+        !matches!(self, ChaosOutcome::Routed(_))
+    }
+}
+
+impl Chaos {
+    /// rates are clamped into `[0, 1]`, misses are drawn before errors
+    pub fn new(seed: u64, miss_rate: f64, error_rate: f64) -> Chaos {
+        let miss_rate = miss_rate.clamp(0.0, 1.0);
+        Chaos {
+            seed,
+            miss_rate,
+            error_rate: error_rate.clamp(0.0, 1.0 - miss_rate),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// restart the sequence of draws
+    pub fn reset(&self) {
+        self.count.store(0, Ordering::SeqCst)
+    }
+
+    // splitmix64 over the number of draws, uniform in [0, 1)
+    fn draw(&self) -> f64 {
+        let n = self.count.fetch_add(1, Ordering::SeqCst);
+        let mut z = self
+            .seed
+            .wrapping_add(n.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<T> Router<T> {
+    /// recognize a path, a fraction of lookups is replaced by injected
+    /// failures as configured in `chaos`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Chaos, Router};
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", 1).unwrap();
+    ///
+    /// let chaos = Chaos::new(42, 0.5, 0.0);
+    /// let injected = (0..1000)
+    ///     .filter(|_| router.recognize_chaos("/user/1", &chaos).is_injected())
+    ///     .count();
+    /// assert!(injected > 400 && injected < 600);
+    /// ```
+    pub fn recognize_chaos<'a>(
+        &'a self,
+        path: &str,
+        chaos: &Chaos,
+    ) -> ChaosOutcome<Match<'a, &'a T>> {
+        let x = chaos.draw();
+        if x < chaos.miss_rate {
+            ChaosOutcome::InjectedMiss
+        } else if x < chaos.miss_rate + chaos.error_rate {
+            ChaosOutcome::InjectedError
+        } else {
+            ChaosOutcome::Routed(self.recognize(path))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(router: &Router<usize>, chaos: &Chaos) -> Vec<u8> {
+        (0..100)
+            .map(|_| match router.recognize_chaos("/a", chaos) {
+                ChaosOutcome::Routed(Some(_)) => 0,
+                ChaosOutcome::Routed(None) => panic!("route missed"),
+                ChaosOutcome::InjectedMiss => 1,
+                ChaosOutcome::InjectedError => 2,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deterministic_chaos() {
+        let mut router = Router::default();
+        router.add("/a", 1).unwrap();
+
+        let chaos = Chaos::new(7, 0.2, 0.1);
+        let first = outcomes(&router, &chaos);
+        chaos.reset();
+        assert_eq!(first, outcomes(&router, &chaos));
+        assert_eq!(first, outcomes(&router, &Chaos::new(7, 0.2, 0.1)));
+        assert_ne!(first, outcomes(&router, &Chaos::new(8, 0.2, 0.1)));
+        assert!(first.contains(&0) && first.contains(&1) && first.contains(&2));
+
+        assert!(!outcomes(&router, &Chaos::new(7, 0.0, 0.0)).contains(&1));
+        assert!(outcomes(&router, &Chaos::new(7, 1.0, 1.0))
+            .iter()
+            .all(|o| *o == 1));
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Collation, Router};
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+// a node with at least this many static children looks them up by hash
+const HASHED_CHILDREN: usize = 32;
+
+// the static children of a node in the order of the collation, a large set
+// of string keys also gets a hash index built when it's first searched
+//
+// the index is dropped by every insertion, the keys and the order of the
+// children must not change through the mutable slice
+#[derive(Clone)]
+pub(crate) struct StaticChildren<T, K> {
+    nodes: Vec<Router<T, K>>,
+    #[cfg(feature = "std")]
+    index: OnceLock<HashMap<String, usize>>,
+}
+
+impl<T, K> Default for StaticChildren<T, K> {
+    fn default() -> StaticChildren<T, K> {
+        StaticChildren {
+            nodes: vec![],
+            #[cfg(feature = "std")]
+            index: OnceLock::new(),
+        }
+    }
+}
+
+impl<T, K> StaticChildren<T, K> {
+    pub(crate) fn insert(&mut self, idx: usize, node: Router<T, K>) {
+        self.invalidate();
+        self.nodes.insert(idx, node);
+    }
+
+    pub(crate) fn push(&mut self, node: Router<T, K>) {
+        self.invalidate();
+        self.nodes.push(node);
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(crate) fn pop(&mut self) -> Option<Router<T, K>> {
+        self.invalidate();
+        self.nodes.pop()
+    }
+
+    fn invalidate(&mut self) {
+        #[cfg(feature = "std")]
+        {
+            self.index = OnceLock::new();
+        }
+    }
+}
+
+impl<T> StaticChildren<T, String> {
+    // the position of the child of a segment
+    pub(crate) fn find(&self, segment: &str, collation: Collation) -> Option<usize> {
+        if collation.is_bytewise() && self.nodes.len() >= HASHED_CHILDREN {
+            // without std the children are only searched in order
+            #[cfg(feature = "std")]
+            {
+                let index = self.index.get_or_init(|| {
+                    let keys = self.nodes.iter().enumerate();
+                    keys.map(|(i, n)| (n.text.clone(), i)).collect()
+                });
+                return index.get(segment).copied();
+            }
+        }
+        self.nodes
+            .binary_search_by(|n| collation.compare(&n.text, segment))
+            .ok()
+    }
+}
+
+impl<T, K> Deref for StaticChildren<T, K> {
+    type Target = [Router<T, K>];
+
+    fn deref(&self) -> &[Router<T, K>] {
+        &self.nodes
+    }
+}
+
+impl<T, K> DerefMut for StaticChildren<T, K> {
+    fn deref_mut(&mut self) -> &mut [Router<T, K>] {
+        &mut self.nodes
+    }
+}
+
+impl<T, K> IntoIterator for StaticChildren<T, K> {
+    type Item = Router<T, K>;
+    type IntoIter = alloc::vec::IntoIter<Router<T, K>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.into_iter()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashed_children() {
+        let mut router = Router::default();
+        let codes = (0..100).map(|i| format!("c{:02}", i)).collect::<Vec<_>>();
+        for (i, code) in codes.iter().enumerate().rev() {
+            router.add(&format!("/{}/about", code), i).unwrap();
+        }
+        assert_eq!(router.static_children.len(), 100);
+        for (i, code) in codes.iter().enumerate() {
+            let m = router.recognize(&format!("/{}/about", code)).unwrap();
+            assert_eq!(*m.data, i);
+        }
+        assert!(router.static_children.index.get().is_some());
+        assert!(router.recognize("/c100/about").is_none());
+
+        // adding drops the index, the next search builds it again
+        router.add("/c5", 100).unwrap();
+        assert!(router.static_children.index.get().is_none());
+        assert_eq!(*router.recognize("/c5").unwrap().data, 100);
+        assert_eq!(*router.recognize("/c50/about").unwrap().data, 50);
+
+        let mut router = Router::with_options(crate::Options {
+            collation: Collation::ascii_case_insensitive(),
+            ..crate::Options::default()
+        });
+        for code in codes.iter() {
+            router.add(&format!("/{}", code.to_uppercase()), 1).unwrap();
+        }
+        assert!(router.recognize("/c42").is_some());
+        assert!(router.static_children.index.get().is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Error, RouteInfo, Router};
+use std::error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// a route declared in a configuration file
+///
+/// ```toml
+/// [[route]]
+/// pattern = "/user/:id"
+/// name = "user"
+/// response_schema = "#/components/schemas/User"
+/// tags = ["users"]
+/// annotations = { auth = "user" }
+/// compression = { encoding = "br", min_size = 1024 }
+/// max_body_bytes = 65536
+/// data = { handler = "user_info", timeout_ms = 500 }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConfigEntry {
+    pub name: Option<String>,
+    /// arbitrary payload of the route, an empty table if absent
+    #[serde(default = "empty_table")]
+    pub data: toml::Value,
+}
+
+fn empty_table() -> toml::Value {
+    toml::Value::Table(toml::map::Map::new())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    #[serde(default)]
+    route: Vec<RouteConfig>,
+}
+
+#[derive(Deserialize)]
+struct RouteConfig {
+    pattern: String,
+    #[serde(flatten)]
+    info: RouteInfo,
+    #[serde(flatten)]
+    entry: ConfigEntry,
+}
+
+/// error loading routes from a configuration
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    /// the configuration is not a valid route list
+    Parse(String),
+    /// a route pattern is rejected by the router
+    Route {
+        pattern: String,
+        error: Error,
+    },
+}
+
+impl error::Error for ConfigError {}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "{}", e),
+            ConfigError::Parse(e) => write!(f, "{}", e),
+            ConfigError::Route { pattern, error } if error.route.is_empty() => {
+                write!(f, "{}: {}", pattern, error)
+            }
+            ConfigError::Route { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Router<ConfigEntry> {
+    /// load routes from a TOML route list
+    ///
+    /// every `[[route]]` has a `pattern`, an optional `name`, the optional
+    /// fields of `RouteInfo` and an optional `data` payload, patterns follow
+    /// the rules of `Router::add`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let router = Router::from_config_str(r#"
+    /// [[route]]
+    /// pattern = "/user/:id"
+    /// name = "user"
+    /// data = { handler = "user_info" }
+    /// "#).unwrap();
+    ///
+    /// let m = router.recognize("/user/42").unwrap();
+    /// assert_eq!(m.data.name.as_deref(), Some("user"));
+    /// assert_eq!(m.data.data["handler"].as_str(), Some("user_info"));
+    /// ```
+    pub fn from_config_str(config: &str) -> Result<Router<ConfigEntry>, ConfigError> {
+        let config: Config =
+            toml::from_str(config).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        let mut router = Router::default();
+        for route in config.route {
+            let RouteConfig {
+                pattern,
+                info,
+                entry,
+            } = route;
+            if let Err(error) = router.add_with_info(&pattern, info, entry) {
+                return Err(ConfigError::Route { pattern, error });
+            }
+        }
+        Ok(router)
+    }
+
+    /// load routes from a TOML file, see `from_config_str`
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Router<ConfigEntry>, ConfigError> {
+        let config = fs::read_to_string(path).map_err(ConfigError::Io)?;
+        Router::from_config_str(&config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Encoding;
+
+    #[test]
+    fn load_config() {
+        let router = Router::from_config_str(
+            r##"
+[[route]]
+pattern = "/"
+
+[[route]]
+pattern = "/files/*path"
+name = "files"
+request_schema = "#/File"
+compression = { encoding = "identity" }
+tags = ["files"]
+annotations = { auth = "user" }
+
+[route.data]
+root = "/srv"
+cache = true
+"##,
+        )
+        .unwrap();
+        assert_eq!(router.list_routes(), vec!["/", "/files/*path"]);
+        let root = router.recognize("/").unwrap();
+        assert_eq!(root.data.name, None);
+        assert_eq!(root.data.data, empty_table());
+        let m = router.recognize("/files/a/b").unwrap();
+        assert_eq!(m.data.name.as_deref(), Some("files"));
+        assert_eq!(m.data.data["cache"].as_bool(), Some(true));
+        assert_eq!(m.route.request_schema.as_deref(), Some("#/File"));
+        assert_eq!(
+            (&m.route.tags[0][..], &m.route.annotations["auth"][..]),
+            ("files", "user")
+        );
+        let compression = m.route.compression.unwrap();
+        assert_eq!(
+            (compression.encoding, compression.min_size),
+            (Encoding::Identity, 0)
+        );
+
+        let err = Router::from_config_str(
+            r#"
+[[route]]
+pattern = "/a/:x"
+[[route]]
+pattern = "/a/:y"
+"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid route /a/:y: collides with /a/:x at segment 1"
+        );
+        assert!(matches!(
+            Router::from_config_str("[[route]]\nname = \"x\""),
+            Err(ConfigError::Parse(_))
+        ));
+        assert!(matches!(
+            Router::from_config_file("/nonexistent/routes.toml"),
+            Err(ConfigError::Io(_))
+        ));
+    }
+}
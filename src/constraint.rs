@@ -0,0 +1,374 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Reason};
+use alloc::sync::Arc;
+use core::fmt;
+
+/// constraint attached to a parameter segment
+///
+/// a parameter node with a constraint only catches the segments that pass it,
+/// other segments fall through to the sibling parameter nodes
+#[derive(Clone)]
+pub enum Constraint {
+    /// segment must be parsable as the type
+    Type(ParamType),
+    /// segment must be fully matched by the regular expression
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+    /// segment is made of literals and several parameters, like `:name.:ext`
+    Template(Template),
+    /// segment must be accepted by a user matcher
+    Custom(Arc<dyn SegmentMatcher>),
+}
+
+/// a kind of parameter segment defined outside the crate, like a matcher of
+/// weekday names
+///
+/// a matcher is given to `Router::add_with_constraint` through
+/// `Constraint::custom`, or registered in `Options::segment_matchers` and
+/// written inline like `:day<weekday>`, built-in type names go first;
+/// matchers with the same name are the same kind and share a node; like
+/// other constrained parameters, a custom one is tried after the static
+/// segments and before the unconstrained parameter, in the order the
+/// constraints were first added; matchers are code, so a serialized router
+/// with custom parameters can't be deserialized
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Router, SegmentMatcher};
+/// use std::sync::Arc;
+///
+/// struct Weekday;
+///
+/// impl SegmentMatcher for Weekday {
+///     fn name(&self) -> &str {
+///         "weekday"
+///     }
+///
+///     fn matches(&self, segment: &str) -> bool {
+///         ["mon", "tue", "wed", "thu", "fri", "sat", "sun"].contains(&segment)
+///     }
+/// }
+///
+/// let mut router = Router::default();
+/// router.options_mut().segment_matchers.push(Arc::new(Weekday));
+/// router.add("/open/:day<weekday>", 1).unwrap();
+/// router.add("/open/:other", 2).unwrap();
+///
+/// assert_eq!(*router.recognize("/open/mon").unwrap().data, 1);
+/// assert_eq!(*router.recognize("/open/xmas").unwrap().data, 2);
+/// assert_eq!(router.list_routes()[0], "/open/:day<weekday>");
+/// ```
+pub trait SegmentMatcher: Send + Sync {
+    /// the name of the kind, written in routes like `:day<weekday>`
+    fn name(&self) -> &str;
+
+    /// whether a parameter of the kind takes the segment
+    fn matches(&self, segment: &str) -> bool;
+}
+
+impl fmt::Debug for dyn SegmentMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SegmentMatcher({:?})", self.name())
+    }
+}
+
+impl Constraint {
+    /// create a constraint from a regular expression
+    ///
+    /// the expression is anchored, it must match the whole segment
+    #[cfg(feature = "regex")]
+    pub fn regex(re: &str) -> Result<Constraint, Error> {
+        match regex::Regex::new(&format!("^(?:{})$", re)) {
+            Ok(re) => Ok(Constraint::Regex(re)),
+            Err(_) => Err(Error::new(Reason::InvalidRegex)),
+        }
+    }
+
+    /// create a constraint from a user matcher
+    pub fn custom<M: SegmentMatcher + 'static>(matcher: M) -> Constraint {
+        Constraint::Custom(Arc::new(matcher))
+    }
+
+    // the regular expression as it was given
+    pub(crate) fn regex_source(&self) -> Option<&str> {
+        match *self {
+            Constraint::Type(_) | Constraint::Template(_) | Constraint::Custom(_) => None,
+            #[cfg(feature = "regex")]
+            Constraint::Regex(ref re) => {
+                let re = re.as_str();
+                Some(&re[4..re.len() - 2]) // anchored as `^(?:..)$`
+            }
+        }
+    }
+
+    /// check whether a segment passes the constraint
+    pub fn matches(&self, segment: &str) -> bool {
+        match *self {
+            Constraint::Type(ty) => ty.check(segment),
+            #[cfg(feature = "regex")]
+            Constraint::Regex(ref re) => re.is_match(segment),
+            Constraint::Template(ref t) => t.captures(segment).is_some(),
+            Constraint::Custom(ref m) => m.matches(segment),
+        }
+    }
+
+    // whether every segment passing `other` is known to pass this one
+    pub(crate) fn covers(&self, other: &Constraint) -> bool {
+        match (self, other) {
+            (Constraint::Type(a), Constraint::Type(b)) => match (a.range(), b.range()) {
+                (Some(a), Some(b)) => a.0 <= b.0 && b.1 <= a.1,
+                _ => a == b,
+            },
+            _ => self == other,
+        }
+    }
+}
+
+impl PartialEq for Constraint {
+    fn eq(&self, other: &Constraint) -> bool {
+        match (self, other) {
+            (Constraint::Type(a), Constraint::Type(b)) => a == b,
+            #[cfg(feature = "regex")]
+            (Constraint::Regex(a), Constraint::Regex(b)) => a.as_str() == b.as_str(),
+            // parameter names belong to the routes, not to the node
+            (Constraint::Template(a), Constraint::Template(b)) => a.literals == b.literals,
+            (Constraint::Custom(a), Constraint::Custom(b)) => a.name() == b.name(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Constraint::Type(ty) => write!(f, "Type({:?})", ty),
+            #[cfg(feature = "regex")]
+            Constraint::Regex(ref re) => write!(f, "Regex({:?})", re.as_str()),
+            Constraint::Template(ref t) => write!(f, "{:?}", t),
+            Constraint::Custom(ref m) => write!(f, "Custom({:?})", m.name()),
+        }
+    }
+}
+
+/// a segment mixing literal text and parameters, like `:name.:ext` or
+/// `v:major`
+///
+/// parameters are separated by non-empty literals, every parameter catches
+/// at least one character and earlier parameters take as much as they can,
+/// so `:name.:ext` catches `archive.tar` and `gz` from `archive.tar.gz`
+#[derive(Clone)]
+pub struct Template {
+    literals: Vec<String>, // text around the parameters, one more than the names
+    names: Vec<String>,
+}
+
+impl Template {
+    /// parse a segment of a route, parameter names are made of ascii
+    /// alphanumerics and `_`
+    pub fn parse(segment: &str) -> Result<Template, Error> {
+        let mut literals = vec![];
+        let mut names = vec![];
+        let mut rest = segment;
+        while let Some(i) = rest.find(':') {
+            literals.push(String::from(&rest[..i]));
+            let after = &rest[i + 1..];
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            if end == 0 {
+                return Err(Error::new(Reason::EmptyName));
+            }
+            names.push(String::from(&after[..end]));
+            rest = &after[end..];
+        }
+        literals.push(String::from(rest));
+
+        // adjacent parameters couldn't be told apart
+        if names.is_empty() || literals[1..names.len()].iter().any(String::is_empty) {
+            return Err(Error::new(Reason::InvalidTemplate));
+        }
+        Ok(Template { literals, names })
+    }
+
+    /// names of the parameters in order
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub(crate) fn names_mut(&mut self) -> &mut [String] {
+        &mut self.names
+    }
+
+    /// values of the parameters if the segment fits the template
+    pub fn captures<'s>(&self, segment: &'s str) -> Option<Vec<&'s str>> {
+        let n = self.names.len();
+        let mut rest = segment
+            .strip_prefix(self.literals[0].as_str())?
+            .strip_suffix(self.literals[n].as_str())?;
+        let mut values = vec![""; n];
+        for i in (1..n).rev() {
+            let sep = self.literals[i].as_str();
+            let at = rest
+                .rmatch_indices(sep)
+                .map(|(at, _)| at)
+                .find(|at| at + sep.len() < rest.len())?;
+            values[i] = &rest[at + sep.len()..];
+            rest = &rest[..at];
+        }
+        values[0] = rest;
+        if rest.is_empty() {
+            return None;
+        }
+        Some(values)
+    }
+
+    // the segment with every parameter written by `param`
+    pub(crate) fn render(&self, param: impl Fn(&str) -> String) -> String {
+        let mut result = String::new();
+        for (literal, name) in self.literals.iter().zip(self.names.iter()) {
+            result.push_str(literal);
+            result.push_str(&param(name));
+        }
+        result.push_str(&self.literals[self.names.len()]);
+        result
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(|name| format!(":{}", name)))
+    }
+}
+
+impl fmt::Debug for Template {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Template({:?})", self.to_string())
+    }
+}
+
+/// expected type of a parameter, written inline like `:id<u32>`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamType {
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    Bool,
+    /// calendar date formatted as `yyyy-mm-dd`
+    Date,
+}
+
+impl ParamType {
+    /// parse the type name used in routes, e.g. `u32` or `yyyy-mm-dd`
+    pub fn from_name(name: &str) -> Result<ParamType, Error> {
+        let ty = match name {
+            "u8" => ParamType::U8,
+            "u16" => ParamType::U16,
+            "u32" => ParamType::U32,
+            "u64" => ParamType::U64,
+            "usize" => ParamType::Usize,
+            "i8" => ParamType::I8,
+            "i16" => ParamType::I16,
+            "i32" => ParamType::I32,
+            "i64" => ParamType::I64,
+            "isize" => ParamType::Isize,
+            "bool" => ParamType::Bool,
+            "yyyy-mm-dd" => ParamType::Date,
+            _ => return Err(Error::new(Reason::UnknownType(String::from(name)))),
+        };
+        Ok(ty)
+    }
+
+    /// type name used in routes
+    pub fn name(self) -> &'static str {
+        match self {
+            ParamType::U8 => "u8",
+            ParamType::U16 => "u16",
+            ParamType::U32 => "u32",
+            ParamType::U64 => "u64",
+            ParamType::Usize => "usize",
+            ParamType::I8 => "i8",
+            ParamType::I16 => "i16",
+            ParamType::I32 => "i32",
+            ParamType::I64 => "i64",
+            ParamType::Isize => "isize",
+            ParamType::Bool => "bool",
+            ParamType::Date => "yyyy-mm-dd",
+        }
+    }
+
+    // bounds of an integer type
+    fn range(self) -> Option<(i128, i128)> {
+        let range = match self {
+            ParamType::U8 => (0, u8::MAX.into()),
+            ParamType::U16 => (0, u16::MAX.into()),
+            ParamType::U32 => (0, u32::MAX.into()),
+            ParamType::U64 => (0, u64::MAX.into()),
+            ParamType::Usize => (0, usize::MAX as i128),
+            ParamType::I8 => (i8::MIN.into(), i8::MAX.into()),
+            ParamType::I16 => (i16::MIN.into(), i16::MAX.into()),
+            ParamType::I32 => (i32::MIN.into(), i32::MAX.into()),
+            ParamType::I64 => (i64::MIN.into(), i64::MAX.into()),
+            ParamType::Isize => (isize::MIN as i128, isize::MAX as i128),
+            ParamType::Bool | ParamType::Date => return None,
+        };
+        Some(range)
+    }
+
+    /// check whether a segment is a valid value of the type
+    pub fn check(self, segment: &str) -> bool {
+        match self {
+            ParamType::U8 => segment.parse::<u8>().is_ok(),
+            ParamType::U16 => segment.parse::<u16>().is_ok(),
+            ParamType::U32 => segment.parse::<u32>().is_ok(),
+            ParamType::U64 => segment.parse::<u64>().is_ok(),
+            ParamType::Usize => segment.parse::<usize>().is_ok(),
+            ParamType::I8 => segment.parse::<i8>().is_ok(),
+            ParamType::I16 => segment.parse::<i16>().is_ok(),
+            ParamType::I32 => segment.parse::<i32>().is_ok(),
+            ParamType::I64 => segment.parse::<i64>().is_ok(),
+            ParamType::Isize => segment.parse::<isize>().is_ok(),
+            ParamType::Bool => segment.parse::<bool>().is_ok(),
+            ParamType::Date => is_date(segment),
+        }
+    }
+}
+
+fn is_date(segment: &str) -> bool {
+    let bytes = segment.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let number = |s: &[u8]| -> Option<u32> {
+        if s.iter().all(u8::is_ascii_digit) {
+            core::str::from_utf8(s).ok()?.parse().ok()
+        } else {
+            None
+        }
+    };
+    let (year, month, day) = match (
+        number(&bytes[..4]),
+        number(&bytes[5..7]),
+        number(&bytes[8..]),
+    ) {
+        (Some(y), Some(m), Some(d)) => (y, m, d),
+        _ => return false,
+    };
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    day >= 1 && day <= days
+}
@@ -0,0 +1,188 @@
+use crate::{Error, Match, Reason, RouteInfo, Router, Values};
+use regex::Regex;
+use std::sync::Arc;
+
+/// which routes of a `DualRouter` are tried first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    /// the routes of the trie, the regex routes only when the trie misses
+    TrieFirst,
+    /// the regex routes, the trie only when none of them matches
+    RegexFirst,
+}
+
+/// a router with an ordered list of regex routes next to its trie, see
+/// `Router::dual`
+///
+/// a regex route must match the whole normalized path, the regex routes are
+/// tried in the order they were added and the named groups of the first one
+/// matching become the parameters, decoded like the segments of the trie;
+/// they're meant for the few irregular paths the trie can't express, every
+/// regex route costs a regex match on every path reaching the list
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Precedence, Router};
+/// let mut router = Router::default();
+/// router.add("/users/:id", 1).unwrap();
+///
+/// let mut router = router.dual(Precedence::TrieFirst);
+/// router.add_regex(r"/legacy/user(?P<id>[0-9]+)\.php", 2).unwrap();
+/// router.add_regex(r"/users/(?P<id>.+)", 3).unwrap();
+///
+/// let m = router.recognize("/legacy/user42.php").unwrap();
+/// assert_eq!((*m.data, &m.params["id"][..]), (2, "42"));
+/// assert_eq!(*router.recognize("/users/1").unwrap().data, 1);
+/// assert_eq!(*router.recognize("/users/1/2").unwrap().data, 3);
+/// assert!(router.recognize("/legacy/user.php").is_none());
+/// ```
+pub struct DualRouter<T> {
+    router: Router<T>,
+    regex_routes: Vec<RegexRoute<T>>,
+    precedence: Precedence,
+}
+
+struct RegexRoute<T> {
+    regex: Regex,
+    names: Vec<Arc<str>>,
+    info: RouteInfo,
+    data: T,
+}
+
+impl<T> Router<T> {
+    /// turn the router into a `DualRouter` taking regex routes too
+    pub fn dual(self, precedence: Precedence) -> DualRouter<T> {
+        DualRouter {
+            router: self,
+            regex_routes: vec![],
+            precedence,
+        }
+    }
+}
+
+impl<T> DualRouter<T> {
+    /// add a route to the trie like `Router::add`
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.router.add(route, data)
+    }
+
+    /// add a regex route after the existing ones, the expression is anchored
+    /// and must match the whole path
+    pub fn add_regex(&mut self, re: &str, data: T) -> Result<&mut T, Error> {
+        self.add_regex_with_info(re, RouteInfo::default(), data)
+    }
+
+    /// add a regex route with information like `Router::add_with_info`
+    pub fn add_regex_with_info(
+        &mut self,
+        re: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        let regex = Regex::new(&format!("^(?:{})$", re))
+            .map_err(|_| Error::new(Reason::InvalidRegex).of(re))?;
+        let names = regex.capture_names().flatten().map(Arc::from).collect();
+        self.regex_routes.push(RegexRoute {
+            regex,
+            names,
+            info,
+            data,
+        });
+        Ok(&mut self.regex_routes.last_mut().expect("route just added").data)
+    }
+
+    /// recognize a path with the trie and the regex routes in the order of
+    /// the precedence
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        match self.precedence {
+            Precedence::TrieFirst => self
+                .router
+                .recognize(path)
+                .or_else(|| self.recognize_regex(path)),
+            Precedence::RegexFirst => self
+                .recognize_regex(path)
+                .or_else(|| self.router.recognize(path)),
+        }
+    }
+
+    fn recognize_regex<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        let options = self.router.options();
+        let path = options.normalize_path(path).ok()?;
+        for route in self.regex_routes.iter() {
+            let captures = match route.regex.captures(&path) {
+                Some(captures) => captures,
+                None => continue,
+            };
+            let mut values = Values::new();
+            for name in route.names.iter() {
+                let value = captures.name(name).map_or("", |m| m.as_str());
+                values.push(options.decode(value)?.into_owned().into());
+            }
+            return Some(Match {
+                data: &route.data,
+                params: crate::route_params(&route.names, &route.info, values),
+                route: &route.info,
+            });
+        }
+        None
+    }
+
+    /// the trie of the router
+    pub fn router(&self) -> &Router<T> {
+        &self.router
+    }
+
+    /// the trie of the router, to add routes with constraints or info
+    pub fn router_mut(&mut self) -> &mut Router<T> {
+        &mut self.router
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PercentDecode;
+
+    #[test]
+    fn dual_routes() {
+        let mut router = Router::default();
+        router.add("/files/*path", 1).unwrap();
+        router.options_mut().percent_decode = PercentDecode::PassThrough;
+        let mut router = router.dual(Precedence::RegexFirst);
+        router
+            .add_regex(r"/files/(?P<name>[^/]+)\.(?P<ext>bak|old)", 2)
+            .unwrap();
+        router.add_regex(r"/files/(?P<name>.+)", 3).unwrap();
+        router
+            .add_regex(r"/(?P<y>\d{4})(/(?P<m>\d\d))?", 4)
+            .unwrap();
+        assert_eq!(
+            router.add_regex("(", 5).unwrap_err().reason,
+            Reason::InvalidRegex
+        );
+
+        let m = router.recognize("/files/a%20b.bak").unwrap();
+        assert_eq!(*m.data, 2);
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("name", "a b"), ("ext", "bak")]
+        );
+        assert_eq!(*router.recognize("/files/a/b.bak").unwrap().data, 3);
+        let m = router.recognize("/2024").unwrap();
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("y", "2024"), ("m", "")]
+        );
+
+        let mut router = Router::default();
+        router.add("/files/*path", 1).unwrap();
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        let mut router = router.dual(Precedence::TrieFirst);
+        router.add_regex(r"/(?P<name>.+)\.bak", 2).unwrap();
+        assert_eq!(*router.recognize("/files/a.bak").unwrap().data, 1);
+        assert_eq!(*router.recognize("/a.bak").unwrap().data, 2);
+        assert!(router.recognize("/a%zz.bak").is_none());
+        assert!(router.recognize("/2024").is_none());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Error, Match, Params, RouteInfo, Router};
+
+/// the hook of an `EnrichedRouter`, filling the extension of a match from its
+/// route information and parameters
+pub type Enrichment<E> = fn(&RouteInfo, &Params, &mut E);
+
+/// a router running a hook on every match to fill a user extension, see
+/// `Router::enriched`
+///
+/// the extension starts as `E::default()` on every match, so values derived
+/// from the route, like a tenant parsed from a parameter, are computed once
+/// by the router instead of by every handler
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Params, RouteInfo, Router};
+///
+/// #[derive(Default)]
+/// struct Ext {
+///     tenant: Option<u32>,
+///     admin: bool,
+/// }
+///
+/// fn enrich(info: &RouteInfo, params: &Params, ext: &mut Ext) {
+///     ext.tenant = params.get("tenant").and_then(|t| t.parse().ok());
+///     ext.admin = info.tags.iter().any(|t| t == "admin");
+/// }
+///
+/// let mut router = Router::default();
+/// router.add("/t/:tenant/users", 1).unwrap();
+/// let info = RouteInfo {
+///     tags: vec![String::from("admin")],
+///     ..RouteInfo::default()
+/// };
+/// router.add_with_info("/admin", info, 2).unwrap();
+///
+/// let router = router.enriched(enrich);
+/// let m = router.recognize("/t/7/users").unwrap();
+/// assert_eq!((*m.data, m.ext.tenant, m.ext.admin), (1, Some(7), false));
+/// assert!(router.recognize("/admin").unwrap().ext.admin);
+/// ```
+pub struct EnrichedRouter<T, E> {
+    router: Router<T>,
+    hook: Enrichment<E>,
+}
+
+/// a match of an `EnrichedRouter` with the extension filled by its hook
+#[derive(Debug)]
+pub struct EnrichedMatch<'a, T, E> {
+    /// data set in the route
+    pub data: T,
+    /// extracted parameters from the path
+    pub params: Params,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+    /// the extension filled by the hook
+    pub ext: E,
+}
+
+impl<T> Router<T> {
+    /// turn the router into an `EnrichedRouter` running `hook` on every match
+    pub fn enriched<E: Default>(self, hook: Enrichment<E>) -> EnrichedRouter<T, E> {
+        EnrichedRouter { router: self, hook }
+    }
+}
+
+impl<T, E: Default> EnrichedRouter<T, E> {
+    /// add a route like `Router::add`
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.router.add(route, data)
+    }
+
+    /// add a route with information like `Router::add_with_info`
+    pub fn add_with_info(
+        &mut self,
+        route: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        self.router.add_with_info(route, info, data)
+    }
+
+    /// recognize a path like `Router::recognize` and run the hook on the match
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<EnrichedMatch<'a, &'a T, E>> {
+        self.router.recognize(path).map(|m| self.enrich(m))
+    }
+
+    /// run the hook on a match of the router, e.g. one from `Router::resolve`
+    pub fn enrich<'a, D>(&self, m: Match<'a, D>) -> EnrichedMatch<'a, D, E> {
+        let mut ext = E::default();
+        (self.hook)(m.route, &m.params, &mut ext);
+        EnrichedMatch {
+            data: m.data,
+            params: m.params,
+            route: m.route,
+            ext,
+        }
+    }
+
+    /// the router without the hook
+    pub fn router(&self) -> &Router<T> {
+        &self.router
+    }
+
+    pub fn router_mut(&mut self) -> &mut Router<T> {
+        &mut self.router
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+
+    fn locale(_: &RouteInfo, params: &Params, ext: &mut String) {
+        if let Some(lang) = params.get("lang") {
+            *ext = lang.to_lowercase().replace('_', "-");
+        }
+    }
+
+    #[test]
+    fn enrichment() {
+        let mut router = Router::default().enriched(locale);
+        router.add("/:lang/docs/*page", 1).unwrap();
+        router.add("/docs/*page", 2).unwrap();
+        router.router_mut().set_fallback("/", 0).unwrap();
+
+        let m = router.recognize("/EN_us/docs/a/b").unwrap();
+        assert_eq!(
+            (*m.data, &m.params["page"][..], &m.ext[..]),
+            (1, "a/b", "en-us")
+        );
+        assert_eq!(router.recognize("/docs/a").unwrap().ext, "");
+        let m = router.enrich(router.router().resolve("/x").into_match().unwrap());
+        assert_eq!((*m.data, m.ext.as_str()), (0, ""));
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Collate, Error, ParsedRoute, Router, Segment, Step};
+
+/// a route of a router which may or may not have data, see `Router::entry`
+pub struct Entry<'a, T> {
+    slot: Slot<'a, T>,
+}
+
+// the node of a route with data, or the route to add once it gets data
+enum Slot<'a, T> {
+    Occupied(&'a mut Router<T>),
+    Vacant(&'a mut Router<T>, String, ParsedRoute),
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// set the data if the route has none, return the data of the route
+    pub fn or_insert(self, data: T) -> &'a mut T {
+        self.or_insert_with(|| data)
+    }
+
+    /// set the data computed by `f` if the route has none, return the data of
+    /// the route
+    pub fn or_insert_with<F: FnOnce() -> T>(self, f: F) -> &'a mut T {
+        let node = match self.slot {
+            Slot::Occupied(node) => node,
+            Slot::Vacant(router, route, parsed) => match router.add_parsed(&route, parsed) {
+                Ok(node) => node,
+                Err(_) => panic!("impossible"), // conflicts are checked by `entry`
+            },
+        };
+        node.data.get_or_insert_with(f)
+    }
+
+    pub fn or_default(self) -> &'a mut T
+    where
+        T: Default,
+    {
+        self.or_insert_with(T::default)
+    }
+
+    /// modify the data of the route if it has any
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Entry<'a, T> {
+        if let Slot::Occupied(ref mut node) = self.slot {
+            if let Some(ref mut data) = node.data {
+                f(data);
+            }
+        }
+        self
+    }
+
+    /// the data of the route if it has any
+    pub fn get(&self) -> Option<&T> {
+        match self.slot {
+            Slot::Occupied(ref node) => node.data.as_ref(),
+            Slot::Vacant(..) => None,
+        }
+    }
+}
+
+impl<T> Router<T> {
+    /// the entry of a route for in-place manipulation of its data
+    ///
+    /// the route follows the rules of `Router::add`, a route conflicting with
+    /// existing routes is an error even if no data is inserted; the router is
+    /// left as it is until data is inserted
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::<Vec<&str>>::default();
+    /// router.entry("/user/:id").unwrap().or_default().push("auth");
+    /// router
+    ///     .entry("/user/:id")
+    ///     .unwrap()
+    ///     .and_modify(|handlers| handlers.push("profile"))
+    ///     .or_insert_with(Vec::new);
+    ///
+    /// assert_eq!(*router.recognize("/user/1").unwrap().data, vec!["auth", "profile"]);
+    /// ```
+    pub fn entry(&mut self, route: &str) -> Result<Entry<'_, T>, Error> {
+        let parsed = self.parse_route(route, None)?;
+        let collate = self.options.collation.for_keys();
+        if let Some(segment) = self.find_conflict(&parsed.0, parsed.1, &collate) {
+            return Err(self.named_conflict(route, &parsed, segment));
+        }
+        let slot = match self.route_steps(&parsed, &collate) {
+            Some(steps) => Slot::Occupied(self.node_mut(&steps)),
+            None => Slot::Vacant(self, String::from(route), parsed),
+        };
+        Ok(Entry { slot })
+    }
+
+    // the steps to the node of a route, None if there's no such node or it
+    // has no data
+    fn route_steps(
+        &self,
+        (segments, _): &ParsedRoute,
+        collate: Collate<String>,
+    ) -> Option<Vec<Step>> {
+        let mut node = self;
+        let mut steps = vec![];
+        let mut pending: &[String] = &[]; // tail keys of the static node still to match
+        for (segment, constraint) in segments {
+            if let Some((first, rest)) = pending.split_first() {
+                match segment {
+                    Segment::Static(key) if collate(first, key).is_eq() => pending = rest,
+                    _ => return None,
+                }
+                continue;
+            }
+            let step = match segment {
+                Segment::Static(key) => Step::Static(
+                    node.static_children
+                        .binary_search_by(|c| collate(&c.text, key))
+                        .ok()?,
+                ),
+                Segment::Param(_) => Step::Param(
+                    node.param_children
+                        .iter()
+                        .position(|c| c.constraint == *constraint)?,
+                ),
+                Segment::CatchAll(_) => Step::CatchAll,
+            };
+            node = match step {
+                Step::Static(idx) => &node.static_children[idx],
+                Step::Param(idx) => &node.param_children[idx],
+                Step::CatchAll => node.catch_all_child.as_deref()?,
+            };
+            if let Step::Static(_) = step {
+                pending = &node.tail;
+            }
+            steps.push(step);
+        }
+        match pending.is_empty() && node.data.is_some() {
+            true => Some(steps),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_entries() {
+        let mut router = Router::default();
+        assert_eq!(*router.entry("/a/:x").unwrap().or_insert(1), 1);
+        assert_eq!(*router.entry("/a/:x").unwrap().or_insert(2), 1);
+        assert_eq!(router.entry("/a/:x").unwrap().get(), Some(&1));
+        *router
+            .entry("/a/:x")
+            .unwrap()
+            .and_modify(|d| *d += 10)
+            .or_insert(0) += 100;
+        assert_eq!(*router.recognize("/a/b").unwrap().data, 111);
+
+        let entry = router.entry("/b/:y?").unwrap();
+        assert_eq!(entry.get(), None);
+        entry.and_modify(|d| *d = 5).or_insert_with(|| 3);
+        assert_eq!(*router.recognize("/b").unwrap().data, 3);
+
+        assert!(router.entry("/a/:z").is_err());
+        assert!(router.entry("/a/").is_err());
+        assert!(router.entry("/c").is_ok());
+        assert_eq!(router.list_routes(), vec!["/a/:x", "/b/:y?"]);
+
+        // a dropped entry leaves the router as it was
+        let mut router = Router::default();
+        router.entry("/a/:x<u32>/c").unwrap();
+        router.entry("/a/:x<u32>/c").unwrap().and_modify(|d| *d = 1);
+        assert!(router.list_routes().is_empty());
+        assert_eq!(format!("{:?}", router), "root\n");
+        router.add("/a/*z/d", 2).unwrap();
+        assert!(router.entry("/a/:x<u32>/c").is_err());
+
+        let mut router = Router::default();
+        router.add("/a/bc/d", 1).unwrap();
+        router.entry("/a/bc").unwrap();
+        router.entry("/a").unwrap();
+        assert_eq!(router.list_routes(), vec!["/a/bc/d"]);
+        assert_eq!(router.entry("/a/bc/d").unwrap().get(), Some(&1));
+        *router.entry("/a/bc").unwrap().or_insert(2) += 1;
+        assert_eq!(*router.recognize("/a/bc").unwrap().data, 3);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::merge::route_key;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::Router;
+use alloc::collections::BTreeMap;
+
+/// routers are equal when they have the same routes with equal data and
+/// the same fallbacks with equal data
+///
+/// a route is its pattern with the names and constraints of its parameters,
+/// the layout of the trie, the options and the information of the routes
+/// aren't compared, so routers built in any order are equal
+///
+/// # Example
+///
+/// ```
+/// use path_router::Router;
+/// let mut a = Router::default();
+/// a.add("/users/:id", 1).unwrap();
+/// a.add("/users", 2).unwrap();
+/// let mut b = Router::default();
+/// b.add("/users", 2).unwrap();
+/// b.add("/users/:id", 1).unwrap();
+/// assert_eq!(a, b);
+///
+/// b.add("/posts", 3).unwrap();
+/// assert_ne!(a, b);
+/// ```
+impl<T: PartialEq> PartialEq for Router<T> {
+    fn eq(&self, other: &Router<T>) -> bool {
+        self.route_table() == other.route_table()
+    }
+}
+
+impl<T: Eq> Eq for Router<T> {}
+
+// routes and fallbacks by key with their data
+type RouteTable<'a, T> = (BTreeMap<String, &'a T>, BTreeMap<String, &'a T>);
+
+impl<T> Router<T> {
+    fn route_table(&self) -> RouteTable<'_, T> {
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+        let routes = routes
+            .into_iter()
+            .filter_map(|(route, node)| Some((route_key(&route), node.data.as_ref()?)))
+            .collect();
+        let mut fallbacks = vec![];
+        self.parsed_fallbacks(vec![], &mut fallbacks);
+        let fallbacks = fallbacks
+            .into_iter()
+            .map(|(prefix, data)| (route_key(&(prefix, false)), data))
+            .collect();
+        (routes, fallbacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_set_equality() {
+        let mut a = Router::default();
+        a.add("/", 0).unwrap();
+        a.add("/a/b/c", 1).unwrap();
+        a.add("/a/:id<u32>", 2).unwrap();
+        a.add("/a/:name.:ext", 3).unwrap();
+        a.set_fallback("/a", 4).unwrap();
+        let mut b = Router::default();
+        b.set_fallback("/a", 4).unwrap();
+        b.add("/a/:name.:ext", 3).unwrap();
+        b.add("/a/b/c", 1).unwrap();
+        b.add("/a/b", 5).unwrap();
+        b.add("/a/:id<u32>", 2).unwrap();
+        assert_ne!(a, b);
+        a.add("/a/b", 5).unwrap();
+        assert_ne!(a, b);
+        b.add("/", 0).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a, a.clone());
+
+        let route = |route: &str, data: usize| {
+            let mut router = Router::default();
+            router.add(route, data).unwrap();
+            router
+        };
+        assert_ne!(route("/a/:x", 1), route("/a/:y", 1));
+        assert_ne!(route("/a/:x", 1), route("/a/:x<u32>", 1));
+        assert_ne!(route("/a/:x", 1), route("/a/:x?", 1));
+        assert_ne!(route("/a/:x", 1), route("/a/:x", 2));
+        assert_eq!(route("/a/*x", 1), route("/a/*x", 1));
+        let mut c = route("/a/x", 1);
+        c.set_fallback("/", 2).unwrap();
+        assert_ne!(c, route("/a/x", 1));
+
+        // fallbacks beneath parameters and catchalls count too
+        let mut d = route("/t/:tenant/files/a", 1);
+        d.sub_route("/t/:tenant")
+            .unwrap()
+            .set_fallback("/files", 9)
+            .unwrap();
+        assert_ne!(d, route("/t/:tenant/files/a", 1));
+        let mut e = route("/t/:tenant/files/a", 1);
+        e.sub_route("/t/:tenant")
+            .unwrap()
+            .set_fallback("/files", 8)
+            .unwrap();
+        assert_ne!(d, e);
+        e.sub_route("/t/:tenant")
+            .unwrap()
+            .set_fallback("/files", 9)
+            .unwrap();
+        assert_eq!(d, e);
+        let mut f = route("/g/*path/x", 1);
+        f.sub_route("/g/*path")
+            .unwrap()
+            .set_fallback("/x", 2)
+            .unwrap();
+        assert_ne!(f, route("/g/*path/x", 1));
+    }
+}
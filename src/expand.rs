@@ -0,0 +1,150 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Options, Reason, Router};
+use alloc::sync::Arc;
+
+/// expand the brace groups of a pattern like `/users/:id/{repos,gists}` into
+/// its routes
+///
+/// a group lists alternatives separated by `,`, groups don't nest, several
+/// groups give every combination of their alternatives in order
+///
+/// # Example
+///
+/// ```
+/// use path_router::expand_braces;
+/// assert_eq!(
+///     expand_braces("/{a,b}/x{1,2}").unwrap(),
+///     ["/a/x1", "/a/x2", "/b/x1", "/b/x2"]
+/// );
+/// assert!(expand_braces("/{a,{b}}").is_err());
+/// ```
+pub fn expand_braces(pattern: &str) -> Result<Vec<String>, Error> {
+    let mut routes = vec![String::new()];
+    let mut rest = pattern;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err(Error::new(Reason::InvalidFormat));
+        }
+        let close = open
+            + rest[open..]
+                .find('}')
+                .ok_or_else(|| Error::new(Reason::InvalidFormat))?;
+        let group = &rest[open + 1..close];
+        if group.contains('{') {
+            return Err(Error::new(Reason::InvalidFormat));
+        }
+        let head = &rest[..open];
+        routes = routes
+            .iter()
+            .flat_map(|route| {
+                group
+                    .split(',')
+                    .map(move |alt| format!("{}{}{}", route, head, alt))
+            })
+            .collect();
+        rest = &rest[close + 1..];
+    }
+    for route in routes.iter_mut() {
+        route.push_str(rest);
+    }
+    Ok(routes)
+}
+
+impl<T> Router<Arc<T>> {
+    /// add every route of a pattern with brace groups, see `expand_braces`,
+    /// the routes share the data
+    ///
+    /// nothing is added when a route is invalid or conflicts with an existing
+    /// route or another route of the pattern
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// use std::sync::Arc;
+    /// let mut router = Router::default();
+    /// router
+    ///     .add_expanded("/users/:id/{repos,gists,followers}", "list")
+    ///     .unwrap();
+    ///
+    /// let repos = router.recognize("/users/1/repos").unwrap();
+    /// let gists = router.recognize("/users/1/gists").unwrap();
+    /// assert!(Arc::ptr_eq(repos.data, gists.data));
+    /// assert!(router.add_expanded("/users/{me,:uid}/repos", "own").is_err());
+    /// assert!(router.recognize("/users/me/repos").unwrap().data.contains("list"));
+    /// ```
+    pub fn add_expanded(&mut self, pattern: &str, data: T) -> Result<Arc<T>, Error> {
+        let routes = expand_braces(pattern)?;
+        let mut scratch = Router::<()>::with_options(Options {
+            on_ambiguity: None,
+            ..Options::clone(&self.options)
+        });
+        let collation = self.options.collation;
+        for route in routes.iter() {
+            let parsed = self.parse_route(route, None)?;
+            if let Some(segment) = self.find_conflict(&parsed.0, parsed.1, &collation.for_keys()) {
+                return Err(self.named_conflict(route, &parsed, segment));
+            }
+            scratch.add(route, ())?;
+        }
+        let data = Arc::new(data);
+        for route in routes.iter() {
+            self.add(route, data.clone())?;
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn brace_expansion() {
+        assert_eq!(expand_braces("/a/b").unwrap(), ["/a/b"]);
+        assert_eq!(
+            expand_braces("/x/{,:id}/{a}").unwrap(),
+            ["/x//a", "/x/:id/a"]
+        );
+        for bad in ["/{a", "/a}", "/{a}}", "/{{a}}"] {
+            assert_eq!(expand_braces(bad), Err(Error::new(Reason::InvalidFormat)));
+        }
+
+        let mut router = Router::default();
+        router.add("/users/:id/stars", Arc::new(0)).unwrap();
+        let data = router
+            .add_expanded("/users/:id/{repos,gists}/{recent,all}", 1)
+            .unwrap();
+        assert_eq!(Arc::strong_count(&data), 5);
+        assert_eq!(router.list_routes().len(), 5);
+
+        assert_eq!(
+            router
+                .add_expanded("/users/{me,:uid}/stars", 2)
+                .unwrap_err(),
+            Error {
+                route: String::from("/users/:uid/stars"),
+                segment: Some(2),
+                reason: Reason::Conflict {
+                    existing: String::from("/users/:id/stars")
+                },
+            }
+        );
+        assert_eq!(
+            router.add_expanded("/teams/{:a,:b}", 3).unwrap_err(),
+            Error {
+                route: String::from("/teams/:b"),
+                segment: Some(1),
+                reason: Reason::Conflict {
+                    existing: String::from("/teams/:a")
+                },
+            }
+        );
+        assert_eq!(
+            router.add_expanded("/x/{a,}", 4).unwrap_err().reason,
+            Reason::TrailingSlash
+        );
+        assert_eq!(router.list_routes().len(), 5);
+    }
+}
@@ -0,0 +1,117 @@
+//! the GitHub API route corpus used by the benchmarks, for correctness tests
+//! of routers built on this crate
+//!
+//! every route comes in the colon syntax of this crate and in the braces
+//! syntax of other routers, with a path which matches it
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Params, Router};
+
+mod github;
+
+/// syntax of the parameters in a route list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// `/users/:username`, the syntax of `Router::add`
+    Colon,
+    /// `/users/{username}`
+    Braces,
+}
+
+/// a route of the corpus with a path it matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    /// the route in colon syntax
+    pub route: &'static str,
+    /// the same route in braces syntax
+    pub braces: &'static str,
+    /// a path matching the route
+    pub path: &'static str,
+}
+
+impl Fixture {
+    /// the parameters the path is expected to yield
+    pub fn params(&self) -> Params {
+        self.route
+            .split('/')
+            .zip(self.path.split('/'))
+            .filter_map(|(r, p)| Some((String::from(r.strip_prefix(':')?), String::from(p))))
+            .collect()
+    }
+}
+
+/// routes of the GitHub API in the syntax, in corpus order
+pub fn github_routes(syntax: Syntax) -> &'static [&'static str] {
+    match syntax {
+        Syntax::Colon => &github::ROUTES_WITH_COLON,
+        Syntax::Braces => &github::ROUTES_WITH_BRACES,
+    }
+}
+
+/// paths matching the GitHub API routes, in corpus order
+pub fn github_paths() -> &'static [&'static str] {
+    &github::ROUTES_URLS
+}
+
+/// the GitHub API corpus, each route with its path
+pub fn github() -> Vec<Fixture> {
+    github::ROUTES_WITH_COLON
+        .iter()
+        .zip(github::ROUTES_WITH_BRACES.iter())
+        .zip(github::ROUTES_URLS.iter())
+        .map(|((&route, &braces), &path)| Fixture {
+            route,
+            braces,
+            path,
+        })
+        .collect()
+}
+
+/// a router of the GitHub API routes with their positions in the corpus as
+/// data
+///
+/// # Example
+///
+/// ```
+/// use path_router::fixtures;
+/// let router = fixtures::github_router();
+///
+/// for (i, fixture) in fixtures::github().iter().enumerate() {
+///     let m = router.recognize(fixture.path).unwrap();
+///     assert_eq!((*m.data, m.params), (i, fixture.params()));
+/// }
+/// ```
+pub fn github_router() -> Router<usize> {
+    let mut router = Router::default();
+    for (i, route) in github::ROUTES_WITH_COLON.iter().enumerate() {
+        router.add(route, i).expect("valid corpus route");
+    }
+    router
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn github_corpus() {
+        let fixtures = github();
+        assert_eq!(fixtures.len(), 315);
+        assert_eq!(github_routes(Syntax::Colon).len(), github_paths().len());
+        for fixture in fixtures.iter() {
+            assert_eq!(fixture.braces.matches('{').count(), fixture.params().len());
+        }
+        let router = github_router();
+        assert_eq!(router.list_routes().len(), 315);
+        for (i, fixture) in fixtures.iter().enumerate() {
+            let m = router.recognize(fixture.path).unwrap();
+            assert_eq!(
+                (*m.data, m.params),
+                (i, fixture.params()),
+                "{}",
+                fixture.path
+            );
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::case::case_corrected;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    descend, route_params, Collation, Constraint, Landing, Match, NodeKind, NodeRef, Options,
+    Params, RouteInfo, Router, Trail,
+};
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// a router compiled for lookups, made by `Router::freeze`
+///
+/// the nodes are laid out breadth first in one array, the children of a node
+/// next to each other, and refer to their keys, parameter names and data by
+/// index; it recognizes paths like the router it's made of, with its
+/// options, and can't be changed
+///
+/// # Example
+///
+/// ```
+/// use path_router::Router;
+/// let mut router = Router::default();
+/// router.add("/users/:id<u32>", 1).unwrap();
+/// router.add("/users/:name/posts", 2).unwrap();
+///
+/// let frozen = router.freeze();
+/// let m = frozen.recognize("/users/bob/posts").unwrap();
+/// assert_eq!((*m.data, &m.params["name"][..]), (2, "bob"));
+/// assert_eq!(*frozen.recognize("/users/7").unwrap().data, 1);
+/// ```
+pub struct FrozenRouter<T> {
+    nodes: Vec<Node>,
+    keys: Vec<String>,    // the key and the tail of every static node
+    names: Vec<Arc<str>>, // parameter names of the routes
+    constraints: Vec<Constraint>,
+    infos: Vec<RouteInfo>, // the first one is the default for nodes without data
+    data: Vec<T>,          // data and fallbacks
+    options: Options,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    keys: (usize, usize), // range of `keys`, empty for wildcard nodes
+    children: usize,      // first child, static ones go first, then parameters and the catch all
+    statics: usize,
+    params: usize,
+    catch_all: bool,
+    kind: NodeKind,
+    constraint: Option<usize>,
+    optional: bool,
+    names: (usize, usize),
+    info: usize,
+    data: Option<usize>,
+    fallback: Option<usize>,
+}
+
+// a node of a frozen router as the matcher sees it
+struct FrozenNode<'a, T> {
+    router: &'a FrozenRouter<T>,
+    idx: usize,
+}
+
+impl<T> Router<T> {
+    /// compile the router into a `FrozenRouter`
+    pub fn freeze(self) -> FrozenRouter<T> {
+        let mut frozen = FrozenRouter {
+            nodes: vec![],
+            keys: vec![],
+            names: vec![],
+            constraints: vec![],
+            infos: vec![RouteInfo::default()],
+            data: vec![],
+            options: Options::clone(&self.options),
+        };
+        let mut queue = VecDeque::new();
+        queue.push_back(self);
+        let mut next = 1; // index of the first child of the next node
+        while let Some(node) = queue.pop_front() {
+            let keys = frozen.keys.len();
+            if let NodeKind::Static = node.kind {
+                frozen.keys.push(node.text);
+                frozen.keys.extend(node.tail);
+            }
+            let names = frozen.names.len();
+            frozen.names.extend(node.params);
+            let info = match node.data.is_some() || node.fallback.is_some() {
+                true => {
+                    frozen.infos.push(node.info);
+                    frozen.infos.len() - 1
+                }
+                false => 0,
+            };
+            let mut push_data = |data: Option<T>| {
+                data.map(|d| {
+                    frozen.data.push(d);
+                    frozen.data.len() - 1
+                })
+            };
+            let data = push_data(node.data);
+            let fallback = push_data(node.fallback);
+            let constraint = node.constraint.map(|c| {
+                frozen.constraints.push(c);
+                frozen.constraints.len() - 1
+            });
+
+            let (statics, params) = (node.static_children.len(), node.param_children.len());
+            let catch_all = node.catch_all_child.is_some();
+            frozen.nodes.push(Node {
+                keys: (keys, frozen.keys.len()),
+                children: next,
+                statics,
+                params,
+                catch_all,
+                kind: node.kind,
+                constraint,
+                optional: node.optional,
+                names: (names, frozen.names.len()),
+                info,
+                data,
+                fallback,
+            });
+            next += statics + params + catch_all as usize;
+            queue.extend(node.static_children);
+            queue.extend(node.param_children);
+            queue.extend(node.catch_all_child.map(|c| *c));
+        }
+        frozen
+    }
+}
+
+impl<T> FrozenRouter<T> {
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// recognize a path like `Router::recognize`
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        let normalized = self.options.normalize_path(path).ok()?;
+        let mut trail = Trail::new(false);
+        let route = self
+            .find(&normalized, &mut trail)
+            .and_then(NodeRef::data_node);
+        let landing = self.options.settle(
+            path,
+            &normalized,
+            route.map(|n| n.node().kind),
+            trail.fallback.is_some(),
+            || case_corrected(&self.options, self.root(), &normalized),
+        );
+        match landing {
+            Landing::Route => {
+                let node = route?;
+                Some(Match {
+                    data: node.data()?,
+                    params: route_params(node.names(), node.info(), trail.values),
+                    route: node.info(),
+                })
+            }
+            Landing::Fallback => {
+                let (node, _) = trail.fallback?;
+                Some(Match {
+                    data: &self.data[node.node().fallback?],
+                    params: Params::new(),
+                    route: node.info(),
+                })
+            }
+            Landing::Miss(_) => None,
+        }
+    }
+
+    fn root(&self) -> FrozenNode<'_, T> {
+        FrozenNode {
+            router: self,
+            idx: 0,
+        }
+    }
+
+    fn find<'a, 'p>(
+        &'a self,
+        path: &'p str,
+        trail: &mut Trail<'a, 'p, T, str, FrozenNode<'a, T>>,
+    ) -> Option<FrozenNode<'a, T>> {
+        let path = if path.is_empty() { "/" } else { path };
+        if !path.starts_with('/') {
+            return None;
+        }
+        descend(&self.options, self.root(), &[], path, 1, trail)
+    }
+}
+
+impl<'a, T> FrozenNode<'a, T> {
+    fn node(self) -> &'a Node {
+        &self.router.nodes[self.idx]
+    }
+
+    fn at(self, idx: usize) -> FrozenNode<'a, T> {
+        FrozenNode {
+            router: self.router,
+            idx,
+        }
+    }
+}
+
+impl<T> Clone for FrozenNode<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FrozenNode<'_, T> {}
+
+impl<'a, T> NodeRef<'a, T> for FrozenNode<'a, T> {
+    fn data(self) -> Option<&'a T> {
+        self.node().data.map(|i| &self.router.data[i])
+    }
+
+    fn has_fallback(self) -> bool {
+        self.node().fallback.is_some()
+    }
+
+    fn info(self) -> &'a RouteInfo {
+        &self.router.infos[self.node().info]
+    }
+
+    fn names(self) -> &'a [Arc<str>] {
+        let (start, end) = self.node().names;
+        &self.router.names[start..end]
+    }
+
+    fn constraint(self) -> Option<&'a Constraint> {
+        self.node().constraint.map(|c| &self.router.constraints[c])
+    }
+
+    fn optional(self) -> bool {
+        self.node().optional
+    }
+
+    fn key(self) -> &'a str {
+        &self.router.keys[self.node().keys.0]
+    }
+
+    fn tail(self) -> &'a [String] {
+        let (start, end) = self.node().keys;
+        &self.router.keys[start + 1..end]
+    }
+
+    fn statics(self) -> usize {
+        self.node().statics
+    }
+
+    fn nth_static(self, idx: usize) -> Self {
+        self.at(self.node().children + idx)
+    }
+
+    fn find_static(self, segment: &str, collation: Collation) -> Option<usize> {
+        let node = self.node();
+        let statics = &self.router.nodes[node.children..node.children + node.statics];
+        statics
+            .binary_search_by(|n| collation.compare(&self.router.keys[n.keys.0], segment))
+            .ok()
+    }
+
+    fn params(self) -> usize {
+        self.node().params
+    }
+
+    fn nth_param(self, idx: usize) -> Self {
+        self.at(self.node().children + self.node().statics + idx)
+    }
+
+    fn catch_all(self) -> Option<Self> {
+        let node = self.node();
+        match node.catch_all {
+            true => Some(self.at(node.children + node.statics + node.params)),
+            false => None,
+        }
+    }
+
+    fn is(self, other: Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FrozenRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FrozenRouter")
+            .field("nodes", &self.nodes.len())
+            .field("data", &self.data)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PercentDecode, Pick, TieBreaker};
+
+    #[test]
+    fn frozen_router() {
+        let routes = [
+            "/",
+            "/a/b/c/d",
+            "/a/b/x",
+            "/user/:id<u32>",
+            "/user/:name/:tab?",
+            "/user/:name/posts/:post",
+            "/n/:a<u32>/x",
+            "/n/:b<u16>/x",
+            "/download/:name.:ext",
+            "/download/:file",
+            "/repo/*path/manifest.json",
+            "/repo/*path",
+            "/files/a%20b/*rest",
+        ];
+        let mut router = Router::default();
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        router.options_mut().tie_breaker = Some(TieBreaker::new(|_| Pick::Second));
+        for (i, route) in routes.iter().enumerate() {
+            router.add(route, i).unwrap();
+        }
+        router.set_fallback("/user", 100).unwrap();
+        router.set_fallback("/a/b", 101).unwrap();
+        let paths = [
+            "",
+            "/",
+            "/a/b/c/d",
+            "/a/b/c",
+            "/a/b/x",
+            "/a/b/c/d/e",
+            "/user/7",
+            "/user/bob",
+            "/user/bob/info",
+            "/user/7/posts/1",
+            "/user/a/b/c/d",
+            "/n/7/x",
+            "/n/70000/x",
+            "/download/a.tar.gz",
+            "/download/README",
+            "/repo/a/b/manifest.json",
+            "/repo/a/b/",
+            "/files/a b/x%2Fy",
+            "/files/a%20b/%zz",
+            "/nothing",
+            "nothing",
+        ];
+        let expected = paths
+            .iter()
+            .map(|p| router.recognize(p).map(|m| (*m.data, m.params)))
+            .collect::<Vec<_>>();
+        let frozen = router.freeze();
+        let found = paths
+            .iter()
+            .map(|p| frozen.recognize(p).map(|m| (*m.data, m.params)))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected);
+        assert_eq!(found[11].as_ref().map(|m| m.0), Some(7));
+        assert_eq!(found[10].as_ref().map(|m| m.0), Some(100));
+
+        fn is_send_sync<T: Send + Sync>(_: &T) {}
+        is_send_sync(&frozen);
+    }
+}
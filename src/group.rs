@@ -0,0 +1,220 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, RouteInfo, Router};
+use alloc::sync::Arc;
+
+// wraps the data of a route added to a group
+type Wrap<'a, T> = Arc<dyn Fn(T) -> T + 'a>;
+
+/// routes added under a prefix with shared information and data wrappers,
+/// see `Router::group`
+pub struct RouteGroup<'a, T> {
+    node: &'a mut Router<T>,
+    info: RouteInfo,
+    wraps: Vec<Wrap<'a, T>>, // outermost first
+}
+
+impl<T> Router<T> {
+    /// a group of routes under a prefix, every route added to the group
+    /// takes its information and goes through its wrappers
+    ///
+    /// the prefix follows the rules of `sub_route`; groups nest, a nested
+    /// group starts with the information and the wrappers of its parent
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// let mut admin = router
+    ///     .group("/admin")
+    ///     .unwrap()
+    ///     .tag("admin")
+    ///     .annotate("auth", "admin")
+    ///     .wrap(|handler: String| format!("auth({})", handler));
+    /// admin.add("/users", String::from("users")).unwrap();
+    /// admin.group("/stats").unwrap().tag("stats").add("/", String::from("stats")).unwrap();
+    /// drop(admin);
+    ///
+    /// let m = router.recognize("/admin/users").unwrap();
+    /// assert_eq!(m.data, "auth(users)");
+    /// assert_eq!(m.route.annotations["auth"], "admin");
+    /// let m = router.recognize("/admin/stats").unwrap();
+    /// assert_eq!(m.data, "auth(stats)");
+    /// assert_eq!(m.route.tags, ["admin", "stats"]);
+    /// ```
+    pub fn group(&mut self, prefix: &str) -> Result<RouteGroup<'_, T>, Error> {
+        Ok(RouteGroup {
+            node: self.sub_route(prefix)?,
+            info: RouteInfo::default(),
+            wraps: vec![],
+        })
+    }
+}
+
+impl<'a, T> RouteGroup<'a, T> {
+    /// information shared by the routes of the group in place of what it has
+    pub fn info(mut self, info: RouteInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.info.tags.push(String::from(tag));
+        self
+    }
+
+    pub fn annotate(mut self, key: &str, value: &str) -> Self {
+        self.info
+            .annotations
+            .insert(String::from(key), String::from(value));
+        self
+    }
+
+    /// wrap the data of the routes added after, wrappers added later wrap
+    /// the data first, so the wrappers of a parent group are outermost
+    pub fn wrap<F: Fn(T) -> T + 'a>(mut self, f: F) -> Self {
+        self.wraps.push(Arc::new(f));
+        self
+    }
+
+    /// add a route with the information of the group, see `Router::add`
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add_with_info(route, RouteInfo::default(), data)
+    }
+
+    /// add a route with its own information on top of the group's
+    ///
+    /// tags and transforms of the group come before the route's, annotations
+    /// and the other fields of the route win over the group's
+    pub fn add_with_info(
+        &mut self,
+        route: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        let data = self.wraps.iter().rev().fold(data, |data, wrap| wrap(data));
+        let info = inherit(&self.info, info);
+        self.node.add_with_info(route, info, data)
+    }
+
+    /// a group nested under a prefix of this one
+    pub fn group(&mut self, prefix: &str) -> Result<RouteGroup<'_, T>, Error> {
+        Ok(RouteGroup {
+            node: self.node.sub_route(prefix)?,
+            info: self.info.clone(),
+            wraps: self.wraps.clone(),
+        })
+    }
+
+    /// the router of the prefix of the group
+    pub fn router_mut(&mut self) -> &mut Router<T> {
+        self.node
+    }
+}
+
+fn inherit(group: &RouteInfo, info: RouteInfo) -> RouteInfo {
+    let mut tags = group.tags.clone();
+    tags.extend(info.tags);
+    let mut annotations = group.annotations.clone();
+    annotations.extend(info.annotations);
+    let mut transforms = group.transforms.clone();
+    transforms.extend(info.transforms);
+    RouteInfo {
+        request_schema: info.request_schema.or_else(|| group.request_schema.clone()),
+        response_schema: info
+            .response_schema
+            .or_else(|| group.response_schema.clone()),
+        summary: info.summary.or_else(|| group.summary.clone()),
+        tags,
+        annotations,
+        compression: info.compression.or(group.compression),
+        transforms,
+        catch_all_slash: info.catch_all_slash.or(group.catch_all_slash),
+        id_template: info.id_template.or_else(|| group.id_template.clone()),
+        max_body_bytes: info.max_body_bytes.or(group.max_body_bytes),
+        breaker: info.breaker.or_else(|| group.breaker.clone()),
+        guard: info.guard.or_else(|| group.guard.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BraceSyntax, Guard, Options, Transform};
+
+    #[test]
+    fn route_groups() {
+        let mut router = Router::default();
+        let mut api = router
+            .group("/api/:version")
+            .unwrap()
+            .info(RouteInfo {
+                summary: Some(String::from("api")),
+                max_body_bytes: Some(1024),
+                transforms: vec![(String::from("version"), Transform::Lowercase)],
+                ..RouteInfo::default()
+            })
+            .tag("api")
+            .wrap(|d: usize| d * 10);
+        api.add("/users/:id", 1).unwrap();
+        let info = RouteInfo {
+            summary: Some(String::from("posts")),
+            tags: vec![String::from("posts")],
+            guard: Some(Guard::new(|p| p["id"] != "0")),
+            ..RouteInfo::default()
+        };
+        api.add_with_info("/posts/:id", info, 2).unwrap();
+        {
+            let mut inner = api.group("/admin").unwrap().wrap(|d| d + 1);
+            inner.add("/", 3).unwrap();
+        }
+        api.add("/health", 4).unwrap();
+        assert!(api.add("/users/:uid", 5).is_err());
+        assert!(api.group("/x/:y?").is_err());
+        drop(api);
+
+        let m = router.recognize("/api/V2/users/7").unwrap();
+        assert_eq!((*m.data, &m.params["version"][..]), (10, "v2"));
+        assert_eq!(m.route.summary.as_deref(), Some("api"));
+        let m = router.recognize("/api/v2/posts/7").unwrap();
+        assert_eq!((*m.data, m.route.summary.as_deref()), (20, Some("posts")));
+        assert_eq!(
+            (m.route.tags.len(), m.route.max_body_bytes),
+            (2, Some(1024))
+        );
+        assert!(router.recognize("/api/v2/posts/0").is_none());
+        assert_eq!(*router.recognize("/api/v2/admin").unwrap().data, 40);
+        assert_eq!(*router.recognize("/api/v2/health").unwrap().data, 40);
+        assert!(router
+            .recognize("/api/v2/health")
+            .unwrap()
+            .route
+            .guard
+            .is_none());
+
+        let mut router = Router::with_options(Options {
+            parser: Some(Arc::new(BraceSyntax)),
+            ..Options::default()
+        });
+        router.group("/api").unwrap().add("/{id:u32}", 2).unwrap();
+        router
+            .group("/v{major}")
+            .unwrap()
+            .group("/files")
+            .unwrap()
+            .add("/{path:*}", 3)
+            .unwrap();
+        assert_eq!(
+            router.list_routes(),
+            ["/api/:id<u32>", "/v:major/files/*path"]
+        );
+        assert_eq!(*router.recognize("/api/7").unwrap().data, 2);
+        assert!(router.recognize("/api/x").is_none());
+        let m = router.recognize("/v2/files/a/b").unwrap();
+        assert_eq!(
+            (&m.params["major"][..], &m.params["path"][..]),
+            ("2", "a/b")
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::text::Text;
+use crate::{route_params, Error, Params, RouteInfo, Router};
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// a predicate on the parameters of a route, checked when the route takes a
+/// path, see `Router::add_guarded`
+#[derive(Clone)]
+pub struct Guard(Arc<dyn Fn(&Params) -> bool + Send + Sync>);
+
+impl Guard {
+    pub fn new<F: Fn(&Params) -> bool + Send + Sync + 'static>(f: F) -> Guard {
+        Guard(Arc::new(f))
+    }
+
+    /// whether the route may take a path with the parameters
+    pub fn allows(&self, params: &Params) -> bool {
+        (self.0)(params)
+    }
+}
+
+impl PartialEq for Guard {
+    fn eq(&self, other: &Guard) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Guard")
+    }
+}
+
+// whether the guard of a route lets it take the values, the parameters are
+// only built for guarded routes, values of raw bytes are decoded lossily
+pub(crate) fn guard_allows<S: Text + ?Sized>(
+    names: &[Arc<str>],
+    info: &RouteInfo,
+    values: &[Cow<S>],
+) -> bool {
+    match info.guard {
+        Some(ref guard) => {
+            let values = values.iter().map(|v| S::lossy(v));
+            guard.allows(&route_params(names, info, values))
+        }
+        None => true,
+    }
+}
+
+impl<T> Router<T> {
+    /// set a route whose matches must pass a guard on their parameters
+    ///
+    /// a path the guard turns down goes on to the next route which may take
+    /// it, as if the route wasn't there; the guard sees the parameters as
+    /// they land in `Params`, it's kept in `RouteInfo::guard`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router
+    ///     .add_guarded("/t/:tenant/home", 1, |p| p["tenant"].starts_with("acme"))
+    ///     .unwrap();
+    /// router.set_fallback("/t", 2).unwrap();
+    ///
+    /// assert_eq!(*router.recognize("/t/acme-eu/home").unwrap().data, 1);
+    /// assert_eq!(*router.recognize("/t/other/home").unwrap().data, 2);
+    /// ```
+    pub fn add_guarded<F>(&mut self, route: &str, data: T, guard: F) -> Result<&mut T, Error>
+    where
+        F: Fn(&Params) -> bool + Send + Sync + 'static,
+    {
+        let info = RouteInfo {
+            guard: Some(Guard::new(guard)),
+            ..RouteInfo::default()
+        };
+        self.add_with_info(route, info, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+    use crate::{Options, Transform};
+
+    #[test]
+    fn guards() {
+        let mut router = Router::default();
+        router
+            .add_guarded("/user/:id", 1, |p| {
+                p["id"].bytes().all(|b| b.is_ascii_digit())
+            })
+            .unwrap();
+        router
+            .add_guarded("/user/:id/*rest", 2, |p| p["rest"] != "secret")
+            .unwrap();
+        router.set_fallback("/user", 0).unwrap();
+        router
+            .add_guarded("/n/:id<u32>", 3, |p| p["id"] != "0")
+            .unwrap();
+        router.add("/n/:name", 4).unwrap();
+
+        assert_eq!(*router.recognize("/user/42").unwrap().data, 1);
+        assert_eq!(*router.recognize("/user/bob").unwrap().data, 0);
+        assert_eq!(*router.recognize("/user/bob/a/b").unwrap().data, 2);
+        assert_eq!(*router.recognize("/user/bob/secret").unwrap().data, 0);
+        assert_eq!(*router.recognize("/n/1").unwrap().data, 3);
+        assert_eq!(*router.recognize("/n/0").unwrap().data, 4);
+        // a guard turning down bytes goes on to the next candidate too
+        assert_eq!(*router.recognize_bytes(b"/user/bob").unwrap().data, 0);
+        assert_eq!(*router.recognize_bytes(b"/user/7/secret").unwrap().data, 0);
+        assert_eq!(*router.recognize_bytes(b"/n/0").unwrap().data, 4);
+        assert_eq!(*router.recognize_bytes(b"/n/1").unwrap().data, 3);
+        let frozen = router.freeze();
+        assert_eq!(*frozen.recognize("/user/bob").unwrap().data, 0);
+        assert_eq!(*frozen.recognize("/user/7").unwrap().data, 1);
+        assert_eq!(*frozen.recognize("/user/7/secret").unwrap().data, 0);
+        assert_eq!(*frozen.recognize("/n/0").unwrap().data, 4);
+
+        let mut router = Router::with_options(Options::default());
+        let info = RouteInfo {
+            transforms: vec![(String::from("name"), Transform::Lowercase)],
+            guard: Some(Guard::new(|p| p["name"] == "admin")),
+            ..RouteInfo::default()
+        };
+        router.add_with_info("/:name", info, 1).unwrap();
+        assert!(router.recognize("/ADMIN").is_some());
+        assert!(router.recognize("/root").is_none());
+        assert_eq!(router.recognize_all("/root").len(), 0);
+
+        let mut router = Router::default();
+        router
+            .add_guarded("/u/:id", 1, |p| p["id"] == "ok")
+            .unwrap();
+        assert!(router.recognize("/u/bad").is_none());
+        assert!(router.recognize_bytes(b"/u/bad").is_none());
+        assert!(router.recognize_bytes(b"/u/\xffok").is_none());
+        assert_eq!(*router.recognize_bytes(b"/u/ok").unwrap().data, 1);
+    }
+}
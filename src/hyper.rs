@@ -0,0 +1,476 @@
+//! routing of hyper requests
+//!
+//! a `RequestRouter` picks the handler of a request by its method and path
+//! and puts the `Params` of the route in the extensions of the request
+
+use crate::{Error, Method, MethodRouter, Miss, Options, Params, Recorder, Resolution, RouteInfo};
+use ::hyper::header::{HeaderValue, ALLOW, CONTENT_LENGTH, LOCATION};
+use ::hyper::rt::Stream;
+use ::hyper::{Body, Chunk, Request, Response, StatusCode};
+use std::error;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+/// the methods of the route matching the path of a request whose method it
+/// lacks, put in the extensions of the request handed to the not found
+/// handler, e.g. for the `Allow` header of a 405 response
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allowed(pub Vec<Method>);
+
+/// the error of reading a request body past the `RouteInfo::max_body_bytes`
+/// of its route
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyTooLarge {
+    pub max_body_bytes: u64,
+}
+
+impl error::Error for BodyTooLarge {}
+
+impl fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "request body over {} bytes", self.max_body_bytes)
+    }
+}
+
+/// A router of hyper requests to handlers
+///
+/// # Example
+///
+/// ```
+/// use hyper::{Body, Request};
+/// use path_router::hyper::RequestRouter;
+/// use path_router::Params;
+///
+/// let mut router = RequestRouter::new();
+/// router.get("/user/:id", "user").unwrap();
+/// router.not_found("not found");
+///
+/// let mut req = Request::get("/user/42?full=1").body(Body::empty()).unwrap();
+/// assert_eq!(router.route(&mut req), Some(&"user"));
+/// assert_eq!(req.extensions().get::<Params>().unwrap()["id"], "42");
+///
+/// let mut req = Request::get("/users").body(Body::empty()).unwrap();
+/// assert_eq!(router.route(&mut req), Some(&"not found"));
+/// ```
+pub struct RequestRouter<H> {
+    router: MethodRouter<H>,
+    not_found: Option<H>,
+    recorder: Option<Arc<dyn Recorder>>,
+}
+
+impl<H> Default for RequestRouter<H> {
+    fn default() -> RequestRouter<H> {
+        RequestRouter {
+            router: MethodRouter::default(),
+            not_found: None,
+            recorder: None,
+        }
+    }
+}
+
+impl<H> RequestRouter<H> {
+    pub fn new() -> RequestRouter<H> {
+        RequestRouter::default()
+    }
+
+    /// a router with options, see `Router::with_options`
+    pub fn with_options(options: Options) -> RequestRouter<H> {
+        RequestRouter {
+            router: MethodRouter::with_options(options),
+            ..RequestRouter::default()
+        }
+    }
+
+    /// set the handler of a route for a method, see `MethodRouter::add`
+    pub fn add(
+        &mut self,
+        method: &::hyper::Method,
+        route: &str,
+        handler: H,
+    ) -> Result<&mut H, Error> {
+        self.router.add(method.as_str().parse()?, route, handler)
+    }
+
+    pub fn get(&mut self, route: &str, handler: H) -> Result<&mut H, Error> {
+        self.router.get(route, handler)
+    }
+
+    pub fn post(&mut self, route: &str, handler: H) -> Result<&mut H, Error> {
+        self.router.post(route, handler)
+    }
+
+    pub fn put(&mut self, route: &str, handler: H) -> Result<&mut H, Error> {
+        self.router.put(route, handler)
+    }
+
+    pub fn delete(&mut self, route: &str, handler: H) -> Result<&mut H, Error> {
+        self.router.delete(route, handler)
+    }
+
+    /// set the handler of requests no route takes
+    pub fn not_found(&mut self, handler: H) {
+        self.not_found = Some(handler);
+    }
+
+    /// the handler of a request
+    ///
+    /// the handler of the route matching the method and path of the request
+    /// with the parameters of the route set in the extensions of the request,
+    /// otherwise the not found handler, with `Allowed` set in the extensions
+    /// when a route matches the path but not the method
+    pub fn route<B>(&self, req: &mut Request<B>) -> Option<&H> {
+        match self.find(req) {
+            Routed::Handler(handler, _) => Some(handler),
+            Routed::Redirect(_) => self.not_found.as_ref(),
+            Routed::MethodNotAllowed(_) | Routed::NotFound => None,
+        }
+    }
+
+    // what a request is routed to
+    fn find<B>(&self, req: &mut Request<B>) -> Routed<'_, H> {
+        let method = match req.method().as_str().parse::<Method>() {
+            Ok(method) => method,
+            Err(_) => return self.unrouted(),
+        };
+        match self.router.resolve(&method, req.uri().path()) {
+            Resolution::Hit(m) | Resolution::Fallback(m) => {
+                req.extensions_mut().insert::<Params>(m.params);
+                Routed::Handler(m.data, Some(m.route))
+            }
+            Resolution::MethodNotAllowed { allowed } => {
+                req.extensions_mut().insert(Allowed(allowed.clone()));
+                match self.not_found {
+                    Some(ref handler) => Routed::Handler(handler, None),
+                    None => Routed::MethodNotAllowed(allowed),
+                }
+            }
+            Resolution::Miss(Miss::TslRedirect(to)) | Resolution::Miss(Miss::CaseRedirect(to)) => {
+                Routed::Redirect(to)
+            }
+            Resolution::Miss(_) => self.unrouted(),
+        }
+    }
+
+    // the not found handler of a request no route takes
+    fn unrouted(&self) -> Routed<'_, H> {
+        match self.not_found {
+            Some(ref handler) => Routed::Handler(handler, None),
+            None => Routed::NotFound,
+        }
+    }
+
+    /// call the handler of a request, see `route`, and return its response
+    ///
+    /// a panic of the handler is caught and answered with a 500 response, so
+    /// one bad handler doesn't take down the connection, and it's reported
+    /// to the recorder with the route of the request; a request without a
+    /// handler gets a 404 response, or a 405 response with an `Allow` header
+    /// when a route matches its path but not its method, and a path
+    /// `Options::strict_slash` or `Options::case_redirect` redirects gets a
+    /// 308 response to the path of the route before the not found handler is
+    /// tried
+    ///
+    /// a request whose `Content-Length` is over the `RouteInfo::max_body_bytes`
+    /// of its route gets a 413 response without calling the handler, the
+    /// body of other requests of the route fails with `BodyTooLarge` once
+    /// the handler reads past the limit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hyper::{Body, Request, Response, StatusCode};
+    /// use path_router::hyper::RequestRouter;
+    ///
+    /// type Handler = fn(Request<Body>) -> Response<Body>;
+    /// let mut router = RequestRouter::<Handler>::new();
+    /// router.get("/ok", |_| Response::new(Body::from("ok"))).unwrap();
+    /// router.get("/bad", |_| panic!("bad handler")).unwrap();
+    ///
+    /// let req = |path| Request::get(path).body(Body::empty()).unwrap();
+    /// assert_eq!(router.dispatch(req("/ok")).status(), StatusCode::OK);
+    /// assert_eq!(
+    ///     router.dispatch(req("/bad")).status(),
+    ///     StatusCode::INTERNAL_SERVER_ERROR
+    /// );
+    /// assert_eq!(router.dispatch(req("/none")).status(), StatusCode::NOT_FOUND);
+    /// ```
+    pub fn dispatch(&self, mut req: Request<Body>) -> Response<Body>
+    where
+        H: Fn(Request<Body>) -> Response<Body>,
+    {
+        let (handler, info) = match self.find(&mut req) {
+            Routed::Handler(handler, info) => (handler, info),
+            Routed::Redirect(to) => {
+                let location = match req.uri().query() {
+                    Some(query) => format!("{}?{}", to, query),
+                    None => to,
+                };
+                let mut res = status(StatusCode::PERMANENT_REDIRECT);
+                if let Ok(location) = HeaderValue::from_str(&location) {
+                    res.headers_mut().insert(LOCATION, location);
+                }
+                return res;
+            }
+            Routed::MethodNotAllowed(allowed) => {
+                let allow = allowed.iter().map(Method::as_str).collect::<Vec<_>>();
+                let mut res = status(StatusCode::METHOD_NOT_ALLOWED);
+                if let Ok(allow) = HeaderValue::from_str(&allow.join(", ")) {
+                    res.headers_mut().insert(ALLOW, allow);
+                }
+                return res;
+            }
+            Routed::NotFound => return status(StatusCode::NOT_FOUND),
+        };
+        if let Some(max) = info.and_then(|info| info.max_body_bytes) {
+            let length = req.headers().get(CONTENT_LENGTH);
+            match length.and_then(|l| l.to_str().ok()?.parse::<u64>().ok()) {
+                Some(length) if length > max => return status(StatusCode::PAYLOAD_TOO_LARGE),
+                _ => req = req.map(|body| limit_body(body, max)),
+            }
+        }
+        let path = String::from(req.uri().path());
+        match panic::catch_unwind(AssertUnwindSafe(|| handler(req))) {
+            Ok(res) => res,
+            Err(_) => {
+                if let Some(ref recorder) = self.recorder {
+                    // the not found handler serves no route, even when the
+                    // path matches one lacking the method
+                    let route = info.and_then(|_| self.router.router().matched_route(&path));
+                    recorder.handler_panicked(route.as_deref());
+                }
+                status(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+
+    /// report the panics of handlers caught by `dispatch` to `recorder`
+    pub fn set_recorder(&mut self, recorder: Arc<dyn Recorder>) {
+        self.recorder = Some(recorder);
+    }
+
+    pub fn router(&self) -> &MethodRouter<H> {
+        &self.router
+    }
+
+    pub fn router_mut(&mut self) -> &mut MethodRouter<H> {
+        &mut self.router
+    }
+}
+
+// what a request is routed to
+enum Routed<'a, H> {
+    Handler(&'a H, Option<&'a RouteInfo>),
+    // the path redirects, see `Miss::TslRedirect` and `Miss::CaseRedirect`
+    Redirect(String),
+    // a route matches the path but lacks the method, with the methods it has
+    MethodNotAllowed(Vec<Method>),
+    NotFound,
+}
+
+// a body failing with `BodyTooLarge` past `max` bytes
+fn limit_body(body: Body, max: u64) -> Body {
+    let mut read = 0;
+    let limited = body
+        .map_err(|e| -> Box<dyn error::Error + Send + Sync> { Box::new(e) })
+        .and_then(move |chunk: Chunk| {
+            read += chunk.len() as u64;
+            match read > max {
+                true => Err(Box::new(BodyTooLarge {
+                    max_body_bytes: max,
+                }) as Box<dyn error::Error + Send + Sync>),
+                false => Ok(chunk),
+            }
+        });
+    Body::wrap_stream(limited)
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut res = Response::new(Body::empty());
+    *res.status_mut() = status;
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::hyper::rt::Future;
+    use std::io;
+    use std::sync::Mutex;
+
+    #[test]
+    fn request_router() {
+        let mut router = RequestRouter::new();
+        router.get("/user/:id", 1).unwrap();
+        router
+            .add(
+                &::hyper::Method::from_bytes(b"PURGE").unwrap(),
+                "/user/:id",
+                2,
+            )
+            .unwrap();
+        router.post("/user", 3).unwrap();
+
+        let request = |method: &str, uri: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap()
+        };
+        let mut req = request("PURGE", "/user/7");
+        assert_eq!(router.route(&mut req), Some(&2));
+        assert_eq!(req.extensions().get::<Params>().unwrap()["id"], "7");
+
+        let mut req = request("DELETE", "/user/7");
+        assert_eq!(router.route(&mut req), None);
+        assert_eq!(
+            req.extensions().get::<Allowed>(),
+            Some(&Allowed(vec![
+                Method::Get,
+                Method::Other(String::from("PURGE"))
+            ]))
+        );
+        assert!(req.extensions().get::<Params>().is_none());
+
+        router.not_found(0);
+        assert_eq!(router.route(&mut request("GET", "/users")), Some(&0));
+        assert_eq!(
+            router.route(&mut request("POST", "http://example.com/user")),
+            Some(&3)
+        );
+    }
+
+    #[test]
+    fn redirects() {
+        type Handler = fn(Request<Body>) -> Response<Body>;
+        let mut router = RequestRouter::<Handler>::with_options(Options {
+            strict_slash: true,
+            case_redirect: true,
+            ..Options::default()
+        });
+        router
+            .get("/about", |_| Response::new(Body::empty()))
+            .unwrap();
+        router
+            .get("/evil.com", |_| Response::new(Body::empty()))
+            .unwrap();
+
+        let get = |path| router.dispatch(Request::get(path).body(Body::empty()).unwrap());
+        // a redirect never starts with `//`, browsers would leave the site
+        for (path, location) in [
+            ("/about/?a=1", "/about?a=1"),
+            ("/About", "/about"),
+            ("//evil.com/", "/evil.com"),
+            ("//evil.com//", "/evil.com"),
+        ] {
+            let res = get(path);
+            assert_eq!(res.status(), StatusCode::PERMANENT_REDIRECT);
+            assert_eq!(res.headers()[LOCATION], location);
+        }
+        assert_eq!(get("/about").status(), StatusCode::OK);
+        assert_eq!(get("/other/").status(), StatusCode::NOT_FOUND);
+
+        let res = router.dispatch(Request::post("/about").body(Body::empty()).unwrap());
+        assert_eq!(res.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers()[ALLOW], "GET");
+    }
+
+    #[derive(Default)]
+    struct Panics(Mutex<Vec<Option<String>>>);
+
+    impl Recorder for Panics {
+        fn handler_panicked(&self, route: Option<&str>) {
+            self.0.lock().unwrap().push(route.map(String::from));
+        }
+    }
+
+    #[test]
+    fn panicking_handlers() {
+        type Handler = Box<dyn Fn(Request<Body>) -> Response<Body>>;
+        let mut router = RequestRouter::<Handler>::new();
+        router
+            .get("/user/:id", Box::new(|_| panic!("bad user")))
+            .unwrap();
+        router
+            .get("/ok", Box::new(|_| Response::new(Body::from("ok"))))
+            .unwrap();
+        router.not_found(Box::new(|_| panic!("bad not found")));
+        let panics = Arc::new(Panics::default());
+        router.set_recorder(panics.clone());
+
+        let get = |path| Request::get(path).body(Body::empty()).unwrap();
+        for path in ["/user/1", "/ok", "/users", "/user/2"] {
+            let status = match path {
+                "/ok" => StatusCode::OK,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            assert_eq!(router.dispatch(get(path)).status(), status);
+        }
+        let post = Request::post("/user/3").body(Body::empty()).unwrap();
+        assert_eq!(
+            router.dispatch(post).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            *panics.0.lock().unwrap(),
+            [
+                Some(String::from("/user/:id")),
+                None,
+                Some(String::from("/user/:id")),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn body_limits() {
+        type Handler = fn(Request<Body>) -> Response<Body>;
+        fn read(req: Request<Body>) -> Response<Body> {
+            match req.into_body().concat2().wait() {
+                Ok(body) => Response::new(Body::from(body)),
+                Err(e) => {
+                    let e = error::Error::source(&e).and_then(|e| e.downcast_ref::<BodyTooLarge>());
+                    assert_eq!(e, Some(&BodyTooLarge { max_body_bytes: 4 }));
+                    status(StatusCode::BAD_REQUEST)
+                }
+            }
+        }
+        let mut router = RequestRouter::<Handler>::new();
+        let info = RouteInfo {
+            max_body_bytes: Some(4),
+            ..RouteInfo::default()
+        };
+        router
+            .router_mut()
+            .add_with_info(Method::Post, "/small", info, read)
+            .unwrap();
+        router.post("/big", read).unwrap();
+
+        let post = |path, length: Option<&str>, chunks: Vec<&'static str>| {
+            let mut req = Request::post(path);
+            if let Some(length) = length {
+                req.header(CONTENT_LENGTH, length);
+            }
+            let chunks = futures::stream::iter_ok::<_, io::Error>(chunks);
+            req.body(Body::wrap_stream(chunks)).unwrap()
+        };
+        let status_of = |req| router.dispatch(req).status();
+        assert_eq!(
+            status_of(post("/small", Some("4"), vec!["ab", "cd"])),
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_of(post("/small", Some("5"), vec![])),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            status_of(post("/small", None, vec!["abc", "de"])),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status_of(post("/big", Some("5"), vec!["abcde"])),
+            StatusCode::OK
+        );
+    }
+}
@@ -0,0 +1,461 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::Method;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+/// a route read from the route listing of another framework
+///
+/// a listed route with optional parts is imported once per combination of
+/// them, all sharing the listed `source`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedRoute {
+    /// `None` for routes of any method, like mounted Rails engines or `ALL`
+    /// Express routes
+    pub method: Option<Method>,
+    /// the route in the syntax of `Router::add`
+    pub route: String,
+    /// the route as listed
+    pub source: String,
+    /// the route name of Rails
+    pub name: Option<String>,
+    /// what handles the route as listed, like `users#show` or a handler name
+    pub target: Option<String>,
+    /// regular expressions of parameters like `:id(\d+)` and their names, to
+    /// be given to `Constraint::regex`
+    pub constraints: Vec<(String, String)>,
+}
+
+/// a line of a route listing which couldn't be imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    /// line number, from 1
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ImportError {}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+// routes of a listed pattern with the regex constraints of their parameters
+type Converted = Vec<(String, Vec<(String, String)>)>;
+
+/// import the output of `rails routes`
+///
+/// the header, blank lines and the `Routes for` titles of engines are
+/// skipped, the parameter syntax of Rails is the one of this crate, the
+/// optional groups like `(.:format)` are expanded and verbs like `GET|POST`
+/// give one route per method
+///
+/// # Example
+///
+/// ```
+/// use path_router::{import_rails_routes, Method};
+/// let dump = "
+///    Prefix Verb   URI Pattern          Controller#Action
+///      user GET    /users/:id(.:format) users#show
+///           DELETE /users/:id(.:format) users#destroy
+/// ";
+/// let routes = import_rails_routes(dump).unwrap();
+/// let routes = routes
+///     .iter()
+///     .map(|r| (r.method.clone().unwrap(), r.route.as_str()))
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     routes,
+///     [
+///         (Method::Get, "/users/:id"),
+///         (Method::Get, "/users/:id.:format"),
+///         (Method::Delete, "/users/:id"),
+///         (Method::Delete, "/users/:id.:format"),
+///     ]
+/// );
+/// ```
+pub fn import_rails_routes(dump: &str) -> Result<Vec<ImportedRoute>, ImportError> {
+    let mut routes = vec![];
+    for (i, line) in dump.lines().enumerate() {
+        let error = |reason| ImportError {
+            line: i + 1,
+            reason,
+        };
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        let at = match tokens.iter().position(|t| t.starts_with('/')) {
+            Some(at) => at,
+            None if is_rails_title(&tokens) => continue,
+            None => return Err(error("no URI pattern")),
+        };
+        let (name, verbs) = match tokens[..at] {
+            [] => (None, None),
+            [verbs] if is_verbs(verbs) => (None, Some(verbs)),
+            [name] => (Some(name), None),
+            [name, verbs] if is_verbs(verbs) => (Some(name), Some(verbs)),
+            _ => {
+                return Err(error(
+                    "expected a route name and a verb before the URI pattern",
+                ))
+            }
+        };
+        let source = tokens[at];
+        let target = match tokens[at + 1..].join(" ") {
+            t if t.is_empty() => None,
+            t => Some(t),
+        };
+        let converted = convert(source, '(', ')').map_err(error)?;
+        let methods = match verbs {
+            Some(verbs) => verbs.split('|').map(|v| v.parse().ok()).collect(),
+            None => vec![None],
+        };
+        push_routes(&mut routes, methods, &converted, source, name, target);
+    }
+    Ok(routes)
+}
+
+/// import a listing of Express routes, one `METHOD /path` per line
+///
+/// methods may be joined by `,` or `|` and `ALL` stands for any method,
+/// anything after the path is kept as the target; optional parameters like
+/// `:id?`, repeated ones like `:path*` and `:path+`, optional `{..}` groups
+/// are expanded, unnamed `*` wildcards are named by their position like
+/// Express does, from `0`
+///
+/// # Example
+///
+/// ```
+/// use path_router::import_express_routes;
+/// let dump = "
+/// GET     /users/:id(\\d+)   showUser
+/// POST    /files/*
+/// ";
+/// let routes = import_express_routes(dump).unwrap();
+/// assert_eq!(routes[0].route, "/users/:id");
+/// assert_eq!(routes[0].constraints, [(String::from("id"), String::from("\\d+"))]);
+/// assert_eq!(routes[0].target.as_deref(), Some("showUser"));
+/// assert_eq!(routes[1].route, "/files/*0");
+/// ```
+pub fn import_express_routes(dump: &str) -> Result<Vec<ImportedRoute>, ImportError> {
+    let mut routes = vec![];
+    for (i, line) in dump.lines().enumerate() {
+        let error = |reason| ImportError {
+            line: i + 1,
+            reason,
+        };
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        if tokens.is_empty() {
+            continue;
+        }
+        let at = tokens
+            .iter()
+            .position(|t| t.starts_with('/'))
+            .ok_or_else(|| error("no path"))?;
+        let mut methods = vec![];
+        for method in tokens[..at]
+            .iter()
+            .flat_map(|t| t.split([',', '|']))
+            .filter(|m| !m.is_empty())
+        {
+            match method.to_ascii_uppercase().as_str() {
+                "ALL" => methods.push(None),
+                m => methods.push(m.parse().ok()),
+            }
+        }
+        if methods.is_empty() {
+            return Err(error("no method before the path"));
+        }
+        let source = tokens[at];
+        let target = match tokens[at + 1..].join(" ") {
+            t if t.is_empty() => None,
+            t => Some(t),
+        };
+        let converted = convert_express(source).map_err(error)?;
+        push_routes(&mut routes, methods, &converted, source, None, target);
+    }
+    Ok(routes)
+}
+
+fn push_routes(
+    routes: &mut Vec<ImportedRoute>,
+    methods: Vec<Option<Method>>,
+    converted: &[(String, Vec<(String, String)>)],
+    source: &str,
+    name: Option<&str>,
+    target: Option<String>,
+) {
+    for method in methods {
+        for (route, constraints) in converted.iter() {
+            routes.push(ImportedRoute {
+                method: method.clone(),
+                route: route.clone(),
+                source: String::from(source),
+                name: name.map(String::from),
+                target: target.clone(),
+                constraints: constraints.clone(),
+            });
+        }
+    }
+}
+
+// the header of the listing or the title of an engine's routes
+fn is_rails_title(tokens: &[&str]) -> bool {
+    match tokens {
+        [] => true,
+        ["Routes", "for", ..] => true,
+        _ => tokens.contains(&"Pattern") || tokens.contains(&"Controller#Action"),
+    }
+}
+
+fn is_verbs(token: &str) -> bool {
+    token
+        .split('|')
+        .all(|v| !v.is_empty() && v.bytes().all(|b| b.is_ascii_uppercase()))
+}
+
+// expand the optional groups between `open` and `close`, nested ones too
+fn expand(pattern: &str, open: char, close: char) -> Result<Vec<String>, &'static str> {
+    let mut alternatives = vec![String::new()];
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        if c == close {
+            return Err("unbalanced optional group");
+        }
+        if c != open {
+            alternatives.iter_mut().for_each(|a| a.push(c));
+            continue;
+        }
+        let mut depth = 1;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| {
+                if c == open {
+                    depth += 1;
+                } else if c == close {
+                    depth -= 1;
+                }
+                depth == 0
+            })
+            .map(|(at, _)| at)
+            .ok_or("unbalanced optional group")?;
+        let inner = expand(&rest[..end], open, close)?;
+        rest = &rest[end + 1..];
+        alternatives = alternatives
+            .iter()
+            .flat_map(|a| {
+                core::iter::once(a.clone()).chain(inner.iter().map(move |i| format!("{}{}", a, i)))
+            })
+            .collect();
+    }
+    Ok(alternatives)
+}
+
+// expand the optional groups of a pattern into routes
+fn convert(pattern: &str, open: char, close: char) -> Result<Converted, &'static str> {
+    Ok(expand(pattern, open, close)?
+        .into_iter()
+        .map(|mut route| {
+            while route.len() > 1 && route.ends_with('/') {
+                route.pop();
+            }
+            if route.is_empty() {
+                route.push('/');
+            }
+            (route, vec![])
+        })
+        .collect())
+}
+
+// convert the segments of an Express path, `{..}` groups are optional
+fn convert_express(pattern: &str) -> Result<Converted, &'static str> {
+    let mut routes = vec![];
+    for (route, _) in convert(pattern, '{', '}')? {
+        // every segment gives its alternatives, `None` drops the segment
+        let mut alternatives: Converted = vec![(String::new(), vec![])];
+        let mut unnamed = 0;
+        for segment in route[1..].split('/').filter(|s| !s.is_empty()) {
+            let options = express_segment(segment, &mut unnamed)?;
+            alternatives = alternatives
+                .into_iter()
+                .flat_map(|(route, constraints)| {
+                    options.iter().map(move |option| match option {
+                        Some((text, regex)) => {
+                            let mut constraints = constraints.clone();
+                            constraints.extend(regex.iter().cloned());
+                            (format!("{}/{}", route, text), constraints)
+                        }
+                        None => (route.clone(), constraints.clone()),
+                    })
+                })
+                .collect();
+        }
+        for (mut route, constraints) in alternatives {
+            if route.is_empty() {
+                route.push('/');
+            }
+            routes.push((route, constraints));
+        }
+    }
+    Ok(routes)
+}
+
+// a segment of a converted route with the regex of its parameter, `None`
+// for a segment left out
+type SegmentOption = Option<(String, Option<(String, String)>)>;
+
+// one segment of an Express path as the segments it may become
+fn express_segment(segment: &str, unnamed: &mut usize) -> Result<Vec<SegmentOption>, &'static str> {
+    if segment == "*" || segment == "(.*)" {
+        *unnamed += 1;
+        return Ok(vec![Some((format!("*{}", *unnamed - 1), None))]);
+    }
+    let name = match segment.strip_prefix(':') {
+        Some(name) => name,
+        None if segment.contains(['*', '(', '?', '+']) => {
+            return Err("wildcards must be whole segments")
+        }
+        None => return Ok(vec![Some((String::from(segment), None))]),
+    };
+    let end = name
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    let (name, mut rest) = name.split_at(end);
+    if name.is_empty() {
+        return Err("parameter without a name");
+    }
+    let mut regex = None;
+    if let Some(inner) = rest.strip_prefix('(') {
+        let close = inner.rfind(')').ok_or("unbalanced parameter regex")?;
+        regex = Some((String::from(name), String::from(&inner[..close])));
+        rest = &inner[close + 1..];
+    }
+    let param = Some((format!(":{}", name), regex));
+    let catch_all = Some((format!("*{}", name), None));
+    match rest {
+        "" => Ok(vec![param]),
+        "?" => Ok(vec![None, param]),
+        "*" => Ok(vec![None, catch_all]),
+        "+" => Ok(vec![catch_all]),
+        _ => Err("unsupported parameter modifier"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MethodRouter;
+
+    fn routes(imported: Vec<ImportedRoute>) -> Vec<String> {
+        imported
+            .iter()
+            .map(|r| match r.method {
+                Some(ref m) => format!("{} {}", m, r.route),
+                None => format!("* {}", r.route),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rails_routes() {
+        let dump = "
+                   Prefix Verb      URI Pattern                                   Controller#Action
+                    users GET       /users(.:format)                              users#index
+                          POST      /users(.:format)                              users#create
+                 new_user GET       /users/new(.:format)                          users#new
+                     page GET|POST  /pages(/:year(/:month))                       pages#show
+       rails_service_blob GET       /rails/active_storage/blobs/:id/*filename      active_storage/blobs#show
+              sidekiq_web           /sidekiq                                      Sidekiq::Web
+
+Routes for Blog::Engine:
+                    posts GET       /posts                                        blog/posts#index
+";
+        let imported = import_rails_routes(dump).unwrap();
+        let mut router = MethodRouter::new();
+        for r in imported.iter() {
+            let method = r.method.clone().unwrap_or(Method::Get);
+            router.add(method, &r.route, ()).unwrap();
+        }
+        assert_eq!(
+            imported[5],
+            ImportedRoute {
+                method: Some(Method::Get),
+                route: String::from("/users/new.:format"),
+                source: String::from("/users/new(.:format)"),
+                name: Some(String::from("new_user")),
+                target: Some(String::from("users#new")),
+                constraints: vec![],
+            }
+        );
+        assert_eq!(
+            routes(imported),
+            [
+                "GET /users",
+                "GET /users.:format",
+                "POST /users",
+                "POST /users.:format",
+                "GET /users/new",
+                "GET /users/new.:format",
+                "GET /pages",
+                "GET /pages/:year",
+                "GET /pages/:year/:month",
+                "POST /pages",
+                "POST /pages/:year",
+                "POST /pages/:year/:month",
+                "GET /rails/active_storage/blobs/:id/*filename",
+                "* /sidekiq",
+                "GET /posts",
+            ]
+        );
+        assert_eq!(
+            import_rails_routes("users GET /users(.:format users#index"),
+            Err(ImportError {
+                line: 1,
+                reason: "unbalanced optional group"
+            })
+        );
+        assert!(import_rails_routes("users#index").is_err());
+    }
+
+    #[test]
+    fn express_routes() {
+        let dump = "
+GET      /
+GET      /api/users/:id(\\d+)/:tab?     auth, showUser
+POST,PUT /api/files/*
+ALL      /proxy/:path+
+get      /docs{/:page}/:rest*
+";
+        let imported = import_express_routes(dump).unwrap();
+        assert_eq!(
+            (&imported[1].constraints, imported[1].target.as_deref()),
+            (
+                &vec![(String::from("id"), String::from("\\d+"))],
+                Some("auth, showUser")
+            )
+        );
+        assert_eq!(
+            routes(imported),
+            [
+                "GET /",
+                "GET /api/users/:id",
+                "GET /api/users/:id/:tab",
+                "POST /api/files/*0",
+                "PUT /api/files/*0",
+                "* /proxy/*path",
+                "GET /docs",
+                "GET /docs/*rest",
+                "GET /docs/:page",
+                "GET /docs/:page/*rest",
+            ]
+        );
+        assert_eq!(
+            import_express_routes("GET /a/b*").unwrap_err().to_string(),
+            "line 1: wildcards must be whole segments"
+        );
+        assert!(import_express_routes("/a").is_err());
+    }
+}
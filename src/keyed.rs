@@ -0,0 +1,87 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Match, Router};
+
+// set the data of a key, replacing the data it has
+pub(crate) fn set_slot<S: PartialEq, T>(slots: &mut Vec<(S, T)>, key: S, data: T) -> &mut T {
+    let idx = match slots.iter().position(|(k, _)| *k == key) {
+        Some(idx) => {
+            slots[idx].1 = data;
+            idx
+        }
+        None => {
+            slots.push((key, data));
+            slots.len() - 1
+        }
+    };
+    &mut slots[idx].1
+}
+
+impl<S: PartialEq, T> Router<Vec<(S, T)>> {
+    /// set the data of a key on a route, a route holds a slot per key in the
+    /// order they were added
+    ///
+    /// routes follow the rules of `Router::add`, setting the same key on a
+    /// route again replaces its data, `MethodRouter` is this keyed by
+    /// `Method`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// #[derive(PartialEq)]
+    /// enum Accept {
+    ///     Json,
+    ///     Html,
+    /// }
+    ///
+    /// let mut router = Router::default();
+    /// router.add_keyed("/user/:id", Accept::Json, 1).unwrap();
+    /// router.add_keyed("/user/:id", Accept::Html, 2).unwrap();
+    ///
+    /// let m = router.recognize_keyed(&Accept::Html, "/user/7").unwrap();
+    /// assert_eq!((*m.data, &m.params["id"][..]), (2, "7"));
+    /// assert_eq!(router.recognize("/user/7").unwrap().data.len(), 2);
+    /// ```
+    pub fn add_keyed(&mut self, route: &str, key: S, data: T) -> Result<&mut T, Error> {
+        let node = self.add_node(route, None)?;
+        Ok(set_slot(node.data.get_or_insert_with(Vec::new), key, data))
+    }
+
+    /// recognize a path and take the slot of a key, a path taken by a route
+    /// without the key isn't recognized
+    pub fn recognize_keyed<'a>(&'a self, key: &S, path: &str) -> Option<Match<'a, &'a T>> {
+        let m = self.recognize(path)?;
+        let (_, data) = m.data.iter().find(|(k, _)| k == key)?;
+        Some(Match {
+            data,
+            params: m.params,
+            route: m.route,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_slots() {
+        let mut router = Router::default();
+        router.add_keyed("/a/:x", 'g', 1).unwrap();
+        router.add_keyed("/a/:x", 'p', 2).unwrap();
+        *router.add_keyed("/a/:x", 'g', 3).unwrap() += 10;
+        router.add_keyed("/a/b", 'g', 4).unwrap();
+        assert!(router.add_keyed("/a/:y", 'd', 5).is_err());
+
+        assert_eq!(*router.recognize_keyed(&'g', "/a/1").unwrap().data, 13);
+        assert_eq!(*router.recognize_keyed(&'p', "/a/1").unwrap().data, 2);
+        assert_eq!(*router.recognize_keyed(&'g', "/a/b").unwrap().data, 4);
+        assert!(router.recognize_keyed(&'p', "/a/b").is_none());
+        assert!(router.recognize_keyed(&'g', "/b").is_none());
+        assert_eq!(
+            router.recognize("/a/1").unwrap().data,
+            &vec![('g', 13), ('p', 2)]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::resolution::Landing;
+use crate::{Error, Params, Reason, RouteInfo, Router, Trail};
+
+/// a router keeping ordered middleware values by route prefix
+///
+/// the layers of a prefix apply to every route starting with it, a match
+/// hands back the layers of the prefixes of its route from the shortest to
+/// the longest, layers of the same prefix in the order they were added
+///
+/// layers are kept on the node of their prefix, a match collects them on the
+/// way down to its route
+///
+/// # Example
+///
+/// ```
+/// use path_router::LayeredRouter;
+/// let mut router = LayeredRouter::new();
+/// router.add("/api/users/:id", 1).unwrap();
+/// router.add("/api/users/me", 2).unwrap();
+/// router.add("/health", 3).unwrap();
+/// router.layer("/", "log").unwrap();
+/// router.layer("/api", "auth").unwrap();
+/// router.layer("/api/users/:uid", "load_user").unwrap();
+///
+/// let m = router.recognize("/api/users/7").unwrap();
+/// assert_eq!((*m.data, m.layers), (1, vec![&"log", &"auth", &"load_user"]));
+/// assert_eq!(router.recognize("/api/users/me").unwrap().layers, [&"log", &"auth"]);
+/// assert_eq!(router.recognize("/health").unwrap().layers, [&"log"]);
+/// ```
+pub struct LayeredRouter<T, M> {
+    router: Router<Slot<T, M>>,
+}
+
+// the data of a route, or the layers of a prefix kept in the fallback of its
+// node, which never takes a path
+enum Slot<T, M> {
+    Route(T),
+    Layers(Vec<M>),
+}
+
+/// a match of a `LayeredRouter` with the layers of its route
+#[derive(Debug)]
+pub struct LayeredMatch<'a, T, M> {
+    /// data set in the route
+    pub data: &'a T,
+    /// extracted parameters from the path
+    pub params: Params,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+    /// layers of the prefixes of the route, from the shortest to the longest
+    pub layers: Vec<&'a M>,
+}
+
+impl<T, M> Default for LayeredRouter<T, M> {
+    fn default() -> LayeredRouter<T, M> {
+        LayeredRouter {
+            router: Router::default(),
+        }
+    }
+}
+
+impl<T, M> LayeredRouter<T, M> {
+    pub fn new() -> LayeredRouter<T, M> {
+        LayeredRouter::default()
+    }
+
+    /// add a route like `Router::add`
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add_with_info(route, RouteInfo::default(), data)
+    }
+
+    /// add a route with information like `Router::add_with_info`
+    pub fn add_with_info(
+        &mut self,
+        route: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        match self.router.add_with_info(route, info, Slot::Route(data))? {
+            Slot::Route(data) => Ok(data),
+            Slot::Layers(_) => unreachable!("routes hold their data"),
+        }
+    }
+
+    /// push a layer on a prefix, before or after its routes are added
+    ///
+    /// the prefix follows the rules of `Router::sub_route`, parameters match
+    /// the parameters of routes of the same constraint whatever their name
+    pub fn layer(&mut self, prefix: &str, layer: M) -> Result<(), Error> {
+        let (segments, optional) = self.router.parse_route(prefix, None)?;
+        if optional {
+            return Err(Error::new(Reason::MisplacedOptional).of(prefix));
+        }
+        let node = self
+            .router
+            .prefix_node(segments)
+            .map_err(|e| e.of(prefix))?;
+        match node.fallback {
+            Some(Slot::Layers(ref mut layers)) => layers.push(layer),
+            _ => node.fallback = Some(Slot::Layers(vec![layer])),
+        }
+        Ok(())
+    }
+
+    /// recognize a path like `Router::recognize` with the layers of the route
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<LayeredMatch<'a, T, M>> {
+        let router = &self.router;
+        let normalized = router.options.normalize_path(path).ok()?;
+        let mut trail = Trail::new(true);
+        let found = router.find(&normalized, &mut trail)?;
+        let route = found.data_node()?;
+        // the layers are fallbacks to the trie, not to a lookup
+        match router.landing(path, &normalized, Some(route), false) {
+            Landing::Route => {}
+            Landing::Fallback | Landing::Miss(_) => return None,
+        }
+        let data = match route.data {
+            Some(Slot::Route(ref data)) => data,
+            _ => return None,
+        };
+        // the route may be the optional child of the node found
+        let mut nodes = router.nodes(&trail.steps.unwrap_or_default());
+        if !core::ptr::eq(found, route) {
+            nodes.push(route);
+        }
+        let layers = nodes.into_iter().flat_map(|node| match node.fallback {
+            Some(Slot::Layers(ref layers)) => layers.iter(),
+            _ => [].iter(),
+        });
+        Some(LayeredMatch {
+            data,
+            params: crate::route_params(&route.params, &route.info, trail.values),
+            route: &route.info,
+            layers: layers.collect(),
+        })
+    }
+
+    /// list routes like `Router::list_routes`
+    pub fn list_routes(&self) -> Vec<String> {
+        self.router.list_routes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers() {
+        let mut router = LayeredRouter::new();
+        router.layer("/a/:x<u32>", 3).unwrap();
+        router.layer("/a", 1).unwrap();
+        router.layer("/a", 2).unwrap();
+        router.add("/a/:n<u32>/b", 10).unwrap();
+        router.add("/a/:s/b", 11).unwrap();
+        router.add("/c/*rest", 12).unwrap();
+        router.add("/b/:x?", 13).unwrap();
+        router.layer("/c/*p", 4).unwrap();
+        router.layer("/b/:y", 5).unwrap();
+        router.layer("/A", 6).unwrap();
+
+        let layers = |path| {
+            let m = router.recognize(path).unwrap();
+            (*m.data, m.layers.into_iter().copied().collect::<Vec<_>>())
+        };
+        assert_eq!(layers("/a/7/b"), (10, vec![1, 2, 3]));
+        assert_eq!(layers("/a/x/b"), (11, vec![1, 2]));
+        assert_eq!(layers("/c/x/y"), (12, vec![4]));
+        assert_eq!(layers("/b/1"), (13, vec![5]));
+        assert_eq!(layers("/b"), (13, vec![5]));
+        assert!(router.recognize("/d").is_none());
+        assert!(router.layer("/b/:x?", 7).is_err());
+        assert_eq!(router.list_routes().len(), 4);
+
+        // only the layers of the nodes on the way to the route, after the
+        // search backtracks from a dead end
+        let mut router = LayeredRouter::new();
+        router.add("/x/y/z", 1).unwrap();
+        router.add("/x/:p/w", 2).unwrap();
+        router.layer("/x/y", 3).unwrap();
+        router.layer("/x/:q", 4).unwrap();
+        router.layer("/x", 5).unwrap();
+        let m = router.recognize("/x/y/w").unwrap();
+        assert_eq!((*m.data, &m.params["p"][..]), (2, "y"));
+        assert_eq!(m.layers, [&5, &4]);
+        assert_eq!(router.recognize("/x/y/z").unwrap().layers, [&5, &3]);
+        assert!(router.recognize("/x/y").is_none());
+        assert!(router.recognize("/x").is_none());
+    }
+}
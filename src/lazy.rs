@@ -0,0 +1,179 @@
+use crate::{route_params, Landing, Params, RouteInfo, Router, Trail, Values};
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+
+/// match result of `Router::recognize_lazy`
+#[derive(Debug)]
+pub struct LazyMatch<'a, 'p, T> {
+    /// data set in the route
+    pub data: T,
+    /// parameters of the path, built when asked for
+    pub params: LazyParams<'a, 'p>,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+}
+
+/// the values caught from a path, borrowed from it where they can be, and
+/// the names of the route; nothing is allocated for them until asked
+#[derive(Debug, Clone)]
+pub struct LazyParams<'a, 'p> {
+    names: &'a [Arc<str>],
+    info: &'a RouteInfo,
+    values: Values<'p>,
+}
+
+impl<'a, 'p> LazyParams<'a, 'p> {
+    /// value of the named parameter like `Params::get`, transformed as the
+    /// route says
+    pub fn get(&self, name: &str) -> Option<Cow<'_, str>> {
+        let idx = self.names.iter().rposition(|n| **n == *name)?;
+        let value = self.values.get(idx)?;
+        let mut transforms = self.info.transforms.iter().filter(|(n, _)| n == name);
+        match transforms.next() {
+            Some((_, first)) => {
+                let value = transforms.fold(first.apply(value), |v, (_, t)| t.apply(&v));
+                Some(Cow::Owned(value))
+            }
+            None => Some(Cow::Borrowed(value)),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// build the parameters `recognize` returns
+    pub fn to_params(&self) -> Params {
+        route_params(self.names, self.info, self.values.iter().cloned())
+    }
+
+    fn into_owned<'q>(self) -> LazyParams<'a, 'q> {
+        LazyParams {
+            names: self.names,
+            info: self.info,
+            values: self
+                .values
+                .into_iter()
+                .map(|v| Cow::Owned(v.into_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Router<T> {
+    /// recognize a path like `recognize` without building its parameters
+    ///
+    /// the values stay borrowed from the path unless decoding or
+    /// normalization changes them, `LazyParams` builds what's asked for
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/health", 0).unwrap();
+    /// router.add("/user/:id", 1).unwrap();
+    ///
+    /// assert_eq!(*router.recognize_lazy("/health").unwrap().data, 0);
+    /// let m = router.recognize_lazy("/user/42").unwrap();
+    /// assert_eq!(m.params.get("id").as_deref(), Some("42"));
+    /// assert_eq!(m.params.to_params()["id"], "42");
+    /// ```
+    pub fn recognize_lazy<'a, 'p>(&'a self, path: &'p str) -> Option<LazyMatch<'a, 'p, &'a T>> {
+        match self.options.normalize_path(path).ok()? {
+            Cow::Borrowed(normalized) => self.find_lazy(path, normalized),
+            Cow::Owned(normalized) => self.find_lazy(path, &normalized).map(|m| LazyMatch {
+                data: m.data,
+                params: m.params.into_owned(),
+                route: m.route,
+            }),
+        }
+    }
+
+    // `recognize_lazy` of a path once normalized
+    fn find_lazy<'a, 'p>(
+        &'a self,
+        path: &str,
+        normalized: &'p str,
+    ) -> Option<LazyMatch<'a, 'p, &'a T>> {
+        let mut trail = Trail::new(false);
+        let node = self
+            .find(normalized, &mut trail)
+            .and_then(Router::data_node);
+        match self.landing(path, normalized, node, trail.fallback.is_some()) {
+            Landing::Route => {
+                let node = node?;
+                Some(LazyMatch {
+                    data: node.data.as_ref()?,
+                    params: LazyParams {
+                        names: &node.params,
+                        info: &node.info,
+                        values: trail.values,
+                    },
+                    route: &node.info,
+                })
+            }
+            Landing::Fallback => {
+                let (node, _) = trail.fallback?;
+                Some(LazyMatch {
+                    data: node.fallback.as_ref()?,
+                    params: LazyParams {
+                        names: &[],
+                        info: &node.info,
+                        values: Values::new(),
+                    },
+                    route: &node.info,
+                })
+            }
+            Landing::Miss(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+    use crate::{Options, Transform};
+
+    #[test]
+    fn lazy_params() {
+        let mut router = Router::with_options(Options {
+            fullwidth: true,
+            ..Options::default()
+        });
+        let info = RouteInfo {
+            transforms: vec![(String::from("name"), Transform::Lowercase)],
+            ..RouteInfo::default()
+        };
+        router
+            .add_with_info("/repos/:owner/:name/*path", info, 1)
+            .unwrap();
+        router.set_fallback("/repos", 0).unwrap();
+
+        let m = router.recognize_lazy("/repos/Bob/App/src/lib.rs").unwrap();
+        assert!(matches!(m.params.get("owner"), Some(Cow::Borrowed("Bob"))));
+        assert_eq!(m.params.get("name").as_deref(), Some("app"));
+        assert_eq!(m.params.get("path").as_deref(), Some("src/lib.rs"));
+        assert_eq!(m.params.get("other"), None);
+        assert_eq!(m.params.len(), 3);
+        assert_eq!(
+            m.params.to_params(),
+            router
+                .recognize("/repos/Bob/App/src/lib.rs")
+                .unwrap()
+                .params
+        );
+
+        let m = router.recognize_lazy("/repos/ｂｏｂ/x/y").unwrap();
+        assert_eq!(m.params.get("owner").as_deref(), Some("bob"));
+        let m = router.recognize_lazy("/repos/bob").unwrap();
+        assert_eq!((*m.data, m.params.is_empty()), (0, true));
+        assert!(router.recognize_lazy("/users").is_none());
+    }
+}
@@ -1,44 +1,1001 @@
-use std::collections::{BTreeMap, BTreeSet};
-use std::default::Default;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::{Borrow, Cow};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+use children::StaticChildren;
+use core::cmp::Ordering;
+use core::default::Default;
+use core::fmt;
+use core::ops::Range;
+use smallvec::SmallVec;
+#[cfg(feature = "std")]
 use std::error;
-use std::fmt;
 
-#[derive(Debug, PartialEq)]
-pub enum Error {
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+
+mod access_log;
+mod all;
+mod ambiguity;
+mod bounded;
+mod braced;
+mod breaker;
+mod builder;
+#[cfg(feature = "cache")]
+mod cache;
+mod case;
+#[cfg(feature = "chaos")]
+mod chaos;
+mod children;
+#[cfg(feature = "config")]
+mod config;
+mod constraint;
+#[cfg(feature = "regex")]
+mod dual;
+mod enrich;
+mod entry;
+mod eq;
+mod expand;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+mod frozen;
+mod group;
+mod guard;
+#[cfg(feature = "hyper")]
+pub mod hyper;
+mod import;
+mod keyed;
+mod layer;
+mod lazy;
+mod lint;
+mod manifest;
+mod merge;
+mod method;
+mod metrics;
+mod normalize;
+mod openapi;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod params;
+mod prefix;
+mod rebase;
+#[cfg(feature = "replay")]
+mod replay;
+mod report;
+mod request_id;
+mod resolution;
+mod security;
+#[cfg(feature = "std")]
+mod sharded;
+#[cfg(feature = "arc-swap")]
+mod shared;
+#[cfg(feature = "serde")]
+mod snapshot;
+mod stats;
+mod syntax;
+mod tail;
+mod text;
+mod tie_break;
+mod transform;
+mod tree;
+mod uri;
+mod walk;
+
+pub use access_log::{log_line_path, LogReport};
+pub use ambiguity::{Ambiguity, AmbiguityHook};
+pub use bounded::{BoundedRouter, EvictionPolicy};
+pub use breaker::{Breaker, BreakerState};
+pub use builder::{BuildError, BuildProblem, RouterBuilder};
+#[cfg(feature = "cache")]
+pub use cache::CachedRouter;
+#[cfg(feature = "chaos")]
+pub use chaos::{Chaos, ChaosOutcome};
+#[cfg(feature = "config")]
+pub use config::{ConfigEntry, ConfigError};
+pub use constraint::{Constraint, ParamType, SegmentMatcher, Template};
+#[cfg(feature = "regex")]
+pub use dual::{DualRouter, Precedence};
+pub use enrich::{EnrichedMatch, EnrichedRouter, Enrichment};
+pub use entry::Entry;
+pub use expand::expand_braces;
+pub use frozen::FrozenRouter;
+pub use group::RouteGroup;
+pub use guard::Guard;
+pub use import::{import_express_routes, import_rails_routes, ImportError, ImportedRoute};
+pub use layer::{LayeredMatch, LayeredRouter};
+pub use lazy::{LazyMatch, LazyParams};
+pub use lint::{Lint, LintKind};
+pub use manifest::Manifest;
+pub use merge::{Merge3Conflict, Merge3Error, MergeConflict, MergeError, MountError};
+pub use method::{Method, MethodMatch, MethodRouter};
+pub use metrics::Recorder;
+pub use normalize::{
+    normalize_fullwidth, percent_decode, remove_dot_segments, Collation, DotSegments, EncodedSlash,
+    Miss, NameChars, Options, PercentDecode, TrailingSlash,
+};
+pub use params::{Params, ParamsIter};
+#[cfg(feature = "replay")]
+pub use replay::{DecisionLog, Divergence};
+use resolution::Landing;
+pub use resolution::Resolution;
+pub use security::{Finding, Risk};
+#[cfg(feature = "std")]
+pub use sharded::ShardedRouter;
+#[cfg(feature = "arc-swap")]
+pub use shared::SharedRouter;
+pub use stats::RouterStats;
+use syntax::check_pattern;
+pub use syntax::{BraceSyntax, ColonSyntax, RouteParser};
+pub use tail::TailMatch;
+use text::Text;
+pub use tie_break::{Pick, Tie, TieBreaker};
+pub use transform::Transform;
+pub use uri::UriMatch;
+pub use walk::{RouteNodeInfo, RouteNodeKind};
+
+use guard::guard_allows;
+use transform::{route_bytes_params, route_params};
+
+// what the std prelude brings, for builds on `alloc` alone
+#[cfg(not(feature = "std"))]
+pub(crate) mod prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+}
+#[cfg(not(feature = "std"))]
+use prelude::*;
+
+/// a route which can't be added, with why
+///
+/// errors of the generic keys API have an empty `route`, and so do errors of
+/// patterns parsed on their own, like the ones of `expand_braces`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    /// the route as it was given
+    pub route: String,
+    /// index of the segment of `route` at fault, if the fault is in one
+    pub segment: Option<usize>,
+    pub reason: Reason,
+}
+
+/// why a route can't be added
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// the route doesn't start with '/'
+    NoLeadingSlash,
+    /// the route ends with '/'
+    TrailingSlash,
+    /// nothing between two '/'
+    EmptySegment,
+    /// a parameter or a catchall without a name, like `/:` or `/:<u32>`
+    EmptyName,
+    /// a name given to two parameters of the route
+    DuplicateName(String),
+    /// a name with characters `Options::name_chars` doesn't allow
+    InvalidName(String),
+    /// a second catchall, a route has at most one
+    SecondCatchAll,
+    /// a `?` on a segment other than a last parameter
+    MisplacedOptional,
+    /// a parameter type which is neither a `ParamType` nor registered in
+    /// `Options::segment_matchers`
+    UnknownType(String),
+    /// a regex which doesn't compile
+    InvalidRegex,
+    /// a segment mixing literals and parameters which can't be told apart,
+    /// like `:a:b`
+    InvalidTemplate,
+    /// a constraint given to a route without an unconstrained last parameter
+    MisplacedConstraint,
+    /// a segment after the catchall of a route of generic keys
+    CatchAllNotLast,
+    /// a base of a sub route naming its parameters otherwise than when it
+    /// was reached before
+    RenamedBase,
+    /// a static segment with an invalid percent escape
+    InvalidEscape,
+    /// a parameter or a catchall in a pattern which must be static, like the
+    /// prefix of a fallback
+    NotStatic,
+    /// the route collides with an existing one
+    Conflict { existing: String },
+    /// any other malformed pattern, like unbalanced braces
     InvalidFormat,
-    RouteConflict,
 }
 
+impl Error {
+    // an error whose route is named by the caller
+    pub(crate) fn new(reason: Reason) -> Error {
+        Error {
+            route: String::new(),
+            segment: None,
+            reason,
+        }
+    }
+
+    // the error located at a segment, unless it already is
+    pub(crate) fn at(mut self, segment: usize) -> Error {
+        self.segment = self.segment.or(Some(segment));
+        self
+    }
+
+    // the error naming its route, unless it already does
+    pub(crate) fn of(mut self, route: &str) -> Error {
+        if self.route.is_empty() {
+            self.route = String::from(route);
+        }
+        self
+    }
+}
+
+#[cfg(feature = "std")]
 impl error::Error for Error {}
 
 impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.route.is_empty() {
+            true => write!(f, "invalid route: {}", self.reason)?,
+            false => write!(f, "invalid route {}: {}", self.route, self.reason)?,
+        }
+        match self.segment {
+            Some(i) => write!(f, " at segment {}", i),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Reason {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::InvalidFormat => write!(f, "invalid format"),
-            Error::RouteConflict => write!(f, "route conflict"),
+            Reason::NoLeadingSlash => write!(f, "no leading '/'"),
+            Reason::TrailingSlash => write!(f, "trailing '/'"),
+            Reason::EmptySegment => write!(f, "empty segment"),
+            Reason::EmptyName => write!(f, "parameter without a name"),
+            Reason::DuplicateName(name) => write!(f, "parameter {} named twice", name),
+            Reason::InvalidName(name) => write!(f, "invalid parameter name {:?}", name),
+            Reason::SecondCatchAll => write!(f, "second catchall"),
+            Reason::MisplacedOptional => write!(f, "optional segment isn't a last parameter"),
+            Reason::UnknownType(ty) => write!(f, "unknown parameter type {}", ty),
+            Reason::InvalidRegex => write!(f, "invalid regex"),
+            Reason::InvalidTemplate => write!(f, "invalid segment template"),
+            Reason::MisplacedConstraint => write!(f, "no unconstrained last parameter"),
+            Reason::CatchAllNotLast => write!(f, "segment after the catchall"),
+            Reason::RenamedBase => write!(f, "base with renamed parameters"),
+            Reason::InvalidEscape => write!(f, "invalid percent escape"),
+            Reason::NotStatic => write!(f, "parameter in a static pattern"),
+            Reason::Conflict { existing } if existing.is_empty() => write!(f, "route conflict"),
+            Reason::Conflict { existing } => write!(f, "collides with {}", existing),
+            Reason::InvalidFormat => write!(f, "invalid format"),
         }
     }
 }
 
+// a conflict of the generic engine, the string API names the routes
+fn conflict(segment: usize) -> Error {
+    Error::new(Reason::Conflict {
+        existing: String::new(),
+    })
+    .at(segment)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 enum NodeKind {
+    #[default]
     Static,
     Param,
     CatchAll,
 }
 
-impl Default for NodeKind {
-    fn default() -> NodeKind {
-        NodeKind::Static
+// a step from a node to one of its children
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Static(usize),
+    Param(usize),
+    CatchAll,
+}
+
+// values and steps collected down one branch of the trie
+// index of the value caught by a catchall and its range in the path
+type Tail = (usize, Range<usize>);
+
+// the values caught by a lookup, most routes have a few parameters
+pub(crate) type Values<'p, S = str> = SmallVec<[Cow<'p, S>; 4]>;
+
+type Branch<'p, S> = (Values<'p, S>, Vec<Step>, Option<Tail>);
+
+// the nodes of routes reached by a lookup with the values caught on the way
+type Found<'p, S, N> = Vec<(N, Values<'p, S>)>;
+
+// what a lookup collects on its way down the trie, the values are parts of
+// a string path or of a path of raw bytes
+struct Trail<'a, 'p, T, S: ?Sized + ToOwned = str, N = &'a Router<T>> {
+    values: Values<'p, S>,
+    steps: Option<Vec<Step>>, // only kept when the node is looked up again mutably
+    fallback: Option<(N, usize)>, // deepest node with a fallback and where it's reached
+    fallback_steps: Vec<Step>,
+    tail: Option<Tail>,
+    all: Option<Found<'p, S, N>>, // only kept when every route is looked up
+    accept: Option<&'a dyn Fn(&T) -> bool>, // whether a route takes the lookup beside its guards
+}
+
+impl<'a, 'p, T: 'a, S: ?Sized + ToOwned, N: NodeRef<'a, T>> Trail<'a, 'p, T, S, N> {
+    fn new(keep_steps: bool) -> Trail<'a, 'p, T, S, N> {
+        Trail {
+            values: Values::new(),
+            steps: if keep_steps { Some(vec![]) } else { None },
+            fallback: None,
+            fallback_steps: vec![],
+            tail: None,
+            all: None,
+            accept: None,
+        }
+    }
+
+    // a trail keeping every route reached instead of stopping at the first
+    fn collecting() -> Trail<'a, 'p, T, S, N> {
+        Trail {
+            all: Some(vec![]),
+            ..Trail::new(false)
+        }
+    }
+
+    // whether the route of a node reached takes the lookup, a route turned
+    // down is a dead end like a guard turning it down
+    fn accepts(&self, route: N) -> bool
+    where
+        S: Text,
+    {
+        guard_allows(route.names(), route.info(), &self.values)
+            && self
+                .accept
+                .map_or(true, |accept| route.data().is_some_and(accept))
+    }
+
+    // keep the node of a route reached, whether the lookup goes on
+    fn collect(&mut self, node: N) -> bool {
+        match self.all {
+            Some(ref mut all) => {
+                all.push((node, self.values.clone()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // forget the routes kept from `from` on which are the node
+    fn forget(&mut self, from: usize, node: N) {
+        if let Some(ref mut all) = self.all {
+            let below = all.split_off(from);
+            all.extend(below.into_iter().filter(|(n, _)| !n.is(node)));
+        }
+    }
+
+    fn collected(&self) -> usize {
+        self.all.as_ref().map_or(0, Vec::len)
+    }
+
+    fn push_step(&mut self, step: Step) {
+        if let Some(ref mut steps) = self.steps {
+            steps.push(step);
+        }
+    }
+
+    fn pop_step(&mut self) {
+        if let Some(ref mut steps) = self.steps {
+            steps.pop();
+        }
+    }
+
+    fn step_mark(&self) -> usize {
+        self.steps.as_ref().map_or(0, Vec::len)
+    }
+
+    // take the values and steps of a branch off the trail
+    fn split_off(&mut self, values: usize, steps: usize) -> Branch<'p, S> {
+        let tail = match self.tail {
+            Some((i, _)) if i >= values => self.tail.take(),
+            _ => None,
+        };
+        let values = self.values.drain(values..).collect();
+        let steps = match self.steps {
+            Some(ref mut s) => s.split_off(steps),
+            None => vec![],
+        };
+        (values, steps, tail)
+    }
+
+    fn extend(&mut self, (values, steps, tail): Branch<'p, S>) {
+        self.values.extend(values);
+        if let Some(ref mut s) = self.steps {
+            s.extend(steps);
+        }
+        if tail.is_some() {
+            self.tail = tail;
+        }
+    }
+
+    // keep the node if it's reached by a longer part of the path than the
+    // kept one
+    fn set_fallback(&mut self, node: N, start: usize) {
+        if self.fallback.is_some_and(|(_, at)| at >= start) {
+            return;
+        }
+        self.fallback = Some((node, start));
+        if let Some(ref steps) = self.steps {
+            self.fallback_steps = steps.clone();
+        }
+    }
+}
+
+// a node as the matcher sees it, `Router` and `FrozenRouter` are both
+// looked up by `descend` through it
+trait NodeRef<'a, T: 'a>: Copy {
+    fn data(self) -> Option<&'a T>;
+    fn has_fallback(self) -> bool;
+    fn info(self) -> &'a RouteInfo;
+    fn names(self) -> &'a [Arc<str>];
+    fn constraint(self) -> Option<&'a Constraint>;
+    fn optional(self) -> bool;
+    // the first key of a static node and the keys merged into it
+    fn key(self) -> &'a str;
+    fn tail(self) -> &'a [String];
+    fn statics(self) -> usize;
+    fn nth_static(self, idx: usize) -> Self;
+    // index of the static child with a key equal to the segment
+    fn find_static(self, segment: &str, collation: Collation) -> Option<usize>;
+    fn params(self) -> usize;
+    fn nth_param(self, idx: usize) -> Self;
+    fn catch_all(self) -> Option<Self>;
+    // whether both refer to the same node
+    fn is(self, other: Self) -> bool;
+
+    // the node holding data in place of this one
+    fn data_node(self) -> Option<Self> {
+        if self.data().is_some() {
+            return Some(self);
+        }
+        (0..self.params())
+            .map(|idx| self.nth_param(idx))
+            .find(|child| child.optional() && child.data().is_some())
+    }
+
+    // whether a parameter node takes the segment
+    fn accepts(self, segment: &str) -> bool {
+        self.constraint().map_or(true, |c| c.matches(segment))
+    }
+}
+
+impl<'a, T> NodeRef<'a, T> for &'a Router<T> {
+    fn data(self) -> Option<&'a T> {
+        self.data.as_ref()
+    }
+
+    fn has_fallback(self) -> bool {
+        self.fallback.is_some()
+    }
+
+    fn info(self) -> &'a RouteInfo {
+        &self.info
+    }
+
+    fn names(self) -> &'a [Arc<str>] {
+        &self.params
+    }
+
+    fn constraint(self) -> Option<&'a Constraint> {
+        self.constraint.as_ref()
+    }
+
+    fn optional(self) -> bool {
+        self.optional
+    }
+
+    fn key(self) -> &'a str {
+        &self.text
+    }
+
+    fn tail(self) -> &'a [String] {
+        &self.tail
+    }
+
+    fn statics(self) -> usize {
+        self.static_children.len()
+    }
+
+    fn nth_static(self, idx: usize) -> Self {
+        &self.static_children[idx]
+    }
+
+    fn find_static(self, segment: &str, collation: Collation) -> Option<usize> {
+        self.static_child_index(segment, collation)
+    }
+
+    fn params(self) -> usize {
+        self.param_children.len()
+    }
+
+    fn nth_param(self, idx: usize) -> Self {
+        &self.param_children[idx]
+    }
+
+    fn catch_all(self) -> Option<Self> {
+        self.catch_all_child.as_deref()
+    }
+
+    fn is(self, other: Self) -> bool {
+        core::ptr::eq(self, other)
     }
 }
 
+// match the path from `start` below the node, `pending` are tail keys of
+// the node still to match, alternatives are tried in order until one
+// reaches a route, a dead end leaves the trail as it was except for the
+// fallback; a collecting trail keeps every route reached and goes on
+fn descend<'a, 'p, T: 'a, N: NodeRef<'a, T>, S: Text + ?Sized>(
+    options: &Options,
+    node: N,
+    pending: &'a [String],
+    path: &'p S,
+    start: usize,
+    trail: &mut Trail<'a, 'p, T, S, N>,
+) -> Option<N> {
+    if pending.is_empty() && node.has_fallback() {
+        trail.set_fallback(node, start);
+    }
+    let (offset, segment, next) = match next_segment(path, start) {
+        Some(found) => found,
+        None if pending.is_empty() => {
+            let route = node.data_node()?;
+            if !trail.accepts(route) {
+                return None;
+            }
+            return match trail.collect(node) {
+                true => None,
+                false => Some(node),
+            };
+        }
+        None => return None,
+    };
+    let segment = segment.decode(options)?;
+    // static keys are strings, invalid UTF-8 never matches them
+    let text = segment.text();
+
+    let collation = options.collation;
+    if let Some((first, rest)) = pending.split_first() {
+        if !text.is_some_and(|s| collation.compare(first, s).is_eq()) {
+            return None;
+        }
+        return descend(options, node, rest, path, next, trail);
+    }
+
+    if let Some(idx) = text.and_then(|s| node.find_static(s, collation)) {
+        let child = node.nth_static(idx);
+        trail.push_step(Step::Static(idx));
+        if let Some(found) = descend(options, child, child.tail(), path, next, trail) {
+            return Some(found);
+        }
+        trail.pop_step();
+    }
+
+    let mark = trail.values.len();
+    let step_mark = trail.step_mark();
+    let mut winner = None; // constrained route tied with the later ones
+    for idx in 0..node.params() {
+        let child = node.nth_param(idx);
+        let constraint = child.constraint();
+        if !text.map_or(constraint.is_none(), |s| child.accepts(s)) {
+            continue;
+        }
+        // the unconstrained parameter goes last and never ties
+        if winner.is_some() && constraint.is_none() {
+            break;
+        }
+        trail.push_step(Step::Param(idx));
+        match constraint {
+            Some(Constraint::Template(t)) => {
+                trail.values.extend(template_values(t, segment.clone()))
+            }
+            _ => trail.values.push(segment.clone()),
+        }
+        let found = descend(options, child, &[], path, next, trail);
+        let tie_breaker = options
+            .tie_breaker
+            .as_ref()
+            .filter(|_| constraint.is_some());
+        match (found, tie_breaker) {
+            (None, _) => {
+                trail.values.truncate(mark);
+                trail.pop_step();
+            }
+            (Some(found), None) => return Some(found),
+            (Some(found), Some(tie_breaker)) => {
+                let branch = (found, trail.split_off(mark, step_mark));
+                winner = match winner.take() {
+                    None => Some(branch),
+                    Some(first) => {
+                        let tie = Tie {
+                            path: &path.lossy(),
+                            first: first.0.data_node()?.info(),
+                            second: branch.0.data_node()?.info(),
+                        };
+                        match tie_breaker.decide(&tie) {
+                            Pick::First => Some(first),
+                            Pick::Second => Some(branch),
+                        }
+                    }
+                };
+            }
+        }
+    }
+    if let Some((found, branch)) = winner {
+        trail.extend(branch);
+        return Some(found);
+    }
+
+    let child = node.catch_all()?;
+    trail.push_step(Step::CatchAll);
+    // routes going on after the catch all are tried on the shortest
+    // suffix first, so the catch all takes as many segments as it can
+    let rest = &path.as_bytes()[offset..];
+    if child.statics() + child.params() > 0 {
+        for i in (0..rest.len()).rev().filter(|&i| rest[i] == b'/') {
+            let end = offset + i;
+            if !options.takes_catch_all(i) {
+                continue;
+            }
+            let value = match path.slice(offset..end).decode(options) {
+                Some(value) => value,
+                None => continue,
+            };
+            trail.tail = Some((trail.values.len(), offset..end));
+            trail.values.push(value);
+            let collected = trail.collected();
+            match descend(options, child, &[], path, end + 1, trail) {
+                Some(found) if !found.is(child) => return Some(found),
+                _ => {
+                    // the catch all itself is reached below as it takes
+                    // the rest
+                    trail.forget(collected, child);
+                    trail.values.truncate(mark);
+                    trail.tail = None;
+                }
+            }
+        }
+    }
+    if let Some(route) = child.data_node() {
+        let len = match route
+            .info()
+            .catch_all_slash
+            .unwrap_or(options.catch_all_slash)
+        {
+            TrailingSlash::Keep => rest.len(),
+            TrailingSlash::Strip => rest.iter().rposition(|&b| b != b'/').map_or(0, |i| i + 1),
+        };
+        let value = match options.takes_catch_all(len) {
+            true => path.slice(offset..offset + len).decode(options),
+            false => None,
+        };
+        if let Some(value) = value {
+            trail.tail = Some((trail.values.len(), offset..offset + len));
+            trail.values.push(value);
+            if trail.accepts(route) && !trail.collect(child) {
+                return Some(child);
+            }
+            trail.values.truncate(mark);
+            trail.tail = None;
+        }
+    }
+    trail.pop_step();
+    None
+}
+
 /// match result of a path
 #[derive(Debug)]
-pub struct Match<T> {
+pub struct Match<'a, T> {
     /// data set in the route
     pub data: T,
     /// extracted parameters from the path
-    pub params: BTreeMap<String, String>,
+    pub params: Params,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+}
+
+/// information attached to a route besides its data
+///
+/// it's set with `Router::add_with_info` and returned in `Match`, so
+/// middlewares can read it without encoding everything into the data
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RouteInfo {
+    /// reference to the JSON Schema of the request body, e.g.
+    /// `#/components/schemas/NewUser`
+    pub request_schema: Option<String>,
+    /// reference to the JSON Schema of the response body
+    pub response_schema: Option<String>,
+    /// short description of the route, e.g. for API docs
+    pub summary: Option<String>,
+    /// tags of the route, e.g. to group routes in API docs
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// free-form annotations by key, e.g. `auth` set to `admin` for an auth
+    /// middleware
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotations: BTreeMap<String, String>,
+    /// preferred compression of the responses
+    pub compression: Option<Compression>,
+    /// transformations of parameter values by parameter name, they run in
+    /// order before the values land in `Params`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub transforms: Vec<(String, Transform)>,
+    /// trailing slashes of the catchall ending the route, in place of
+    /// `Options::catch_all_slash`
+    pub catch_all_slash: Option<TrailingSlash>,
+    /// template of the request ids of the route, parameter names in braces
+    /// like `user-{id}-repos`, rendered by `Match::request_id`
+    pub id_template: Option<String>,
+    /// the most bytes a request body of the route may have, enforced by
+    /// `hyper::RequestRouter::dispatch`
+    pub max_body_bytes: Option<u64>,
+    /// circuit breaker state of the route, shared by its matches
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub breaker: Option<Arc<Breaker>>,
+    /// predicate the parameters of a match must pass, see
+    /// `Router::add_guarded`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub guard: Option<Guard>,
+}
+
+/// response compression preferred by a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Compression {
+    pub encoding: Encoding,
+    /// responses smaller than this number of bytes are not compressed
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_size: usize,
+}
+
+/// content coding of a response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Encoding {
+    /// don't compress, e.g. for already compressed bodies
+    #[cfg_attr(feature = "serde", serde(alias = "none"))]
+    Identity,
+    Gzip,
+    /// brotli, `br` in `Content-Encoding`
+    #[cfg_attr(feature = "serde", serde(rename = "br"))]
+    Brotli,
+}
+
+impl Encoding {
+    /// token of the coding in `Content-Encoding` and `Accept-Encoding`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            Encoding::Gzip => "gzip",
+            Encoding::Brotli => "br",
+        }
+    }
+}
+
+/// match result of a route made of generic keys
+#[derive(Debug)]
+pub struct KeyMatch<'p, T, K> {
+    /// data set in the route
+    pub data: T,
+    /// extracted parameters from the key path, catch all may hold several keys
+    pub params: BTreeMap<String, &'p [K]>,
+}
+
+/// match result of a path of raw bytes, see `Router::recognize_bytes`
+#[derive(Debug)]
+pub struct BytesMatch<'a, 'p, T> {
+    /// data set in the route
+    pub data: T,
+    /// extracted parameters from the path, borrowed from it unless they're
+    /// decoded or transformed
+    pub params: BTreeMap<String, Cow<'p, [u8]>>,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+}
+
+/// segment of a route made of generic keys
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<K> {
+    /// match the key exactly
+    Static(K),
+    /// catch one key as the named parameter
+    Param(String),
+    /// catch all the rest keys as the named parameter
+    CatchAll(String),
+}
+
+impl Segment<String> {
+    // parameter may carry an inline type like `:id<u32>`, a segment with
+    // parameters after its start is a template like `:name.:ext`
+    fn parse(
+        segment: &str,
+        matchers: &[Arc<dyn SegmentMatcher>],
+    ) -> Result<(Segment<String>, Option<Constraint>), Error> {
+        if is_template(segment) {
+            let template = Template::parse(segment)?;
+            let name = template.names()[0].clone();
+            Ok((Segment::Param(name), Some(Constraint::Template(template))))
+        } else if let Some(name) = segment.strip_prefix(':') {
+            let (name, ty) = split_param_type(name);
+            let constraint = match ty {
+                Some(ty) => match ParamType::from_name(ty) {
+                    Ok(ty) => Some(Constraint::Type(ty)),
+                    Err(e) => match matchers.iter().find(|m| m.name() == ty) {
+                        Some(m) => Some(Constraint::Custom(m.clone())),
+                        None => return Err(e),
+                    },
+                },
+                None => None,
+            };
+            Ok((Segment::Param(String::from(name)), constraint))
+        } else if let Some(name) = segment.strip_prefix('*') {
+            Ok((Segment::CatchAll(String::from(name)), None))
+        } else {
+            Ok((Segment::Static(segment.to_string()), None))
+        }
+    }
+}
+
+// whether the parameters of a route are named with allowed characters
+fn check_names(
+    segments: &[(Segment<String>, Option<Constraint>)],
+    chars: NameChars,
+) -> Result<(), Error> {
+    for (i, (segment, constraint)) in segments.iter().enumerate() {
+        let names = match (segment, constraint) {
+            (_, Some(Constraint::Template(t))) => t.names(),
+            (Segment::Param(name), _) | (Segment::CatchAll(name), _) => core::slice::from_ref(name),
+            (Segment::Static(_), _) => &[],
+        };
+        if let Some(name) = names.iter().find(|n| !n.chars().all(|c| chars.allows(c))) {
+            return Err(Error::new(Reason::InvalidName(name.clone())).at(i));
+        }
+    }
+    Ok(())
+}
+
+fn is_template(segment: &str) -> bool {
+    !segment.starts_with('*') && segment.chars().skip(1).any(|c| c == ':')
+}
+
+fn check_route_shape(route: &str) -> Result<(), Error> {
+    if !route.starts_with('/') {
+        return Err(Error::new(Reason::NoLeadingSlash));
+    }
+
+    if route.len() > 1 && route.ends_with('/') {
+        return Err(Error::new(Reason::TrailingSlash));
+    }
+
+    Ok(())
+}
+
+// check a route in the syntax of `Router::add`, faults of segments are
+// located at them
+pub(crate) fn check_route(route: &str) -> Result<(), Error> {
+    check_route_shape(route)?;
+
+    if route.len() == 1 {
+        return Ok(());
+    }
+
+    let path = &route[1..];
+    let count = path.split('/').count();
+    let mut checker = BTreeSet::new();
+    let mut has_catch_all = false;
+    for (i, segment) in path.split('/').enumerate() {
+        let fault = |reason| Err(Error::new(reason).at(i));
+        if segment.is_empty() {
+            return fault(Reason::EmptySegment);
+        }
+        let last = i + 1 == count;
+        let wildcard = segment.starts_with(':') || segment.starts_with('*');
+        if wildcard && segment.ends_with('?') && !(last && segment.starts_with(':')) {
+            return fault(Reason::MisplacedOptional);
+        }
+        if is_template(segment) {
+            let mut segment = segment;
+            if segment.starts_with(':') && last {
+                segment = segment.strip_suffix('?').unwrap_or(segment);
+            }
+            let template = Template::parse(segment).map_err(|e| e.at(i))?;
+            if let Some(name) = template
+                .names()
+                .iter()
+                .find(|n| !checker.insert((*n).clone()))
+            {
+                return fault(Reason::DuplicateName(name.clone()));
+            }
+            continue;
+        }
+        if wildcard {
+            let mut name = &segment[1..];
+            if segment.starts_with(':') && last {
+                name = name.strip_suffix('?').unwrap_or(name);
+            }
+            let (name, _) = split_param_type(name);
+            if name.is_empty() {
+                return fault(Reason::EmptyName);
+            }
+            if !checker.insert(String::from(name)) {
+                return fault(Reason::DuplicateName(String::from(name)));
+            }
+        }
+
+        // one catch all, it can be followed by other segments
+        if segment.starts_with('*') {
+            if has_catch_all {
+                return fault(Reason::SecondCatchAll);
+            }
+            has_catch_all = true
+        }
+    }
+
+    Ok(())
+}
+
+// values of the parameters of a segment matched by a template
+fn template_values<'p, S: Text + ?Sized>(
+    template: &Template,
+    segment: Cow<'p, S>,
+) -> Vec<Cow<'p, S>> {
+    let values = match S::into_text(segment) {
+        Some(Cow::Borrowed(s)) => template
+            .captures(s)
+            .unwrap_or_default()
+            .into_iter()
+            .map(Cow::Borrowed)
+            .collect(),
+        Some(Cow::Owned(s)) => template
+            .captures(&s)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|v| Cow::Owned(String::from(v)))
+            .collect(),
+        None => vec![],
+    };
+    values.into_iter().map(S::from_text).collect()
+}
+
+// the next non-empty segment of a path from `start`, with its offset and
+// the start of the segment after it
+fn next_segment<S: Text + ?Sized>(path: &S, mut start: usize) -> Option<(usize, &S, usize)> {
+    let bytes = path.as_bytes();
+    while start < bytes.len() {
+        let end = match bytes[start..].iter().position(|&b| b == b'/') {
+            Some(i) => start + i,
+            None => bytes.len(),
+        };
+        if end > start {
+            return Some((start, path.slice(start..end), end + 1));
+        }
+        start = end + 1;
+    }
+    None
+}
+
+fn split_param_type(name: &str) -> (&str, Option<&str>) {
+    if name.ends_with('>') {
+        if let Some(i) = name.find('<') {
+            return (&name[..i], Some(&name[i + 1..name.len() - 1]));
+        }
+    }
+    (name, None)
 }
 
 /// A generic path router
@@ -63,136 +1020,566 @@ pub struct Match<T> {
 /// assert_eq!(*router.recognize("/user/:id/repos").unwrap().data, 2);
 /// assert_eq!(*router.recognize("/list/*animals").unwrap().data, 3);
 /// ```
-pub struct Router<T> {
+///
+/// the segments are strings by default, any `Ord` key works with
+/// `add_keys` and `recognize_keys`, e.g. bytes or numeric topic ids
+///
+/// ```
+/// use path_router::{Router, Segment};
+/// let mut router = Router::<_, u32>::default();
+/// router
+///     .add_keys(vec![Segment::Static(7), Segment::Param("id".into())], 1)
+///     .unwrap();
+///
+/// let m = router.recognize_keys(&[7, 42]).unwrap();
+/// assert_eq!(*m.data, 1);
+/// assert_eq!(m.params["id"], &[42]);
+/// ```
+#[derive(Clone)]
+pub struct Router<T, K = String> {
     kind: NodeKind,
-    text: String, // text of static node, empty string if it's wildcard node
+    text: K,      // key of static node, default key if it's wildcard node
+    tail: Vec<K>, // static keys of a single child chain merged into this static node
     data: Option<T>,
-    params: Vec<String>, // param or catchall keys of the route, order by their occurrences
-    static_children: Vec<Router<T>>,
-    param_child: Box<Option<Router<T>>>,
-    catch_all_child: Box<Option<Router<T>>>,
+    fallback: Option<T>, // data for paths missing beneath this static node
+    info: RouteInfo,
+    params: Vec<Arc<str>>, // param or catchall keys of the route, order by their occurrences, shared with `Params`
+    base: Vec<Arc<str>>, // param or catchall keys above the node when it's the base of a sub route
+    constraint: Option<Constraint>, // only set on param node
+    optional: bool,      // param node whose data is also reached from its parent
+    static_children: StaticChildren<T, K>,
+    param_children: Vec<Router<T, K>>, // constrained ones go first
+    catch_all_child: Option<Box<Router<T, K>>>,
+    options: Arc<Options>, // shared with the nodes below, copied on write
 }
 
-impl<T> Default for Router<T> {
-    fn default() -> Router<T> {
-        Router::<T> {
+impl<T, K: Default> Default for Router<T, K> {
+    fn default() -> Router<T, K> {
+        Router::empty(Arc::default())
+    }
+}
+
+impl<T, K: Default> Router<T, K> {
+    fn empty(options: Arc<Options>) -> Router<T, K> {
+        Router::<T, K> {
             kind: NodeKind::default(),
-            text: String::from(""),
+            text: K::default(),
+            tail: vec![],
             data: None,
+            fallback: None,
+            info: RouteInfo::default(),
             params: vec![],
-            static_children: vec![],
-            param_child: Box::new(None),
-            catch_all_child: Box::new(None),
+            base: vec![],
+            constraint: None,
+            optional: false,
+            static_children: StaticChildren::default(),
+            param_children: vec![],
+            catch_all_child: None,
+            options,
         }
     }
 }
 
-// impl<T> std::fmt::Debug for Router<T> {
-//     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         unimplemented!()
-//     }
-// }
+/// dump the node tree, one node per line indented by depth
+///
+/// static nodes show their keys, merged chains joined by '/', parameter
+/// nodes show ':' and their constraint, catchall nodes '*', nodes holding
+/// data show their parameter names and data, then their fallback if any
+///
+/// ```text
+/// root => 0
+///   "files"
+///     * [path] => 3
+///   "user"
+///     : [id] => 1
+///       "posts"/"all" [id] => 2
+/// ```
+impl<T: fmt::Debug, K: fmt::Debug> fmt::Debug for Router<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_tree(f, 0)
+    }
+}
+
+impl<T: fmt::Debug, K: fmt::Debug> Router<T, K> {
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}", "", width = depth * 2)?;
+        match self.kind {
+            NodeKind::Static if depth == 0 => write!(f, "root")?,
+            NodeKind::Static => {
+                write!(f, "{:?}", self.text)?;
+                for key in self.tail.iter() {
+                    write!(f, "/{:?}", key)?;
+                }
+            }
+            NodeKind::Param => {
+                write!(f, ":")?;
+                if let Some(ref c) = self.constraint {
+                    write!(f, " {:?}", c)?;
+                }
+            }
+            NodeKind::CatchAll => write!(f, "*")?,
+        }
+        if let Some(ref data) = self.data {
+            if !self.params.is_empty() {
+                write!(f, " [{}]", self.params.join(", "))?;
+            }
+            if self.optional {
+                write!(f, " optional")?;
+            }
+            write!(f, " => {:?}", data)?;
+        }
+        if let Some(ref fallback) = self.fallback {
+            write!(f, " fallback {:?}", fallback)?;
+        }
+        writeln!(f)?;
+
+        for node in self.static_children.iter() {
+            node.fmt_tree(f, depth + 1)?;
+        }
+        for node in self.param_children.iter() {
+            node.fmt_tree(f, depth + 1)?;
+        }
+        if let Some(ref node) = self.catch_all_child {
+            node.fmt_tree(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
 
-// Router as node
 impl<T> Router<T> {
     pub fn new() -> Router<T> {
         Router::default()
     }
 
-    fn new_static_node(segment: &str) -> Router<T> {
+    /// create a router with options
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Options, Router};
+    /// let mut router = Router::with_options(Options {
+    ///     fullwidth: true,
+    ///     ..Options::default()
+    /// });
+    /// router.add("/user/:id", 1).unwrap();
+    ///
+    /// let m = router.recognize("／ｕｓｅｒ／４２").unwrap();
+    /// assert_eq!(m.params["id"], "42");
+    /// ```
+    pub fn with_options(options: Options) -> Router<T> {
+        Router::empty(Arc::new(options))
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn options_mut(&mut self) -> &mut Options {
+        Arc::make_mut(&mut self.options)
+    }
+}
+
+// Router as node
+impl<T, K: Ord + Default> Router<T, K> {
+    // child nodes share the options of their parent
+    fn new_static_node(&self, key: K) -> Router<T, K> {
         Router {
-            text: segment.to_string(),
-            ..Router::default()
+            text: key,
+            ..Router::empty(Arc::clone(&self.options))
         }
     }
 
-    fn new_param_node() -> Router<T> {
+    fn new_param_node(&self, constraint: Option<Constraint>) -> Router<T, K> {
         Router {
             kind: NodeKind::Param,
-            ..Router::default()
+            constraint,
+            ..Router::empty(Arc::clone(&self.options))
         }
     }
 
-    fn new_cache_all_node() -> Router<T> {
+    fn new_cache_all_node(&self) -> Router<T, K> {
         Router {
             kind: NodeKind::CatchAll,
-            ..Router::default()
+            ..Router::empty(Arc::clone(&self.options))
         }
     }
 
-    fn child_index(&self, segment: &str) -> Option<usize> {
-        if let Ok(i) = self.static_children.binary_search_by(|n| {
-            let name = &(n.text)[..];
-            name.cmp(segment)
-        }) {
-            return Some(i);
+    fn child_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.static_children
+            .binary_search_by(|n| n.text.borrow().cmp(key))
+            .ok()
+    }
+
+    fn will_conflit(&self, segment: &Segment<K>) -> bool {
+        match segment {
+            Segment::Param(_) => self.catch_all_child.is_some(),
+            Segment::CatchAll(_) => !self.param_children.is_empty(),
+            Segment::Static(_) => false,
         }
-        None
     }
 
-    fn will_conflit(&self, segment: &str) -> bool {
-        if segment.starts_with(':') && self.catch_all_child.is_some() {
-            return true;
+    fn add_segment(
+        &mut self,
+        segment: Segment<K>,
+        constraint: Option<Constraint>,
+        collate: Collate<K>,
+    ) -> Option<&mut Router<T, K>> {
+        if self.will_conflit(&segment) {
+            return None;
         }
-        if segment.starts_with('*') && self.param_child.is_some() {
-            return true;
+
+        match segment {
+            Segment::Param(_) => {
+                let children = &self.param_children;
+                let idx = match children.iter().position(|n| n.constraint == constraint) {
+                    Some(idx) => idx,
+                    None => {
+                        let idx = match constraint {
+                            Some(_) => children.iter().filter(|n| n.constraint.is_some()).count(),
+                            None => children.len(),
+                        };
+                        let child = self.new_param_node(constraint);
+                        self.param_children.insert(idx, child);
+                        idx
+                    }
+                };
+                Some(&mut self.param_children[idx])
+            }
+            Segment::CatchAll(_) => {
+                if self.catch_all_child.is_none() {
+                    self.catch_all_child = Some(Box::new(self.new_cache_all_node()));
+                }
+                self.catch_all_child.as_deref_mut()
+            }
+            Segment::Static(key) => Some(self.add_statics(vec![key], collate)),
         }
+    }
 
-        false
+    // descend through a run of static keys, creating the missing part as one
+    // compressed node and splitting a compressed node where the run leaves it
+    fn add_statics(&mut self, keys: Vec<K>, collate: Collate<K>) -> &mut Router<T, K> {
+        let mut last = self;
+        let mut keys = keys.into_iter().peekable();
+        while let Some(key) = keys.next() {
+            match last
+                .static_children
+                .binary_search_by(|n| collate(&n.text, &key))
+            {
+                Ok(idx) => {
+                    let child = &mut last.static_children[idx];
+                    let mut matched = 0;
+                    while matched < child.tail.len()
+                        && keys
+                            .peek()
+                            .is_some_and(|k| collate(k, &child.tail[matched]).is_eq())
+                    {
+                        keys.next();
+                        matched += 1;
+                    }
+                    child.split_at(matched);
+                    last = child;
+                }
+                Err(idx) => {
+                    let mut node = last.new_static_node(key);
+                    node.tail = keys.by_ref().collect();
+                    last.static_children.insert(idx, node);
+                    last = &mut last.static_children[idx];
+                }
+            }
+        }
+        last
     }
 
-    fn param_name(&self, segment: &str) -> Option<String> {
-        if segment.starts_with(':') || segment.starts_with('*') {
-            Some(String::from(&segment[1..]))
-        } else {
-            None
+    // keep the first `at` keys of the tail, the rest and everything below
+    // moves to a new single static child
+    fn split_at(&mut self, at: usize) {
+        if at >= self.tail.len() {
+            return;
         }
+        let mut rest = self.tail.split_off(at);
+        let text = rest.remove(0);
+        let child = Router {
+            tail: rest,
+            data: self.data.take(),
+            fallback: self.fallback.take(),
+            info: core::mem::take(&mut self.info),
+            params: core::mem::take(&mut self.params),
+            base: core::mem::take(&mut self.base),
+            static_children: core::mem::take(&mut self.static_children),
+            param_children: core::mem::take(&mut self.param_children),
+            catch_all_child: self.catch_all_child.take(),
+            ..self.new_static_node(text)
+        };
+        self.static_children.push(child);
+    }
+
+    fn set_data(&mut self, data: T) {
+        self.data = Some(data)
     }
 
-    fn add_segment(&mut self, segment: &str) -> Result<&mut Router<T>, Error> {
-        if self.will_conflit(segment) {
-            return Err(Error::RouteConflict);
+    fn check_keys(&self, route: &[Segment<K>]) -> Result<(), Error> {
+        let mut checker = BTreeSet::new();
+        let mut has_catch_all = false;
+        for (i, segment) in route.iter().enumerate() {
+            let fault = |reason| Err(Error::new(reason).at(i));
+            if has_catch_all {
+                return fault(Reason::CatchAllNotLast);
+            }
+            let name = match segment {
+                Segment::Param(name) => name,
+                Segment::CatchAll(name) => {
+                    has_catch_all = true;
+                    name
+                }
+                Segment::Static(_) => continue,
+            };
+            if name.is_empty() {
+                return fault(Reason::EmptyName);
+            }
+            if !checker.insert(name) {
+                return fault(Reason::DuplicateName(name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    // the node holding data in place of this node
+    fn data_node(&self) -> Option<&Router<T, K>> {
+        if self.data.is_some() {
+            return Some(self);
+        }
+        self.optional_child()
+    }
+
+    fn optional_child(&self) -> Option<&Router<T, K>> {
+        self.param_children
+            .iter()
+            .find(|n| n.optional && n.data.is_some())
+    }
+
+    fn insert(
+        &mut self,
+        route: Vec<(Segment<K>, Option<Constraint>)>,
+        optional: bool,
+        data: T,
+        collate: Collate<K>,
+    ) -> Result<&mut T, Error> {
+        let last = self.insert_node(route, optional, collate)?;
+        last.set_data(data);
+        match last.data {
+            Some(ref mut d) => Ok(d),
+            None => panic!("impossible"),
         }
+    }
 
-        if segment.starts_with(':') {
-            return match *self.param_child {
-                Some(ref mut n) => Ok(n),
-                None => {
-                    self.param_child = Box::new(Some(Router::new_param_node()));
-                    match *self.param_child {
-                        Some(ref mut n) => Ok(n),
-                        None => panic!("impossible"),
+    // create the node of a route, the data is left to the caller
+    // optional means the last segment, which must be a parameter, may be absent
+    fn insert_node(
+        &mut self,
+        route: Vec<(Segment<K>, Option<Constraint>)>,
+        optional: bool,
+        collate: Collate<K>,
+    ) -> Result<&mut Router<T, K>, Error> {
+        let n = route.len();
+        let mut params = self.base.clone();
+        let mut last = self;
+        let mut statics = vec![];
+        for (i, (segment, constraint)) in route.into_iter().enumerate() {
+            match segment {
+                Segment::Param(ref name) | Segment::CatchAll(ref name) => match constraint {
+                    Some(Constraint::Template(ref t)) => {
+                        params.extend(t.names().iter().map(|n| Arc::from(n.as_str())))
                     }
+                    _ => params.push(Arc::from(name.as_str())),
+                },
+                Segment::Static(key) => {
+                    statics.push(key);
+                    continue;
                 }
+            }
+            if !statics.is_empty() {
+                last = last.add_statics(core::mem::take(&mut statics), collate);
+            }
+            if optional && i + 1 == n && last.data_node().is_some() {
+                return Err(conflict(i));
+            }
+            last = match last.add_segment(segment, constraint, collate) {
+                Some(node) => node,
+                None => return Err(conflict(i)),
             };
         }
+        if !statics.is_empty() {
+            last = last.add_statics(statics, collate);
+        }
 
-        if segment.starts_with('*') {
-            return match *self.catch_all_child {
-                Some(ref mut n) => return Ok(n),
-                None => {
-                    self.catch_all_child = Box::new(Some(Router::new_cache_all_node()));
-                    match *self.catch_all_child {
-                        Some(ref mut n) => Ok(n),
-                        None => panic!("impossible"),
+        let end = n.saturating_sub(1);
+        if !optional && last.optional_child().is_some() {
+            return Err(conflict(end));
+        }
+        if last.data.is_some() && last.optional != optional {
+            return Err(conflict(end));
+        }
+
+        if !params.is_empty() && last.params.is_empty() {
+            last.params = params;
+        } else if params != last.params {
+            return Err(conflict(end));
+        }
+
+        last.optional = optional;
+        Ok(last)
+    }
+
+    // the segment where a route would conflict with the existing nodes, the
+    // same checks as `insert_node` without touching the trie
+    fn find_conflict(
+        &self,
+        route: &[(Segment<K>, Option<Constraint>)],
+        optional: bool,
+        collate: Collate<K>,
+    ) -> Option<usize> {
+        let n = route.len();
+        let mut node = self;
+        let mut pending: &[K] = &[]; // tail keys of the static node still to match
+        let mut params = self.base.clone();
+        for (i, (segment, constraint)) in route.iter().enumerate() {
+            match segment {
+                Segment::Static(key) => {
+                    if let Some((first, rest)) = pending.split_first() {
+                        // leaving a compressed chain splits it into new nodes
+                        if !collate(first, key).is_eq() {
+                            return None;
+                        }
+                        pending = rest;
+                    } else {
+                        let idx = node
+                            .static_children
+                            .binary_search_by(|c| collate(&c.text, key))
+                            .ok()?;
+                        node = &node.static_children[idx];
+                        pending = &node.tail;
                     }
+                    continue;
                 }
+                Segment::Param(name) | Segment::CatchAll(name) => match constraint {
+                    Some(Constraint::Template(t)) => {
+                        params.extend(t.names().iter().map(|n| Arc::from(n.as_str())))
+                    }
+                    _ => params.push(Arc::from(name.as_str())),
+                },
+            }
+            if !pending.is_empty() {
+                return None;
+            }
+            if (optional && i + 1 == n && node.data_node().is_some()) || node.will_conflit(segment)
+            {
+                return Some(i);
+            }
+            node = match segment {
+                Segment::CatchAll(_) => node.catch_all_child.as_deref()?,
+                _ => node
+                    .param_children
+                    .iter()
+                    .find(|c| c.constraint == *constraint)?,
             };
         }
+        if !pending.is_empty() {
+            return None;
+        }
 
-        if self.child_index(segment).is_none() {
-            self.static_children.push(Router::new_static_node(segment));
-            self.static_children.sort_by(|a, b| a.text.cmp(&b.text))
+        let conflicts = (!optional && node.optional_child().is_some())
+            || (node.data.is_some() && node.optional != optional)
+            || (params != node.params && (params.is_empty() || !node.params.is_empty()));
+        if conflicts {
+            return Some(n.saturating_sub(1));
         }
-        let idx = self.child_index(segment).unwrap();
-        return Ok(&mut self.static_children[idx]);
+        None
     }
 
-    fn set_data(&mut self, data: T) {
-        self.data = Some(data)
+    /// set a route made of generic keys with data
+    ///
+    /// empty parameter name or empty catchall name is not allowed
+    /// there can be one catchall, segments after it like "/repo/*path/meta"
+    /// match the end of the path and the catchall takes as many segments as
+    /// it can
+    /// parameter namse and catchall name must not be duplicated
+    pub fn add_keys(&mut self, route: Vec<Segment<K>>, data: T) -> Result<&mut T, Error> {
+        self.check_keys(&route)?;
+        let route = route.into_iter().map(|s| (s, None)).collect();
+        self.insert(route, false, data, &K::cmp)
+    }
+
+    /// recognize a path made of generic keys
+    ///
+    /// static keys take precedence over parameters, parameters take
+    /// precedence over catchall
+    pub fn recognize_keys<'a, 'p, Q>(&'a self, path: &'p [Q]) -> Option<KeyMatch<'p, &'a T, Q>>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut last = self;
+        let mut values = vec![];
+        let mut pending: &[K] = &[]; // tail keys of the last node still to match
+        for (i, key) in path.iter().enumerate() {
+            if let Some((first, rest)) = pending.split_first() {
+                if first.borrow() != key {
+                    return None;
+                }
+                pending = rest;
+                continue;
+            }
+
+            if let Some(idx) = last.child_index(key) {
+                last = &last.static_children[idx];
+                pending = &last.tail;
+                continue;
+            }
+
+            if let Some(node) = last.param_children.iter().find(|n| n.constraint.is_none()) {
+                values.push(&path[i..=i]);
+                last = node;
+                continue;
+            }
+
+            if let Some(ref node) = last.catch_all_child {
+                values.push(&path[i..]);
+                last = node;
+                break;
+            }
+
+            return None; // miss
+        }
+        if !pending.is_empty() {
+            return None;
+        }
+
+        let last = last.data_node()?;
+        match last.data {
+            Some(ref data) => {
+                let mut params = BTreeMap::new();
+                for (k, v) in last.params.iter().zip(values) {
+                    params.insert(String::from(&**k), v);
+                }
+                Some(KeyMatch { data, params })
+            }
+            None => None,
+        }
     }
 }
 
+// order of static keys, `Ord` for the generic keys and the collation of the
+// options for the string API
+type Collate<'c, K> = &'c dyn Fn(&K, &K) -> Ordering;
+
+/// segments of a route with their constraints and whether its last
+/// parameter is optional, see `RouteParser`
+pub type ParsedRoute = (Vec<(Segment<String>, Option<Constraint>)>, bool);
+
 /// Router as router
 impl<T> Router<T> {
     /// set a route with data
@@ -200,79 +1587,453 @@ impl<T> Router<T> {
     /// a route must start with '/' and have no trailing '/'
     /// empty text between '/' is not allowed
     /// empty parameter name or empty catchall name like "/a/:/b" or "/a/*" is not allowed
-    /// catchall must be the last segment if any
+    /// there can be one catchall, segments after it like "/repo/*path/meta"
+    /// match the end of the path and the catchall takes as many segments as
+    /// it can
     /// parameter namse and catchall name must not be duplicated
+    /// the last parameter can be optional like "/articles/:id/:slug?", the
+    /// route matches with or without it
+    /// a segment with a ':' after its start like "/files/:name.:ext" or
+    /// "/api/v:major" mixes literals and parameters, see `Template`
     pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
-        if !self.is_valid_route(route) {
-            return Err(Error::InvalidFormat);
-        }
+        self.add_constrained(route, None, data)
+    }
 
-        let path = &route[1..];
-        let mut last = self;
-        let mut params = vec![];
-        for segment in path.split('/') {
-            if segment.len() == 0 {
-                break;
-            }
+    /// set a route with data, the last parameter of the route only catches
+    /// segments passing the constraint
+    ///
+    /// a parameter can also be constrained inline with its expected type,
+    /// like `/user/:id<u32>` or `/archive/:date<yyyy-mm-dd>`, see `ParamType`
+    ///
+    /// paths failing the constraint fall through to the other parameters at
+    /// the same position, constrained parameters are tried in the order they
+    /// were added, unconstrained parameter is tried last
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "regex")]
+    /// # {
+    /// use path_router::{Constraint, Router};
+    /// let mut router = Router::default();
+    /// let digits = Constraint::regex("[0-9]+").unwrap();
+    /// router.add_with_constraint("/user/:id", digits, 1).unwrap();
+    /// router.add("/user/:name", 2).unwrap();
+    ///
+    /// assert_eq!(*router.recognize("/user/42").unwrap().data, 1);
+    /// assert_eq!(*router.recognize("/user/abc").unwrap().data, 2);
+    /// # }
+    /// ```
+    pub fn add_with_constraint(
+        &mut self,
+        route: &str,
+        constraint: Constraint,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        self.add_constrained(route, Some(constraint), data)
+    }
 
-            let rs = last.add_segment(segment);
-            last = match rs {
-                Ok(r) => {
-                    match r.kind {
-                        NodeKind::Param | NodeKind::CatchAll => {
-                            params.push(r.param_name(segment).unwrap());
-                        }
-                        NodeKind::Static => (),
-                    }
-                    r
-                }
-                Err(err) => return Err(err),
-            };
+    /// set a route with data and information returned in its matches
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{RouteInfo, Router};
+    /// let mut router = Router::default();
+    /// let info = RouteInfo {
+    ///     request_schema: Some(String::from("#/components/schemas/NewUser")),
+    ///     ..RouteInfo::default()
+    /// };
+    /// router.add_with_info("/user", info, 1).unwrap();
+    ///
+    /// let m = router.recognize("/user").unwrap();
+    /// assert_eq!(
+    ///     m.route.request_schema.as_ref().unwrap(),
+    ///     "#/components/schemas/NewUser"
+    /// );
+    /// ```
+    pub fn add_with_info(
+        &mut self,
+        route: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        if let Some(ref template) = info.id_template {
+            let (segments, _) = self.parse_route(route, None)?;
+            let names = segments
+                .iter()
+                .flat_map(|(segment, constraint)| match constraint {
+                    Some(Constraint::Template(t)) => t.names().iter().map(String::as_str).collect(),
+                    _ => match segment {
+                        Segment::Param(name) | Segment::CatchAll(name) => vec![name.as_str()],
+                        Segment::Static(_) => vec![],
+                    },
+                });
+            request_id::check_id_template(template, names).map_err(|e| e.of(route))?;
         }
-
-        // refine codes here
-        if params.len() > 0 && last.params.len() == 0 {
-            last.params = params;
-        } else if params != last.params {
-            return Err(Error::RouteConflict);
+        let node = self.add_node(route, None)?;
+        node.info = info;
+        node.set_data(data);
+        match node.data {
+            Some(ref mut d) => Ok(d),
+            None => panic!("impossible"),
         }
+    }
 
-        last.set_data(data);
-        match last.data {
+    fn add_constrained(
+        &mut self,
+        route: &str,
+        constraint: Option<Constraint>,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        let node = self.add_node(route, constraint)?;
+        node.set_data(data);
+        match node.data {
             Some(ref mut d) => Ok(d),
             None => panic!("impossible"),
         }
     }
 
+    // create the node of a route, the data is left to the caller
+    fn add_node(
+        &mut self,
+        route: &str,
+        constraint: Option<Constraint>,
+    ) -> Result<&mut Router<T>, Error> {
+        let parsed = self.parse_route(route, constraint)?;
+        self.add_parsed(route, parsed)
+    }
+
+    // create the node of a parsed route written as `route`
+    fn add_parsed(
+        &mut self,
+        route: &str,
+        (segments, optional): ParsedRoute,
+    ) -> Result<&mut Router<T>, Error> {
+        let warning = match self.options.on_ambiguity {
+            Some(ref hook) => self.ambiguity(&segments).map(|a| (hook.clone(), a)),
+            None => None,
+        };
+        let collation = self.options.collation;
+        if let Some(segment) = self.find_conflict(&segments, optional, &collation.for_keys()) {
+            return Err(self.named_conflict(route, &(segments, optional), segment));
+        }
+        let node = self.insert_node(segments, optional, &collation.for_keys())?;
+        if let Some((hook, ambiguity)) = warning {
+            hook.call(&ambiguity);
+        }
+        Ok(node)
+    }
+
+    // the conflict of a route with the first existing route colliding with it
+    pub(crate) fn named_conflict(
+        &self,
+        route: &str,
+        parsed: &ParsedRoute,
+        segment: usize,
+    ) -> Error {
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+        let existing = routes
+            .iter()
+            .find(|(r, _)| merge::collides(r, parsed, self.options.collation))
+            .map(|(r, _)| merge::render_route(r))
+            .unwrap_or_default();
+        Error::new(Reason::Conflict { existing })
+            .at(segment)
+            .of(route)
+    }
+
+    // validate a route and split it into segments as normalized and decoded
+    // by the options
+    fn parse_route(
+        &self,
+        route: &str,
+        constraint: Option<Constraint>,
+    ) -> Result<ParsedRoute, Error> {
+        match self.options.parser {
+            Some(ref parser) => self.parse_route_with(&**parser, route, constraint),
+            None => self.parse_route_with(&ColonSyntax, route, constraint),
+        }
+    }
+
+    // `parse_route` with the syntax of a parser in place of the options'
+    fn parse_route_with(
+        &self,
+        parser: &dyn RouteParser,
+        route: &str,
+        constraint: Option<Constraint>,
+    ) -> Result<ParsedRoute, Error> {
+        let given = route;
+        let route = self.options.normalize(route);
+        let parsed = parser
+            .parse(&route, &self.options)
+            .and_then(|(segments, optional)| {
+                check_pattern(&segments, optional)?;
+                check_names(&segments, self.options.name_chars)?;
+                let mut segments = segments
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, segment)| match segment {
+                        (Segment::Static(text), c) => match self.options.decode(&text) {
+                            Some(text) => Ok((Segment::Static(text.into_owned()), c)),
+                            None => Err(Error::new(Reason::InvalidEscape).at(i)),
+                        },
+                        segment => Ok(segment),
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                if constraint.is_some() {
+                    // constraint goes to the last parameter segment of the route
+                    match segments
+                        .iter_mut()
+                        .rev()
+                        .find(|(s, _)| matches!(s, Segment::Param(_)))
+                    {
+                        Some((_, c @ None)) => *c = constraint,
+                        _ => return Err(Error::new(Reason::MisplacedConstraint)),
+                    }
+                }
+                Ok((segments, optional))
+            });
+        parsed.map_err(|e| e.of(given))
+    }
+
     /// create a sub route from current route
     ///
-    /// route must be static, parameters and catch all are not allowed
+    /// the base may have parameters and a catchall, they're caught for the
+    /// routes added to the sub route as if the base was written before them;
+    /// the base can't end with an optional parameter, and a base reached
+    /// again must name its parameters the same
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// let tenant = router.sub_route("/tenants/:tenant_id").unwrap();
+    /// tenant.add("/users/:id", 1).unwrap();
+    /// assert!(tenant.sub_route("/files/*path").unwrap().add("/raw", 2).is_ok());
+    ///
+    /// let m = router.recognize("/tenants/t1/users/7").unwrap();
+    /// assert_eq!((&m.params["tenant_id"][..], &m.params["id"][..]), ("t1", "7"));
+    /// let m = router.recognize("/tenants/t1/files/a/b/raw").unwrap();
+    /// assert_eq!((*m.data, &m.params["path"][..]), (2, "a/b"));
+    /// assert!(router.sub_route("/tenants/:tid").is_err());
+    /// ```
     pub fn sub_route(&mut self, route: &str) -> Result<&mut Router<T>, Error> {
-        if !self.is_valid_base(route) {
-            return Err(Error::InvalidFormat);
+        let (segments, optional) = self.parse_route(route, None)?;
+        if optional {
+            return Err(Error::new(Reason::MisplacedOptional).of(route));
         }
+        self.sub_route_node(segments).map_err(|e| e.of(route))
+    }
 
-        let path = &route[1..];
-        let mut last = self;
-        for segment in path.split('/') {
-            if segment.len() == 0 {
-                break;
+    // the node of `sub_route` for a parsed route
+    pub(crate) fn sub_route_node(
+        &mut self,
+        segments: Vec<(Segment<String>, Option<Constraint>)>,
+    ) -> Result<&mut Router<T>, Error> {
+        let options = Arc::clone(&self.options);
+        let mut base = self.base.clone();
+        for (segment, constraint) in &segments {
+            match (segment, constraint) {
+                (Segment::Static(_), _) => {}
+                (_, Some(Constraint::Template(t))) => {
+                    base.extend(t.names().iter().map(|n| Arc::from(n.as_str())))
+                }
+                (Segment::Param(name), _) | (Segment::CatchAll(name), _) => {
+                    base.push(Arc::from(name.as_str()))
+                }
+            }
+        }
+        let node = self.prefix_node(segments)?;
+        if !node.base.is_empty() && node.base != base {
+            return Err(Error::new(Reason::RenamedBase));
+        }
+        node.base = base;
+        // routes of the sub route are parsed with the options of this router
+        node.options = options;
+        Ok(node)
+    }
+
+    // the node a parsed prefix reaches, added with the nodes on the way if
+    // it's missing, parameters take the node of the same constraint
+    pub(crate) fn prefix_node(
+        &mut self,
+        segments: Vec<(Segment<String>, Option<Constraint>)>,
+    ) -> Result<&mut Router<T>, Error> {
+        let collate = self.options.collation.for_keys();
+        let mut node = self;
+        for (i, (segment, constraint)) in segments.into_iter().enumerate() {
+            node = match segment {
+                Segment::Static(key) => node.add_statics(vec![key], &collate),
+                segment => node
+                    .add_segment(segment, constraint, &collate)
+                    .ok_or_else(|| conflict(i))?,
+            };
+        }
+        Ok(node)
+    }
+
+    /// recognize a path
+    ///
+    /// path must start with '/'
+    /// segments like '..', '.' are matched as they are unless
+    /// `Options::dot_segments` says otherwise
+    /// static segments are tried first, then parameters, then the catch all,
+    /// a dead end goes back to the next alternative, so `/users/admin/profile`
+    /// matches `/users/:id/profile` beside `/users/admin/settings`
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        self.try_recognize(path).ok()
+    }
+
+    /// recognize a path like `recognize`, telling why it misses
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{DotSegments, Miss, Router};
+    /// let mut router = Router::default();
+    /// router.options_mut().dot_segments = DotSegments::Reject;
+    /// router.add("/user/:id", 1).unwrap();
+    ///
+    /// assert!(router.try_recognize("/user/1").is_ok());
+    /// assert_eq!(router.try_recognize("/users").unwrap_err(), Miss::NotFound);
+    /// assert_eq!(
+    ///     router.try_recognize("/user/../admin").unwrap_err(),
+    ///     Miss::DotSegments
+    /// );
+    /// ```
+    pub fn try_recognize<'a>(&'a self, path: &str) -> Result<Match<'a, &'a T>, Miss> {
+        match self.resolve(path) {
+            Resolution::Hit(m) | Resolution::Fallback(m) => Ok(m),
+            Resolution::MethodNotAllowed { .. } => Err(Miss::NotFound),
+            Resolution::Miss(miss) => Err(miss),
+        }
+    }
+
+    /// recognize a path like `recognize` with mutable access to the data
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", 0).unwrap();
+    ///
+    /// *router.recognize_mut("/user/1").unwrap().data += 1;
+    /// *router.recognize_mut("/user/2").unwrap().data += 1;
+    /// assert_eq!(*router.recognize("/user/3").unwrap().data, 2);
+    /// ```
+    pub fn recognize_mut<'a>(&'a mut self, path: &str) -> Option<Match<'a, &'a mut T>> {
+        let normalized = self.options.normalize_path(path).ok()?;
+        let mut trail = Trail::new(true);
+        let found = self.find(&normalized, &mut trail);
+        let route = found.and_then(Router::data_node);
+        let landing = self.landing(path, &normalized, route, trail.fallback.is_some());
+        // the data may be in the optional child of the node
+        let optional = found.is_some_and(|node| node.data.is_none());
+        let Trail {
+            values,
+            steps,
+            fallback_steps,
+            ..
+        } = trail;
+
+        match landing {
+            Landing::Route => {
+                let mut node = self.node_mut(&steps.unwrap_or_default());
+                if optional {
+                    node = node
+                        .param_children
+                        .iter_mut()
+                        .find(|n| n.optional && n.data.is_some())?;
+                }
+                Some(Match {
+                    params: route_params(&node.params, &node.info, values),
+                    data: node.data.as_mut()?,
+                    route: &node.info,
+                })
             }
+            Landing::Fallback => {
+                let node = self.node_mut(&fallback_steps);
+                Some(Match {
+                    data: node.fallback.as_mut()?,
+                    params: Params::new(),
+                    route: &node.info,
+                })
+            }
+            Landing::Miss(_) => None,
+        }
+    }
 
-            let rs = last.add_segment(segment);
-            last = rs.unwrap();
+    // the nodes reached from this one by the steps, this one first
+    fn nodes<'a>(&'a self, steps: &[Step]) -> Vec<&'a Router<T>> {
+        let mut nodes = vec![self];
+        for step in steps {
+            let node = nodes[nodes.len() - 1];
+            nodes.push(match *step {
+                Step::Static(idx) => &node.static_children[idx],
+                Step::Param(idx) => &node.param_children[idx],
+                Step::CatchAll => node.catch_all_child.as_deref().expect("catch all child"),
+            });
+        }
+        nodes
+    }
+
+    // the node reached from this one by the steps
+    fn node_mut(&mut self, steps: &[Step]) -> &mut Router<T> {
+        let mut node = self;
+        for step in steps {
+            node = match *step {
+                Step::Static(idx) => &mut node.static_children[idx],
+                Step::Param(idx) => &mut node.param_children[idx],
+                Step::CatchAll => node.catch_all_child.as_mut().expect("catch all child"),
+            };
         }
-
-        Ok(last)
+        node
     }
 
-    /// recognize a path
+    /// set the data returned by `recognize` for paths which start with the
+    /// prefix but miss every route
     ///
-    /// path must start with '/'
-    /// path should not have segments like '..', '.'
-    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<&'a T>> {
+    /// the prefix must be static, the fallback of the longest prefix wins
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/", "index").unwrap();
+    /// router.add("/api/users/:id", "user").unwrap();
+    /// router.set_fallback("/", "not found page").unwrap();
+    /// router.set_fallback("/api", "api 404").unwrap();
+    ///
+    /// assert_eq!(*router.recognize("/api/users/1").unwrap().data, "user");
+    /// assert_eq!(*router.recognize("/api/posts").unwrap().data, "api 404");
+    /// assert_eq!(*router.recognize("/about").unwrap().data, "not found page");
+    /// ```
+    pub fn set_fallback(&mut self, prefix: &str, data: T) -> Result<&mut T, Error> {
+        let node = self.fallback_node(prefix)?;
+        Ok(node.fallback.insert(data))
+    }
+
+    // the node of a static prefix holding its fallback
+    pub(crate) fn fallback_node(&mut self, prefix: &str) -> Result<&mut Router<T>, Error> {
+        self.check_base(prefix).map_err(|e| e.of(prefix))?;
+        self.sub_route(prefix)
+    }
+
+    // find the node holding the data of a path, the values of its
+    // parameters and the deepest node with a fallback on the way are kept in
+    // the trail, segments are decoded as the options of this router
+    fn find<'a, 'p>(
+        &'a self,
+        path: &'p str,
+        trail: &mut Trail<'a, 'p, T>,
+    ) -> Option<&'a Router<T>> {
         let path = {
-            if path == "" {
+            if path.is_empty() {
                 "/"
             } else {
                 path
@@ -283,176 +2044,348 @@ impl<T> Router<T> {
             return None;
         }
 
-        let mut last = self;
-        let mut is_catching_all = false;
-        let mut catch_all = String::from("");
-        let mut values = vec![];
-        let path = &path[1..];
-        for segment in path.split('/') {
-            if is_catching_all {
-                catch_all.push('/');
-                catch_all.push_str(segment);
-                continue;
-            }
-
-            if segment.len() == 0 {
-                continue;
-            }
-
-            if let Some(idx) = last.child_index(segment) {
-                last = &last.static_children[idx];
-                continue;
-            }
+        descend(&self.options, self, &[], path, 1, trail)
+    }
 
-            if let Some(ref node) = *last.param_child {
-                values.push(segment);
-                last = node;
-                continue;
+    /// recognize a path of raw bytes, which may not be valid UTF-8
+    ///
+    /// it matches the same way as `recognize`, with the same options, static
+    /// segments and constrained parameters only take segments which are
+    /// valid UTF-8; escapes may decode to invalid UTF-8, the values are
+    /// slices of the path unless they're decoded or transformed, and guards
+    /// see them decoded lossily
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/files/:name", 1).unwrap();
+    ///
+    /// let m = router.recognize_bytes(b"/files/\xffname").unwrap();
+    /// assert_eq!(*m.data, 1);
+    /// assert_eq!(m.params["name"], &b"\xffname"[..]);
+    /// assert_eq!(String::from_utf8_lossy(&m.params["name"]), "\u{fffd}name");
+    /// ```
+    pub fn recognize_bytes<'a, 'p>(&'a self, path: &'p [u8]) -> Option<BytesMatch<'a, 'p, &'a T>> {
+        match self.options.normalize_path(path).ok()? {
+            Cow::Borrowed(path) => self.recognize_normalized_bytes(path),
+            Cow::Owned(path) => {
+                let m = self.recognize_normalized_bytes(&path)?;
+                let params = m.params.into_iter();
+                Some(BytesMatch {
+                    data: m.data,
+                    params: params
+                        .map(|(k, v)| (k, Cow::Owned(v.into_owned())))
+                        .collect(),
+                    route: m.route,
+                })
             }
+        }
+    }
 
-            if let Some(ref node) = *last.catch_all_child {
-                is_catching_all = true;
-                catch_all.push_str(segment);
-                last = node;
-                continue;
+    fn recognize_normalized_bytes<'a, 'p>(
+        &'a self,
+        path: &'p [u8],
+    ) -> Option<BytesMatch<'a, 'p, &'a T>> {
+        if path.first().is_some_and(|&b| b != b'/') {
+            return None;
+        }
+        let mut trail = Trail::new(false);
+        let node =
+            descend(&self.options, self, &[], path, 1, &mut trail).and_then(Router::data_node);
+        let text = String::from_utf8_lossy(path);
+        match self.landing(&text, &text, node, trail.fallback.is_some()) {
+            Landing::Route => {
+                let node = node?;
+                Some(BytesMatch {
+                    data: node.data.as_ref()?,
+                    params: route_bytes_params(&node.params, &node.info, trail.values),
+                    route: &node.info,
+                })
             }
-
-            if segment.len() != 0 {
-                return None; // miss
+            Landing::Fallback => {
+                let (node, _) = trail.fallback?;
+                Some(BytesMatch {
+                    data: node.fallback.as_ref()?,
+                    params: BTreeMap::new(),
+                    route: &node.info,
+                })
             }
+            Landing::Miss(_) => None,
         }
+    }
 
-        if is_catching_all {
-            values.push(catch_all.as_str())
-        }
+    fn static_child(&self, segment: &str, collation: Collation) -> Option<&Router<T>> {
+        let idx = self.static_child_index(segment, collation)?;
+        Some(&self.static_children[idx])
+    }
 
-        match last.data {
-            Some(ref data) => {
-                let mut params = BTreeMap::<String, String>::new();
-                for (k, v) in last.params.iter().zip(values) {
-                    params.insert(k.clone(), String::from(v));
-                }
-                Some(Match { data, params })
-            }
-            None => None,
-        }
+    fn static_child_index(&self, segment: &str, collation: Collation) -> Option<usize> {
+        self.static_children.find(segment, collation)
     }
 
     pub fn list_routes(&self) -> Vec<String> {
-        self.list_sub_routes(&vec![])
+        self.list_sub_nodes(&[])
+            .into_iter()
+            .map(|(route, _)| route)
+            .collect()
     }
 
-    fn combine_route_parts(&self, parts: &Vec<String>, params: &Vec<String>) -> String {
-        if parts.len() == 1 && parts[0] == "" {
-            return String::from("/");
-        }
+    /// list routes like `list_routes` with their information
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{RouteInfo, Router};
+    /// let mut router = Router::default();
+    /// let mut info = RouteInfo::default();
+    /// info.annotations.insert(String::from("auth"), String::from("admin"));
+    /// router.add_with_info("/admin/:page", info, 1).unwrap();
+    /// router.add("/", 0).unwrap();
+    ///
+    /// let routes = router.list_routes_with_info();
+    /// assert_eq!(routes[1].0, "/admin/:page");
+    /// assert_eq!(routes[1].1.annotations["auth"], "admin");
+    /// ```
+    pub fn list_routes_with_info(&self) -> Vec<(String, &RouteInfo)> {
+        self.list_sub_nodes(&[])
+            .into_iter()
+            .map(|(route, node)| (route, &node.info))
+            .collect()
+    }
 
-        let mut i = 0;
-        let mut parts = parts.clone();
-        for part in parts.iter_mut() {
-            if part == ":" || part == "*" {
-                *part = format!("{}{}", part, params[i]);
-                i = i + 1;
-                continue;
-            }
-        }
-        parts.join("/")
+    /// list routes like `list_routes` with the regexes of their parameters by
+    /// name
+    ///
+    /// `list_routes` shows a parameter with a regex like any other, so the
+    /// routes it lists can be added back, the regexes are listed here
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "regex")]
+    /// # {
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add_braced("/post/{id:\\d+}/{slug}", 1).unwrap();
+    ///
+    /// let routes = router.list_routes_with_regexes();
+    /// assert_eq!(routes[0].0, "/post/:id/:slug");
+    /// assert_eq!(routes[0].1["id"], "\\d+");
+    /// assert!(!routes[0].1.contains_key("slug"));
+    /// # }
+    /// ```
+    pub fn list_routes_with_regexes(&self) -> Vec<(String, BTreeMap<String, String>)> {
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+        routes
+            .into_iter()
+            .map(|(route, _)| {
+                let regexes = route
+                    .0
+                    .iter()
+                    .filter_map(|(s, c)| match (s, c.as_ref()?.regex_source()) {
+                        (Segment::Param(name), Some(re)) => Some((name.clone(), String::from(re))),
+                        _ => None,
+                    })
+                    .collect();
+                (merge::render_route(&route), regexes)
+            })
+            .collect()
+    }
+
+    /// list routes like `list_routes` with their data
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", 1).unwrap();
+    /// router.add("/", 0).unwrap();
+    ///
+    /// assert_eq!(
+    ///     router.list_routes_with_data(),
+    ///     [(String::from("/"), &0), (String::from("/user/:id"), &1)]
+    /// );
+    /// ```
+    pub fn list_routes_with_data(&self) -> Vec<(String, &T)> {
+        self.list_sub_nodes(&[])
+            .into_iter()
+            .filter_map(|(route, node)| Some((route, node.data.as_ref()?)))
+            .collect()
     }
 
-    fn list_sub_routes(&self, pre: &Vec<String>) -> Vec<String> {
+    /// list routes like `list_routes` with mutable references to their data
+    pub fn list_routes_with_data_mut(&mut self) -> Vec<(String, &mut T)> {
         let mut result = vec![];
-        let mut cur = pre.clone();
-        match self.kind {
-            NodeKind::Static => cur.push(self.text.clone()),
-            NodeKind::Param => cur.push(String::from(":")),
-            NodeKind::CatchAll => cur.push(String::from("*")),
-        }
+        self.collect_data_mut(&mut vec![], &mut result);
+        result
+    }
 
+    fn collect_data_mut<'a>(
+        &'a mut self,
+        parts: &mut Vec<String>,
+        result: &mut Vec<(String, &'a mut T)>,
+    ) {
+        let mark = parts.len();
+        self.push_route_parts(parts);
         if self.data.is_some() {
-            result.push(self.combine_route_parts(&cur, &self.params))
+            let mut route = self.combine_route_parts(parts, &self.params);
+            if self.optional {
+                route.push('?');
+            }
+            if let Some(ref mut data) = self.data {
+                result.push((route, data));
+            }
         }
-
-        for node in self.static_children.iter() {
-            result.append(&mut node.list_sub_routes(&cur));
+        for node in self.static_children.iter_mut() {
+            node.collect_data_mut(parts, result);
         }
-
-        if let Some(ref node) = *self.param_child {
-            result.append(&mut node.list_sub_routes(&cur));
+        for node in self.param_children.iter_mut() {
+            node.collect_data_mut(parts, result);
         }
-
-        if let Some(ref node) = *self.catch_all_child {
-            result.append(&mut node.list_sub_routes(&cur));
+        if let Some(ref mut node) = self.catch_all_child {
+            node.collect_data_mut(parts, result);
         }
-
-        result
+        parts.truncate(mark);
     }
 
-    fn is_route_in_good_shape(&self, route: &str) -> bool {
-        if !route.starts_with('/') {
-            return false;
+    // the route recognizing a path as listed by `list_routes`, e.g. to report
+    // it, not for the hot path
+    #[cfg(any(feature = "hyper", feature = "replay"))]
+    pub(crate) fn matched_route(&self, path: &str) -> Option<String> {
+        let normalized = self.options.normalize_path(path).ok()?;
+        let node = self
+            .find(&normalized, &mut Trail::new(false))
+            .and_then(Router::data_node)?;
+        if !matches!(
+            self.landing(path, &normalized, Some(node), false),
+            Landing::Route
+        ) {
+            return None;
         }
+        self.list_sub_nodes(&[])
+            .into_iter()
+            .find(|(_, n)| core::ptr::eq(*n, node))
+            .map(|(route, _)| route)
+    }
 
-        if route.len() > 1 && route.ends_with('/') {
-            return false;
+    /// call `f` with every route and its data in `list_routes` order
+    ///
+    /// the trie is walked as it is, e.g. to migrate the data of the routes in
+    /// place, the data of fallbacks isn't visited
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", (1, None)).unwrap();
+    /// router.add("/files/*path", (2, None)).unwrap();
+    ///
+    /// router.visit_data_mut(|route, data| data.1 = Some(route.len()));
+    /// assert_eq!(*router.recognize("/user/7").unwrap().data, (1, Some(9)));
+    /// assert_eq!(*router.recognize("/files/a").unwrap().data, (2, Some(12)));
+    /// ```
+    pub fn visit_data_mut<F: FnMut(&str, &mut T)>(&mut self, mut f: F) {
+        for (route, data) in self.list_routes_with_data_mut() {
+            f(&route, data);
         }
-
-        return true;
     }
 
-    fn is_valid_route(&self, route: &str) -> bool {
-        if !self.is_route_in_good_shape(route) {
-            return false;
+    fn combine_route_parts(&self, parts: &[String], params: &[Arc<str>]) -> String {
+        if parts.len() == 1 && parts[0].is_empty() {
+            return String::from("/");
         }
 
-        if route.len() == 1 {
-            return true;
+        // every `:` of a part and a lone `*` take the next parameter name
+        let mut params = params.iter();
+        let mut parts = parts.to_vec();
+        for part in parts.iter_mut() {
+            if part == "*" {
+                part.push_str(params.next().map_or("", |p| p));
+            } else if part.contains(':') {
+                *part = part
+                    .split(':')
+                    .enumerate()
+                    .map(|(i, s)| match i {
+                        0 => s.to_string(),
+                        _ => format!(":{}{}", params.next().map_or("", |p| p), s),
+                    })
+                    .collect();
+            }
         }
+        parts.join("/")
+    }
 
-        let path = &route[1..];
-        let mut checker = BTreeSet::new();
-        let mut has_catch_all = false;
-        for segment in path.split('/') {
-            if segment.len() == 0 || has_catch_all {
-                return false;
+    // the parts a node adds to its routes, see `combine_route_parts`
+    fn push_route_parts(&self, parts: &mut Vec<String>) {
+        match self.kind {
+            NodeKind::Static => {
+                parts.push(self.text.clone());
+                parts.extend(self.tail.iter().cloned());
             }
-            if segment.starts_with(':') || segment.starts_with('*') {
-                if segment.len() == 1 {
-                    return false;
-                }
-                let name = &segment[1..];
-                if checker.contains(name) {
-                    return false;
-                }
-                checker.insert(&segment[1..]);
+            NodeKind::Param => {
+                let part = match self.constraint {
+                    Some(Constraint::Type(ty)) => format!(":<{}>", ty.name()),
+                    Some(Constraint::Custom(ref m)) => format!(":<{}>", m.name()),
+                    Some(Constraint::Template(ref t)) => t.render(|_| String::from(":")),
+                    _ => String::from(":"),
+                };
+                parts.push(part)
             }
+            NodeKind::CatchAll => parts.push(String::from("*")),
+        }
+    }
 
-            if segment.starts_with('*') {
-                has_catch_all = true
+    // routes with their data nodes
+    fn list_sub_nodes(&self, pre: &[String]) -> Vec<(String, &Router<T>)> {
+        let mut result = vec![];
+        let mut cur = pre.to_vec();
+        self.push_route_parts(&mut cur);
+
+        if self.data.is_some() {
+            let mut route = self.combine_route_parts(&cur, &self.params);
+            if self.optional {
+                route.push('?');
             }
+            result.push((route, self))
         }
 
-        return true;
-    }
+        for node in self.static_children.iter() {
+            result.append(&mut node.list_sub_nodes(&cur));
+        }
+
+        for node in self.param_children.iter() {
+            result.append(&mut node.list_sub_nodes(&cur));
+        }
 
-    fn is_valid_base(&self, route: &str) -> bool {
-        if !self.is_route_in_good_shape(route) {
-            return false;
+        if let Some(ref node) = self.catch_all_child {
+            result.append(&mut node.list_sub_nodes(&cur));
         }
 
+        result
+    }
+
+    fn check_base(&self, route: &str) -> Result<(), Error> {
+        check_route_shape(route)?;
+
         if route.len() == 1 {
-            return true;
+            return Ok(());
         }
 
         let path = &route[1..];
-        for segment in path.split('/') {
-            if segment.len() == 0 {
-                return false;
+        for (i, segment) in path.split('/').enumerate() {
+            if segment.is_empty() {
+                return Err(Error::new(Reason::EmptySegment).at(i));
             }
             if segment.starts_with(':') || segment.starts_with('*') {
-                return false;
+                return Err(Error::new(Reason::NotStatic).at(i));
             }
         }
-        true
+        Ok(())
     }
 }
 
@@ -461,7 +2394,7 @@ mod tests {
     use super::*;
 
     fn build_simple_router(router: &mut Router<usize>) {
-        const ROUTES: [&'static str; 10] = [
+        const ROUTES: [&str; 10] = [
             "/",
             "/users",
             "/users/:id",
@@ -551,7 +2484,7 @@ mod tests {
                 let m = router.recognize(&path_string).unwrap();
                 assert_eq!(m.data, val);
                 for (k, v) in param {
-                    match m.params.get(*k) {
+                    match m.params.get(k) {
                         Some(ref rv) => assert_eq!(v, rv),
                         None => panic!("{} not found", k),
                     }
@@ -584,20 +2517,21 @@ mod tests {
             ("/different_param_name/:b/:c", true, vec!["b", "c"]),
             ("/different_param_name/:a/:d", false, vec![]),
             ("/different_param_name/:a/:d/*e", true, vec!["a", "d", "e"]),
-            ("/catch_all_not_the_last/*a/extra", false, vec![]),
+            ("/catch_all_in_the_middle/*a/extra", true, vec!["a"]),
+            ("/two_catch_alls/*a/b/*c", false, vec![]),
         ];
 
         let mut router = Router::default();
 
         for (route, valid, keys) in checks.iter() {
-            let rs = router.add(*route, 1);
+            let rs = router.add(route, 1);
             if *valid {
                 assert_eq!(*rs.unwrap(), 1);
-                match router.recognize(*route) {
+                match router.recognize(route) {
                     None => panic!("failed to recognize {}", *route),
-                    Some(Match { data: _, params }) => {
+                    Some(Match { params, .. }) => {
                         for k in keys.iter() {
-                            assert!(params.get(*k).is_some(), "miss capturing param: {}", *k)
+                            assert!(params.contains_key(k), "miss capturing param: {}", *k)
                         }
                     }
                 }
@@ -607,6 +2541,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejection_reasons() {
+        let mut router = Router::default();
+        router.add("/a/:id", 1).unwrap();
+        let dup = |name: &str| Reason::DuplicateName(String::from(name));
+        let existing = String::from("/a/:id");
+        for (route, segment, reason) in [
+            ("a/b", None, Reason::NoLeadingSlash),
+            ("/a/b/", None, Reason::TrailingSlash),
+            ("/a//b", Some(1), Reason::EmptySegment),
+            ("/b/:", Some(1), Reason::EmptyName),
+            ("/b/:<u32>", Some(1), Reason::EmptyName),
+            ("/b/:x/:x", Some(2), dup("x")),
+            ("/b/:x/:y.:x", Some(2), dup("x")),
+            ("/b/*x/c/*y", Some(3), Reason::SecondCatchAll),
+            ("/b/:x?/c", Some(1), Reason::MisplacedOptional),
+            (
+                "/b/:x<oct>",
+                Some(1),
+                Reason::UnknownType(String::from("oct")),
+            ),
+            ("/b/:x:y", Some(1), Reason::InvalidTemplate),
+            ("/a/*rest", Some(1), Reason::Conflict { existing }),
+        ] {
+            let err = router.add(route, 2).unwrap_err();
+            assert_eq!(
+                (&err.route[..], err.segment, err.reason),
+                (route, segment, reason)
+            );
+        }
+        assert_eq!(
+            router.set_fallback("/a/:x", 3).unwrap_err(),
+            Error::new(Reason::NotStatic).at(1).of("/a/:x")
+        );
+        assert_eq!(
+            router.sub_route("/b/:x?").unwrap_err().reason,
+            Reason::MisplacedOptional
+        );
+        let mut constrained = Router::default();
+        let err = constrained.add_with_constraint("/a", Constraint::Type(ParamType::U8), 1);
+        assert_eq!(err.unwrap_err().reason, Reason::MisplacedConstraint);
+
+        let mut keys = Router::<_, u8>::default();
+        let err = keys.add_keys(vec![Segment::CatchAll("a".into()), Segment::Static(1)], 1);
+        assert_eq!(err.unwrap_err(), Error::new(Reason::CatchAllNotLast).at(1));
+        assert_eq!(
+            Error::new(Reason::EmptySegment)
+                .at(2)
+                .of("/a/b//c")
+                .to_string(),
+            "invalid route /a/b//c: empty segment at segment 2"
+        );
+    }
+
     #[test]
     fn base_route() {
         let mut router = Router::default();
@@ -628,6 +2616,950 @@ mod tests {
     }
 
     #[test]
+    fn param_base_route() {
+        let mut router = Router::default();
+        router.add("/tenants/:tenant/about", 0).unwrap();
+        {
+            let tenant = router.sub_route("/tenants/:tenant").unwrap();
+            tenant.add("/users/:id", 1).unwrap();
+            tenant.add("/", 2).unwrap();
+            let repo = tenant.sub_route("/repos/:name.:ext/*path").unwrap();
+            repo.add("/blob/:ref", 3).unwrap();
+        }
+        assert!(router.add("/tenants/:t/users/:id", 4).is_err());
+        assert!(router.sub_route("/tenants/:tenant/x/:id?").is_err());
+        assert!(router.sub_route("/tenants/*rest").is_err());
+        assert!(router.set_fallback("/tenants/:tenant", 5).is_err());
+
+        let m = router.recognize("/tenants/acme/users/7").unwrap();
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("tenant", "acme"), ("id", "7")]
+        );
+        assert_eq!(
+            router.recognize("/tenants/acme").unwrap().params["tenant"],
+            "acme"
+        );
+        let m = router
+            .recognize("/tenants/acme/repos/app.rs/src/x/blob/main")
+            .unwrap();
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [
+                ("tenant", "acme"),
+                ("name", "app"),
+                ("ext", "rs"),
+                ("path", "src/x"),
+                ("ref", "main")
+            ]
+        );
+        assert_eq!(
+            router.list_routes(),
+            [
+                "/tenants/:tenant",
+                "/tenants/:tenant/about",
+                "/tenants/:tenant/repos/:name.:ext/*path/blob/:ref",
+                "/tenants/:tenant/users/:id",
+            ]
+        );
+    }
+
+    #[test]
+    fn key_router() {
+        let mut router = Router::<usize, Vec<u8>>::default();
+        let ftp = b"ftp".to_vec();
+        router
+            .add_keys(vec![Segment::Static(ftp.clone())], 1)
+            .unwrap();
+        router
+            .add_keys(
+                vec![Segment::Static(ftp.clone()), Segment::Param("file".into())],
+                2,
+            )
+            .unwrap();
+        router
+            .add_keys(
+                vec![
+                    Segment::Static(b"\xff".to_vec()),
+                    Segment::CatchAll("rest".into()),
+                ],
+                3,
+            )
+            .unwrap();
+        assert!(router
+            .add_keys(
+                vec![Segment::CatchAll("a".into()), Segment::Param("b".into())],
+                4
+            )
+            .is_err());
+
+        assert_eq!(
+            *router
+                .recognize_keys(core::slice::from_ref(&ftp))
+                .unwrap()
+                .data,
+            1
+        );
+        let path = [ftp.clone(), vec![0xc3, 0x28]];
+        let m = router.recognize_keys(&path).unwrap();
+        assert_eq!(*m.data, 2);
+        assert_eq!(m.params["file"], &[vec![0xc3, 0x28]]);
+        let path = [vec![0xff], vec![1], vec![2]];
+        let m = router.recognize_keys(&path).unwrap();
+        assert_eq!(*m.data, 3);
+        assert_eq!(m.params["rest"], &[vec![1], vec![2]]);
+        assert!(router.recognize_keys(&[vec![0xfe]]).is_none());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn constrained_params() {
+        let mut router = Router::default();
+        let digits = Constraint::regex("[0-9]+").unwrap();
+        let lower = Constraint::regex("[a-z]+").unwrap();
+        router
+            .add_with_constraint("/user/:id", digits.clone(), 1)
+            .unwrap();
+        router
+            .add_with_constraint("/user/:id/repos", digits, 2)
+            .unwrap();
+        router.add_with_constraint("/user/:name", lower, 3).unwrap();
+        router.add("/user/:any", 4).unwrap();
+        assert!(router
+            .add_with_constraint("/user", Constraint::regex("x").unwrap(), 5)
+            .is_err());
+        assert!(Constraint::regex("(").is_err());
+
+        assert_eq!(*router.recognize("/user/42").unwrap().data, 1);
+        assert_eq!(*router.recognize("/user/42/repos").unwrap().data, 2);
+        assert_eq!(*router.recognize("/user/abc").unwrap().data, 3);
+        assert_eq!(*router.recognize("/user/abc42").unwrap().data, 4);
+        assert_eq!(router.recognize("/user/abc").unwrap().params["name"], "abc");
+        assert!(router.recognize("/user/abc/repos").is_none());
+
+        router.add_braced("/v/{ver:(?:v|V):?[0-9]}", 6).unwrap();
+        let routes = router.list_routes_with_regexes();
+        let regex = |i: usize, name| routes[i].1.get(name).map(String::as_str);
+        assert_eq!(regex(0, "id"), Some("[0-9]+"));
+        assert_eq!(regex(2, "name"), Some("[a-z]+"));
+        assert_eq!(regex(3, "any"), None);
+        assert_eq!(routes[4], (String::from("/v/:ver"), routes[4].1.clone()));
+        assert_eq!(regex(4, "ver"), Some("(?:v|V):?[0-9]"));
+        router.add("/w/:name.:ext/*path", 7).unwrap();
+        router.add("/w/x/y/:tab?", 8).unwrap();
+        let routes = router.list_routes_with_regexes();
+        let listed = routes.into_iter().map(|(route, _)| route);
+        assert_eq!(listed.collect::<Vec<_>>(), router.list_routes());
+        for route in router.list_routes() {
+            assert!(Router::default().add(&route, 0).is_ok(), "{}", route);
+        }
+    }
+
+    #[test]
+    fn custom_matchers() {
+        struct Hex(&'static str);
+
+        impl SegmentMatcher for Hex {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn matches(&self, segment: &str) -> bool {
+                segment.bytes().all(|b| b.is_ascii_hexdigit())
+            }
+        }
+
+        let mut router = Router::default();
+        router
+            .options_mut()
+            .segment_matchers
+            .push(Arc::new(Hex("hex")));
+        router.add("/obj/:n<u8>", 1).unwrap();
+        router.add("/obj/:sha<hex>/raw", 2).unwrap();
+        router
+            .add_with_constraint("/obj/:sha", Constraint::custom(Hex("hex")), 3)
+            .unwrap();
+        router.add("/obj/:name", 4).unwrap();
+        assert!(router.add("/obj/:x<hex>", 5).is_err());
+        assert_eq!(
+            router.add("/obj/:x<oct>", 5).unwrap_err().reason,
+            Reason::UnknownType(String::from("oct"))
+        );
+
+        assert_eq!(*router.recognize("/obj/12").unwrap().data, 1);
+        assert_eq!(*router.recognize("/obj/fff").unwrap().data, 3);
+        assert_eq!(*router.recognize("/obj/fff/raw").unwrap().data, 2);
+        assert_eq!(*router.recognize("/obj/xyz").unwrap().data, 4);
+        assert_eq!(
+            router.list_routes(),
+            [
+                "/obj/:n<u8>",
+                "/obj/:sha<hex>",
+                "/obj/:sha<hex>/raw",
+                "/obj/:name"
+            ]
+        );
+        assert_eq!(
+            format!("{:?}", Constraint::custom(Hex("hex"))),
+            "Custom(\"hex\")"
+        );
+    }
+
+    #[test]
+    fn bytes_router() {
+        let mut router = Router::default();
+        build_simple_router(&mut router);
+        assert_eq!(*router.recognize_bytes(b"").unwrap().data, 0);
+        assert_eq!(*router.recognize_bytes(b"/about/").unwrap().data, 7);
+        let m = router.recognize_bytes(b"/users/\xc3\x28/repos").unwrap();
+        assert_eq!(*m.data, 4);
+        assert_eq!(m.params["user_id"], &b"\xc3\x28"[..]);
+        let m = router
+            .recognize_bytes(b"/users/42/repos/12/x/\xff//z/")
+            .unwrap();
+        assert_eq!(*m.data, 6);
+        assert_eq!(m.params["any"], &b"x/\xff//z/"[..]);
+        assert!(matches!(m.params["any"], Cow::Borrowed(_)));
+        assert!(router.recognize_bytes(b"users").is_none());
+        assert!(router.recognize_bytes(b"/about/\xff").is_none());
+
+        // catch alls in the middle of routes and the options of `recognize`
+        router.add("/repo/*path/meta", 10).unwrap();
+        let m = router.recognize_bytes(b"/repo/a/\xff/meta").unwrap();
+        assert_eq!((*m.data, &m.params["path"][..]), (10, &b"a/\xff"[..]));
+        router.options_mut().dot_segments = DotSegments::Reject;
+        router.options_mut().encoded_slash = EncodedSlash::Reject;
+        for path in [
+            "/users/../about",
+            "/users/x%2Fy/repos",
+            "/users/%2E%2E/repos",
+        ] {
+            assert!(router.recognize(path).is_none());
+            assert!(
+                router.recognize_bytes(path.as_bytes()).is_none(),
+                "{}",
+                path
+            );
+        }
+        router.options_mut().dot_segments = DotSegments::Resolve;
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        assert_eq!(
+            *router
+                .recognize_bytes(b"/users/\xff/../../about")
+                .unwrap()
+                .data,
+            7
+        );
+        let m = router.recognize_bytes(b"/users/%ff%41/repos").unwrap();
+        assert_eq!(m.params["user_id"], &b"\xffA"[..]);
+        assert!(router.recognize_bytes(b"/users/%zz/repos").is_none());
+    }
+
+    #[test]
+    fn template_segments() {
+        let mut router = Router::default();
+        router.add("/download/:name.:ext", 1).unwrap();
+        router.add("/download/:file", 2).unwrap();
+        router.add("/api/v:major.:minor/users", 3).unwrap();
+        router.add("/api/v:major/users", 4).unwrap();
+        assert!(router.add("/download/:base.:suffix", 5).is_err());
+        assert!(router.add("/a/:x:y", 5).is_err());
+        assert!(router.add("/a/:x.:x", 5).is_err());
+
+        let m = router.recognize("/download/report.pdf").unwrap();
+        assert_eq!(*m.data, 1);
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("name", "report"), ("ext", "pdf")]
+        );
+        let m = router.recognize("/download/archive.tar.gz").unwrap();
+        assert_eq!(
+            (&m.params["name"][..], &m.params["ext"][..]),
+            ("archive.tar", "gz")
+        );
+        assert_eq!(*router.recognize("/download/README").unwrap().data, 2);
+        assert_eq!(*router.recognize("/download/.bashrc").unwrap().data, 2);
+        let m = router.recognize("/api/v2.1/users").unwrap();
+        assert_eq!((*m.data, &m.params["minor"][..]), (3, "1"));
+        assert_eq!(*router.recognize_bytes(b"/api/v2/users").unwrap().data, 4);
+        assert_eq!(
+            router.list_routes(),
+            [
+                "/api/v:major.:minor/users",
+                "/api/v:major/users",
+                "/download/:name.:ext",
+                "/download/:file"
+            ]
+        );
+        assert!(router
+            .to_openapi_paths()
+            .contains(r#""/download/{name}.{ext}":{"parameters":[{"name":"name","#));
+
+        let mut merged = Router::default();
+        merged.merge(router).unwrap();
+        let m = merged.recognize("/api/v1.0/users").unwrap();
+        assert_eq!((&m.params["major"][..], &m.params["minor"][..]), ("1", "0"));
+    }
+
+    #[test]
+    fn typed_params() {
+        let mut router = Router::default();
+        router.add("/user/:id<u32>", 1).unwrap();
+        router.add("/user/:id<u32>/repos", 2).unwrap();
+        router.add("/user/:name", 3).unwrap();
+        router.add("/archive/:date<yyyy-mm-dd>", 4).unwrap();
+        assert!(router.add("/bad/:id<u31>", 5).is_err());
+        assert!(router.add("/bad/:<u32>", 5).is_err());
+        assert!(router.add("/bad/:id<u32>/:id<u8>", 5).is_err());
+
+        assert_eq!(*router.recognize("/user/42").unwrap().data, 1);
+        assert_eq!(router.recognize("/user/42").unwrap().params["id"], "42");
+        assert_eq!(*router.recognize("/user/42/repos").unwrap().data, 2);
+        assert_eq!(*router.recognize("/user/-42").unwrap().data, 3);
+        assert_eq!(*router.recognize("/user/4294967296").unwrap().data, 3);
+        assert_eq!(*router.recognize("/archive/2020-02-29").unwrap().data, 4);
+        assert!(router.recognize("/archive/2019-02-29").is_none());
+        assert!(router.recognize("/archive/2019-1-01").is_none());
+
+        let routes = router.list_routes();
+        assert!(routes.contains(&String::from("/user/:id<u32>/repos")));
+        assert!(routes.contains(&String::from("/archive/:date<yyyy-mm-dd>")));
+    }
+
+    #[test]
+    fn optional_trailing_param() {
+        let mut router = Router::default();
+        router.add("/articles/:id/:slug?", 1).unwrap();
+        router.add("/articles/:id/:slug/comments", 2).unwrap();
+        router.add("/tags/:tag<u32>?", 3).unwrap();
+        assert!(router.add("/articles/:id", 4).is_err());
+        assert!(router.add("/articles/:id/:title?", 4).is_err());
+        assert!(router.add("/articles/:id/:slug", 4).is_err());
+        assert!(router.add("/articles/:id?/:slug", 4).is_err());
+
+        let m = router.recognize("/articles/5").unwrap();
+        assert_eq!(*m.data, 1);
+        assert_eq!(m.params.get("id").unwrap(), "5");
+        assert!(!m.params.contains_key("slug"));
+        let m = router.recognize("/articles/5/hello-world/").unwrap();
+        assert_eq!(*m.data, 1);
+        assert_eq!(m.params.get("slug").unwrap(), "hello-world");
+        assert_eq!(*router.recognize("/articles/5/x/comments").unwrap().data, 2);
+        assert_eq!(*router.recognize("/tags").unwrap().data, 3);
+        assert_eq!(*router.recognize("/tags/7").unwrap().data, 3);
+        assert!(router.recognize("/tags/x").is_none());
+        assert!(router.recognize("/articles").is_none());
+
+        assert_eq!(
+            router.list_routes(),
+            vec![
+                "/articles/:id/:slug?",
+                "/articles/:id/:slug/comments",
+                "/tags/:tag<u32>?"
+            ]
+        );
+    }
+
+    #[test]
+    fn fullwidth_paths() {
+        let mut router = Router::default();
+        router.options_mut().fullwidth = true;
+        router.add("/files/:name", 1).unwrap();
+        router.add("／ｆｕｌｌ", 2).unwrap();
+        assert_eq!(
+            router.recognize("／ｆｉｌｅｓ／ａ．txt").unwrap().params["name"],
+            "a.txt"
+        );
+        assert_eq!(*router.recognize("/full").unwrap().data, 2);
+        assert_eq!(router.list_routes(), vec!["/files/:name", "/full"]);
+
+        let mut router = Router::default();
+        router.add("/files/:name", 1).unwrap();
+        assert!(router.recognize("／ｆｉｌｅｓ／ａ．txt").is_none());
+    }
+
+    #[test]
+    fn percent_decoded_paths() {
+        let mut router = Router::default();
+        router.options_mut().percent_decode = PercentDecode::PassThrough;
+        router.add("/user/:name", 1).unwrap();
+        router.add("/caf%C3%A9/*rest", 2).unwrap();
+        router.add("/100%", 3).unwrap();
+        assert_eq!(
+            router.recognize("/user/john%20doe").unwrap().params["name"],
+            "john doe"
+        );
+        assert_eq!(
+            router.recognize("/user/a%2Fb").unwrap().params["name"],
+            "a/b"
+        );
+        assert_eq!(router.recognize("/user/%zz").unwrap().params["name"], "%zz");
+        let m = router.recognize("/café/x%20y/z").unwrap();
+        assert_eq!((*m.data, m.params["rest"].as_str()), (2, "x y/z"));
+        assert_eq!(*router.recognize("/100%").unwrap().data, 3);
+
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        assert!(router.recognize("/user/%zz").is_none());
+        assert!(router.recognize("/user/%ff").is_none());
+        assert!(router.recognize("/100%").is_none());
+        assert_eq!(
+            router.add("/50%", 4),
+            Err(Error::new(Reason::InvalidEscape).at(0).of("/50%"))
+        );
+
+        let mut router = Router::default();
+        router.add("/user/:name", 1).unwrap();
+        assert_eq!(
+            router.recognize("/user/john%20doe").unwrap().params["name"],
+            "john%20doe"
+        );
+    }
+
+    #[test]
+    fn segment_transforms() {
+        let mut router = Router::default();
+        router.options_mut().percent_decode = PercentDecode::PassThrough;
+        router.options_mut().segment_transforms = vec![
+            Transform::Lowercase,
+            Transform::map("no dashes", |s| s.replace('-', "")),
+        ];
+        router.add("/Blog-Posts/:slug", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+        assert!(router.add("/blogposts/:id", 3).is_err());
+
+        let m = router.recognize("/BLOGPOSTS/Hello-World").unwrap();
+        assert_eq!((*m.data, &m.params["slug"][..]), (1, "helloworld"));
+        assert_eq!(
+            router.recognize("/blog%2Dposts/x").unwrap().params["slug"],
+            "x"
+        );
+        assert_eq!(
+            router.recognize("/FILES/A/B-C").unwrap().params["path"],
+            "a/bc"
+        );
+        assert_eq!(router.list_routes(), ["/blogposts/:slug", "/files/*path"]);
+    }
+
+    #[test]
+    fn dot_segments() {
+        let mut router = Router::default();
+        router.add("/a/:b", 1).unwrap();
+        router.add("/c/*rest", 2).unwrap();
+        router.add("/..", 3).unwrap();
+        assert_eq!(*router.recognize("/..").unwrap().data, 3);
+        assert_eq!(router.recognize("/a/..").unwrap().params["b"], "..");
+        assert_eq!(router.try_recognize("/a/x/y").unwrap_err(), Miss::NotFound);
+
+        router.options_mut().dot_segments = DotSegments::Resolve;
+        assert_eq!(router.recognize("/c/../a/./x").unwrap().params["b"], "x");
+        assert_eq!(
+            router.recognize("/../../c/x/../y/.").unwrap().params["rest"],
+            "y/"
+        );
+        assert!(router.recognize("/..").is_none());
+        assert!(router.recognize_tail("/c/./x").is_none());
+        assert_eq!(router.recognize_tail("/c/.x").unwrap().tail, Some(3..5));
+
+        router.options_mut().dot_segments = DotSegments::Reject;
+        assert_eq!(router.try_recognize("/a/.").unwrap_err(), Miss::DotSegments);
+        assert!(router.recognize_mut("/c/x/..").is_none());
+        assert_eq!(*router.recognize("/a/..x").unwrap().data, 1);
+
+        // encoded dots are dots, whether segments are decoded or not
+        router.add("/x/:y/z", 4).unwrap();
+        for decode in [PercentDecode::Off, PercentDecode::Strict] {
+            router.options_mut().percent_decode = decode;
+            router.options_mut().dot_segments = DotSegments::Reject;
+            for path in ["/x/%2e%2e/z", "/c/a/%2E%2E/etc", "/a/.%2e", "/a/%2E"] {
+                assert_eq!(router.try_recognize(path).unwrap_err(), Miss::DotSegments);
+            }
+            assert_eq!(*router.recognize("/a/%2e%2ex").unwrap().data, 1);
+
+            router.options_mut().dot_segments = DotSegments::Resolve;
+            assert!(router.recognize("/x/%2e%2e/z").is_none());
+            let m = router.recognize("/c/a/%2E%2E/etc").unwrap();
+            assert_eq!((*m.data, &m.params["rest"][..]), (2, "etc"));
+            assert_eq!(router.recognize("/x/%2e/y/z").unwrap().params["y"], "y");
+        }
+    }
+
+    #[test]
+    fn parameter_names() {
+        let mut router = Router::default();
+        router.add("/users/:user_id2/*rest_0", 1).unwrap();
+        let invalid = |route, segment, name: &str| {
+            let reason = Reason::InvalidName(String::from(name));
+            Err(Error::new(reason).at(segment).of(route))
+        };
+        for (route, segment, name) in [
+            ("/a/:id-weird!chars", 1, "id-weird!chars"),
+            ("/a/b/*ré", 2, "ré"),
+            ("/a/:x$<u32>", 1, "x$"),
+        ] {
+            assert_eq!(router.add(route, 2), invalid(route, segment, name));
+        }
+        assert_eq!(
+            router.add("/a/:id-x?", 2).unwrap_err().to_string(),
+            "invalid route /a/:id-x?: invalid parameter name \"id-x\" at segment 1"
+        );
+
+        router.options_mut().name_chars = NameChars::any();
+        router.add("/a/:id-weird!chars", 2).unwrap();
+        let m = router.recognize("/a/7").unwrap();
+        assert_eq!(m.params["id-weird!chars"], "7");
+    }
+
+    #[test]
+    fn encoded_slashes() {
+        let mut router = Router::default();
+        router.add("/repos/:name", 1).unwrap();
+        router.add("/repos/:owner/:name", 2).unwrap();
+        router.add("/files/*path", 3).unwrap();
+
+        let m = router.recognize("/repos/a%2Fb").unwrap();
+        assert_eq!((*m.data, &m.params["name"][..]), (1, "a%2Fb"));
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        assert_eq!(
+            router.recognize("/repos/a%2Fb").unwrap().params["name"],
+            "a/b"
+        );
+
+        router.options_mut().encoded_slash = EncodedSlash::Separator;
+        let m = router.recognize("/repos/a%2fb").unwrap();
+        assert_eq!((*m.data, &m.params["owner"][..]), (2, "a"));
+        assert_eq!(
+            router.recognize("/files/a%2F..%2Fb").unwrap().params["path"],
+            "a/../b"
+        );
+        router.options_mut().dot_segments = DotSegments::Reject;
+        assert!(router.recognize("/files/a%2F..%2Fb").is_none());
+
+        router.options_mut().encoded_slash = EncodedSlash::Reject;
+        assert_eq!(
+            router.try_recognize("/repos/a%2Fb").unwrap_err(),
+            Miss::EncodedSlash
+        );
+        assert_eq!(*router.recognize("/repos/a%20b").unwrap().data, 1);
+    }
+
+    #[test]
+    fn route_info() {
+        let mut router = Router::default();
+        let info = RouteInfo {
+            request_schema: Some(String::from("NewRepo")),
+            response_schema: Some(String::from("Repo")),
+            summary: None,
+            tags: vec![String::from("repos")],
+            annotations: BTreeMap::new(),
+            compression: Some(Compression {
+                encoding: Encoding::Brotli,
+                min_size: 1024,
+            }),
+            transforms: vec![],
+            catch_all_slash: None,
+            id_template: None,
+            max_body_bytes: None,
+            breaker: None,
+            guard: None,
+        };
+        router
+            .add_with_info("/users/:id/repos", info.clone(), 1)
+            .unwrap();
+        router.add("/users/:id", 2).unwrap();
+
+        let m = router.recognize("/users/1/repos").unwrap();
+        assert_eq!(*m.route, info);
+        assert_eq!(m.route.compression.unwrap().encoding.as_str(), "br");
+        let m = router.recognize("/users/1").unwrap();
+        assert_eq!(*m.route, RouteInfo::default());
+    }
+
+    #[test]
+    fn compressed_static_chains() {
+        let mut router = Router::default();
+        router.add("/api/v1/admin/settings/email", 1).unwrap();
+        assert_eq!(router.static_children.len(), 1);
+        assert_eq!(router.static_children[0].text, "api");
+        assert_eq!(
+            router.static_children[0].tail,
+            vec!["v1", "admin", "settings", "email"]
+        );
+        assert!(router.recognize("/api/v1/admin").is_none());
+        assert!(router.recognize("/api/v1/admin/settings/email/x").is_none());
+
+        router.add("/api/v1/admin", 2).unwrap();
+        router.add("/api/v1/admin/users/:id", 3).unwrap();
+        router.add("/api/v2/status", 4).unwrap();
+        let api = &router.static_children[0];
+        assert!(api.tail.is_empty());
+        assert_eq!(api.static_children[0].tail, vec!["admin"]);
+        assert_eq!(api.static_children[1].tail, vec!["status"]);
+
+        assert_eq!(
+            *router
+                .recognize("/api/v1/admin/settings/email")
+                .unwrap()
+                .data,
+            1
+        );
+        assert_eq!(*router.recognize("/api/v1/admin").unwrap().data, 2);
+        assert_eq!(
+            router.recognize("/api/v1/admin/users/7").unwrap().params["id"],
+            "7"
+        );
+        assert_eq!(*router.recognize("/api/v2/status").unwrap().data, 4);
+        assert_eq!(*router.recognize_bytes(b"/api/v2/status").unwrap().data, 4);
+        assert!(router.recognize("/api/v2").is_none());
+        assert!(router.recognize("/api/v1/settings/email").is_none());
+        assert!(router.add("/api/v1/admin/settings/email", 5).is_ok());
+        assert_eq!(
+            router.list_routes(),
+            vec![
+                "/api/v1/admin",
+                "/api/v1/admin/settings/email",
+                "/api/v1/admin/users/:id",
+                "/api/v2/status",
+            ]
+        );
+
+        let sub = router.sub_route("/api/v2").unwrap();
+        sub.add("/health", 6).unwrap();
+        assert_eq!(*router.recognize("/api/v2/health").unwrap().data, 6);
+        assert_eq!(*router.recognize("/api/v2/status").unwrap().data, 4);
+    }
+
+    #[test]
+    fn collated_statics() {
+        let mut router = Router::default();
+        router.options_mut().collation = Collation::ascii_case_insensitive();
+        router.add("/Api/V1/Users", 1).unwrap();
+        router.add("/api/v1/users/:id", 2).unwrap();
+        router.add("/b", 3).unwrap();
+        router.add("/A", 4).unwrap();
+        assert_eq!(
+            router.list_routes(),
+            vec!["/A", "/Api/V1/Users", "/Api/V1/Users/:id", "/b"]
+        );
+        assert_eq!(*router.recognize("/api/v1/users").unwrap().data, 1);
+        assert_eq!(*router.recognize("/API/v1/USERS/7").unwrap().data, 2);
+        assert_eq!(*router.recognize_bytes(b"/a").unwrap().data, 4);
+        assert!(router.recognize_bytes(b"/api/\xff/users").is_none());
+
+        let sub = router.sub_route("/API/v2").unwrap();
+        sub.add("/Status", 5).unwrap();
+        assert_eq!(*router.recognize("/api/V2/status").unwrap().data, 5);
+        // sub routes share the options, a change is their own
+        let options = Arc::clone(&router.options);
+        let sub = router.sub_route("/api/v3").unwrap();
+        assert!(Arc::ptr_eq(&sub.options, &options));
+        sub.options_mut().strict_slash = true;
+        assert!(!Arc::ptr_eq(&sub.options, &options));
+        assert!(!router.options().strict_slash);
+
+        let mut router = Router::with_options(Options {
+            collation: Collation::new("reverse", |a, b| b.cmp(a)),
+            ..Options::default()
+        });
+        for (i, route) in ["/a", "/c", "/b"].iter().enumerate() {
+            router.add(route, i).unwrap();
+        }
+        assert_eq!(router.list_routes(), vec!["/c", "/b", "/a"]);
+        assert_eq!(*router.recognize("/b").unwrap().data, 2);
+        assert!(router.recognize("/B").is_none());
+    }
+
+    #[test]
+    fn visit_data() {
+        let mut router = Router::default();
+        let routes = [
+            "/",
+            "/a/b/c",
+            "/user/:id<u32>",
+            "/user/:name/:tab?",
+            "/download/:name.:ext",
+            "/repo/*path/manifest.json",
+        ];
+        for route in routes.iter() {
+            router.add(route, String::new()).unwrap();
+        }
+        router
+            .set_fallback("/user", String::from("fallback"))
+            .unwrap();
+        let mut visited = vec![];
+        router.visit_data_mut(|route, data| {
+            data.push_str(route);
+            visited.push(String::from(route));
+        });
+        assert_eq!(visited, router.list_routes());
+        for route in routes.iter() {
+            let path = route.replace(":id<u32>", "7").replace("?", "");
+            assert_eq!(router.recognize(&path).unwrap().data, route);
+        }
+        assert_eq!(router.recognize("/user/a/b/c").unwrap().data, "fallback");
+        for (route, data) in router.list_routes_with_data() {
+            assert_eq!(route, *data);
+        }
+        for (_, data) in router.list_routes_with_data_mut() {
+            data.clear();
+        }
+        assert_eq!(router.recognize("/a/b/c").unwrap().data, "");
+        assert_eq!(router.list_routes_with_data().len(), routes.len());
+    }
+
+    #[test]
+    fn scoped_fallbacks() {
+        let mut router = Router::default();
+        router.add("/api/v1/users/:id", 1).unwrap();
+        router.add("/api/v1/files/*path", 2).unwrap();
+        router.add("/site/about", 3).unwrap();
+        assert!(router.recognize("/api/v2").is_none());
+
+        assert_eq!(*router.set_fallback("/api", 10).unwrap(), 10);
+        router.set_fallback("/api/v1/users", 11).unwrap();
+        router.set_fallback("/", 12).unwrap();
+        assert!(router.set_fallback("/api/:x", 13).is_err());
+
+        assert_eq!(*router.recognize("/api/v1/users/7").unwrap().data, 1);
+        assert_eq!(*router.recognize("/api/v1/files/a/b").unwrap().data, 2);
+        assert_eq!(*router.recognize("/api/v1/users/7/x").unwrap().data, 11);
+        assert_eq!(*router.recognize("/api/v1/users").unwrap().data, 11);
+        let m = router.recognize("/api/v1").unwrap();
+        assert_eq!((*m.data, m.params.len()), (10, 0));
+        assert_eq!(*router.recognize("/api/v2/x").unwrap().data, 10);
+        assert_eq!(*router.recognize("/site").unwrap().data, 12);
+        assert_eq!(*router.recognize("/").unwrap().data, 12);
+        assert_eq!(router.list_routes().len(), 3);
+
+        // a fallback in the middle of a merged chain splits it
+        let mut router = Router::default();
+        router.add("/a/b/c/d", 1).unwrap();
+        router.set_fallback("/a/b", 2).unwrap();
+        router.add("/a/b/c/d/e", 3).unwrap();
+        assert_eq!(*router.recognize("/a/b/c").unwrap().data, 2);
+        assert_eq!(*router.recognize("/a/b/c/d").unwrap().data, 1);
+        assert!(router.recognize("/a").is_none());
+    }
+
+    #[test]
+    fn backtracking() {
+        let mut router = Router::default();
+        router.add("/users/admin/settings", 1).unwrap();
+        router.add("/users/:id/profile", 2).unwrap();
+        router.add("/users/:id<u32>/posts", 3).unwrap();
+        router.add("/users/:name/posts/:post", 4).unwrap();
+        router.add("/files/static/a", 5).unwrap();
+        router.add("/files/*path", 6).unwrap();
+        router.set_fallback("/users/admin", 7).unwrap();
+
+        assert_eq!(*router.recognize("/users/admin/settings").unwrap().data, 1);
+        let m = router.recognize("/users/admin/profile").unwrap();
+        assert_eq!((*m.data, m.params["id"].as_str()), (2, "admin"));
+        assert_eq!(*router.recognize("/users/7/posts").unwrap().data, 3);
+        // the typed parameter is entered first and left on the dead end
+        let m = router.recognize("/users/7/posts/9").unwrap();
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("name", "7"), ("post", "9")]
+        );
+        let m = router.recognize("/files/static/b").unwrap();
+        assert_eq!((*m.data, m.params["path"].as_str()), (6, "static/b"));
+        assert_eq!(*router.recognize("/users/admin/x").unwrap().data, 7);
+        assert_eq!(*router.recognize_mut("/users/admin/x").unwrap().data, 7);
+        *router.recognize_mut("/users/admin/profile").unwrap().data += 10;
+        assert_eq!(*router.recognize("/users/bob/profile").unwrap().data, 12);
+
+        // bytes go back on dead ends the same way
+        let m = router.recognize_bytes(b"/users/admin/profile").unwrap();
+        assert_eq!((*m.data, &m.params["id"][..]), (12, &b"admin"[..]));
+        let m = router.recognize_bytes(b"/users/7/posts/9").unwrap();
+        assert_eq!((*m.data, &m.params["name"][..]), (4, &b"7"[..]));
+        assert_eq!(*router.recognize_bytes(b"/files/static/b").unwrap().data, 6);
+        assert_eq!(
+            *router.recognize_bytes(b"/users/admin/\xff").unwrap().data,
+            7
+        );
+    }
+
+    #[test]
+    fn middle_catch_all() {
+        let mut router = Router::default();
+        router.add("/repo/*path/manifest.json", 1).unwrap();
+        router.add("/repo/*path/tags/:tag", 2).unwrap();
+        router.add("/repo/*path", 3).unwrap();
+
+        let m = router.recognize("/repo/a/b/manifest.json").unwrap();
+        assert_eq!((*m.data, m.params["path"].as_str()), (1, "a/b"));
+        let m = router
+            .recognize("/repo/a/manifest.json/b/manifest.json")
+            .unwrap();
+        assert_eq!(
+            (*m.data, m.params["path"].as_str()),
+            (1, "a/manifest.json/b")
+        );
+        let m = router.recognize("/repo/a/tags/tags/v1").unwrap();
+        assert_eq!(
+            (*m.data, &m.params["path"][..], &m.params["tag"][..]),
+            (2, "a/tags", "v1")
+        );
+        assert_eq!(
+            m.params.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            ["path", "tag"]
+        );
+        let m = router.recognize("/repo/a/manifest.json/x").unwrap();
+        assert_eq!(
+            (*m.data, m.params["path"].as_str()),
+            (3, "a/manifest.json/x")
+        );
+        assert_eq!(
+            *router.recognize_mut("/repo/b/manifest.json").unwrap().data,
+            1
+        );
+        assert!(router.recognize("/repo/manifest.json").unwrap().data == &3);
+        assert!(router.add("/repo/*path/*rest", 5).is_err());
+    }
+
+    #[test]
+    fn catch_all_trailing_slash() {
+        let mut router = Router::default();
+        router.add("/files/*path", 1).unwrap();
+        let info = RouteInfo {
+            catch_all_slash: Some(TrailingSlash::Keep),
+            ..RouteInfo::default()
+        };
+        router.add_with_info("/proxy/*rest", info, 2).unwrap();
+        let caught = |router: &Router<_>, path| {
+            router
+                .recognize(path)
+                .unwrap()
+                .params
+                .get_index(0)
+                .map(String::from)
+        };
+        assert_eq!(
+            caught(&router, "/files/x/y/z/"),
+            Some(String::from("x/y/z/"))
+        );
+
+        router.options_mut().catch_all_slash = TrailingSlash::Strip;
+        assert_eq!(
+            caught(&router, "/files/x/y/z/"),
+            Some(String::from("x/y/z"))
+        );
+        assert_eq!(caught(&router, "/files/x//"), Some(String::from("x")));
+        assert_eq!(caught(&router, "/files/x"), Some(String::from("x")));
+        assert_eq!(caught(&router, "/proxy/x/y/"), Some(String::from("x/y/")));
+
+        let caught = |path| {
+            router
+                .recognize_bytes(path)
+                .unwrap()
+                .params
+                .into_values()
+                .next()
+        };
+        assert_eq!(caught(b"/files/x/\xff/"), Some(Cow::from(&b"x/\xff"[..])));
+        assert_eq!(caught(b"/proxy/x/y/"), Some(Cow::from(&b"x/y/"[..])));
+    }
+
+    #[test]
+    fn recognize_mut() {
+        let mut router = Router::default();
+        router.add("/a/b/c", 0).unwrap();
+        router.add("/user/:id<u32>", 0).unwrap();
+        router.add("/user/:name/:tab?", 0).unwrap();
+        router.add("/files/*path", 0).unwrap();
+        router.set_fallback("/user", 0).unwrap();
+        for path in [
+            "/a/b/c",
+            "/user/1",
+            "/user/2",
+            "/user/bob",
+            "/user/bob/x",
+            "/files/a/b",
+            "/user/1/2/3",
+        ] {
+            *router.recognize_mut(path).unwrap().data += 1;
+        }
+        let m = router.recognize_mut("/user/bob/tab").unwrap();
+        assert_eq!((*m.data, m.params["tab"].as_str()), (2, "tab"));
+        assert_eq!(*router.recognize("/a/b/c").unwrap().data, 1);
+        assert_eq!(*router.recognize("/user/3").unwrap().data, 2);
+        assert_eq!(*router.recognize("/files/x").unwrap().data, 1);
+        assert_eq!(*router.recognize("/user/1/2/3").unwrap().data, 1);
+        assert!(router.recognize_mut("/a/b").is_none());
+        assert!(router.recognize_mut("/x").is_none());
+    }
+
+    #[test]
+    fn named_conflicts() {
+        let mut router = Router::default();
+        router.add("/api/v1/users/:id", 1).unwrap();
+        router.add("/api/v1/files/*path", 2).unwrap();
+        router.add("/api/v1/posts/:id/:tab?", 3).unwrap();
+        let conflict = |route: &str, existing: &str, segment| {
+            let existing = String::from(existing);
+            Err(Error::new(Reason::Conflict { existing })
+                .at(segment)
+                .of(route))
+        };
+        assert_eq!(
+            router.add("/api/v1/users/:uid", 4),
+            conflict("/api/v1/users/:uid", "/api/v1/users/:id", 3)
+        );
+        assert_eq!(
+            router.add("/api/v1/users/*rest", 4),
+            conflict("/api/v1/users/*rest", "/api/v1/users/:id", 3)
+        );
+        assert_eq!(
+            router.add("/api/v1/files/:name", 4),
+            conflict("/api/v1/files/:name", "/api/v1/files/*path", 3)
+        );
+        assert_eq!(
+            router.add("/api/v1/posts/:id", 4),
+            conflict("/api/v1/posts/:id", "/api/v1/posts/:id/:tab?", 3)
+        );
+        let err = router.add("/api/v1/posts/:pid/:tab?", 4).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid route /api/v1/posts/:pid/:tab?: collides with /api/v1/posts/:id/:tab? at segment 4"
+        );
+        assert_eq!(router.list_routes().len(), 3);
+
+        let mut keys = Router::<_, u8>::default();
+        keys.add_keys(vec![Segment::Param("a".into())], 1).unwrap();
+        let err = keys
+            .add_keys(vec![Segment::CatchAll("b".into())], 2)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid route: route conflict at segment 0"
+        );
+    }
+
+    #[test]
+    fn debug_tree() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/user/:id", 1).unwrap();
+        router.add("/user/:id/posts/all", 2).unwrap();
+        router.add("/user/:name<bool>/:tab?", 3).unwrap();
+        router.add("/files/*path", 4).unwrap();
+        assert_eq!(
+            format!("{:?}", router),
+            r#"root => 0
+  "files"
+    * [path] => 4
+  "user"
+    : Type(Bool)
+      : [name, tab] optional => 3
+    : [id] => 1
+      "posts"/"all" [id] => 2
+"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn display_routes() {
         let mut router = Router::default();
         build_simple_router(&mut router);
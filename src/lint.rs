@@ -0,0 +1,206 @@
+use crate::merge::render_route;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Collation, Constraint, ParsedRoute, Router, Segment};
+use core::cmp::Ordering;
+use core::fmt;
+
+/// a dead or partly dead route found by `Router::lint`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub route: String,
+    pub kind: LintKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintKind {
+    /// every path of the route is taken by the earlier route `by`, so the
+    /// route never matches
+    Unreachable { by: String },
+    /// the constraint of `param` only lets through segments which the
+    /// constrained parameter of the earlier route `by` also takes, so the
+    /// route is only reached when the rest of the path misses `by`
+    CoveredParam { param: String, by: String },
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            LintKind::Unreachable { ref by } => {
+                write!(f, "{}: unreachable, shadowed by {}", self.route, by)
+            }
+            LintKind::CoveredParam { ref param, ref by } => {
+                write!(f, "{}: {} is covered by {}", self.route, param, by)
+            }
+        }
+    }
+}
+
+// whether segments passing `b` always pass `a`
+fn covers(a: &Option<Constraint>, b: &Option<Constraint>) -> bool {
+    match (a, b) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(a), Some(b)) => a.covers(b),
+    }
+}
+
+// whether paths matching the segment of `b` always match the one of `a`
+fn covers_segment(
+    a: &(Segment<String>, Option<Constraint>),
+    b: &(Segment<String>, Option<Constraint>),
+    collation: Collation,
+) -> bool {
+    match (&a.0, &b.0) {
+        (Segment::Static(x), Segment::Static(y)) => collation.compare(x, y) == Ordering::Equal,
+        (Segment::Param(_), Segment::Static(y)) => match a.1 {
+            Some(ref c) => c.matches(y),
+            None => true,
+        },
+        (Segment::Param(_), Segment::Param(_)) => covers(&a.1, &b.1),
+        (Segment::CatchAll(_), Segment::CatchAll(_)) => true,
+        _ => false,
+    }
+}
+
+// whether both segments lead to the same node
+fn same_node(
+    a: &(Segment<String>, Option<Constraint>),
+    b: &(Segment<String>, Option<Constraint>),
+    collation: Collation,
+) -> bool {
+    match (&a.0, &b.0) {
+        (Segment::Static(x), Segment::Static(y)) => collation.compare(x, y) == Ordering::Equal,
+        (Segment::Param(_), Segment::Param(_)) => a.1 == b.1,
+        (Segment::CatchAll(_), Segment::CatchAll(_)) => true,
+        _ => false,
+    }
+}
+
+impl<T> Router<T> {
+    /// find routes which can never match or only match as a last resort, in
+    /// `list_routes` order
+    ///
+    /// static segments are tried before parameters and catchalls, so a route
+    /// is only shadowed where sibling parameters are both constrained and the
+    /// earlier one takes every segment of the later one, like `:id<u32>` and
+    /// `:n<u8>`; regex constraints only cover themselves; with a
+    /// `Options::tie_breaker` tied parameters aren't reported
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{LintKind, Router};
+    /// let mut router = Router::default();
+    /// router.add("/n/:id<u32>", 1).unwrap();
+    /// router.add("/n/:small<u8>", 2).unwrap();
+    ///
+    /// let lints = router.lint();
+    /// assert_eq!(
+    ///     lints[0].kind,
+    ///     LintKind::Unreachable { by: String::from("/n/:id<u32>") }
+    /// );
+    /// assert_eq!(
+    ///     lints[0].to_string(),
+    ///     "/n/:small<u8>: unreachable, shadowed by /n/:id<u32>"
+    /// );
+    /// ```
+    pub fn lint(&self) -> Vec<Lint> {
+        if self.options.tie_breaker.is_some() {
+            return vec![];
+        }
+        let collation = self.options.collation;
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+        let routes = routes.into_iter().map(|(r, _)| r).collect::<Vec<_>>();
+
+        let mut lints = vec![];
+        for (i, b) in routes.iter().enumerate() {
+            let mut covered = None;
+            for a in routes[..i].iter() {
+                let d = match a
+                    .0
+                    .iter()
+                    .zip(b.0.iter())
+                    .position(|(x, y)| !same_node(x, y, collation))
+                {
+                    Some(d) => d,
+                    None => continue,
+                };
+                // only constrained siblings are tried in the order they were added
+                let param = match (&a.0[d], &b.0[d]) {
+                    ((Segment::Param(_), Some(x)), (Segment::Param(param), Some(y)))
+                        if x.covers(y) =>
+                    {
+                        param
+                    }
+                    _ => continue,
+                };
+                if shadows(a, b, d, collation) {
+                    covered = Some(LintKind::Unreachable {
+                        by: render_route(a),
+                    });
+                    break;
+                }
+                if covered.is_none() {
+                    covered = Some(LintKind::CoveredParam {
+                        param: param.clone(),
+                        by: render_route(a),
+                    });
+                }
+            }
+            if let Some(kind) = covered {
+                lints.push(Lint {
+                    route: render_route(b),
+                    kind,
+                });
+            }
+        }
+        lints
+    }
+}
+
+// whether every path of `b` matches `a` after they part at `d`
+fn shadows(a: &ParsedRoute, b: &ParsedRoute, d: usize, collation: Collation) -> bool {
+    a.0.len() == b.0.len()
+        && (a.1 || !b.1)
+        && a.0[d + 1..]
+            .iter()
+            .zip(b.0[d + 1..].iter())
+            .all(|(x, y)| covers_segment(x, y, collation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Pick, TieBreaker};
+
+    #[test]
+    fn lint_routes() {
+        let mut router = Router::default();
+        router.add("/n/:id<u32>/:tab", 1).unwrap();
+        router.add("/n/:small<u8>/info", 2).unwrap();
+        router.add("/n/:mid<u16>/x/y", 3).unwrap();
+        router.add("/n/:big<u64>/x", 4).unwrap();
+        router.add("/n/:neg<i8>/x", 5).unwrap();
+        router.add("/n/:word", 6).unwrap();
+        router.add("/files/*path", 7).unwrap();
+        router.add("/files/readme", 8).unwrap();
+        router.add("/d/:day<yyyy-mm-dd>/x", 9).unwrap();
+        router.add("/d/:date<yyyy-mm-dd>/y", 10).unwrap();
+        assert_eq!(
+            router
+                .lint()
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "/n/:small<u8>/info: unreachable, shadowed by /n/:id<u32>/:tab",
+                "/n/:mid<u16>/x/y: mid is covered by /n/:id<u32>/:tab",
+            ]
+        );
+
+        router.options_mut().tie_breaker = Some(TieBreaker::new(|_| Pick::First));
+        assert!(router.lint().is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::ShardedRouter;
+use crate::{MethodRouter, Router};
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// summary of a route table, the routes in sorted order and their count per
+/// scope, to pin the table in tests
+///
+/// the scope of a route is its first segment as written, `api` for
+/// `/api/users` and the empty string for `/`; the expected tables are plain
+/// slices so they can live in `const` items next to the tests
+///
+/// # Example
+///
+/// ```
+/// use path_router::Router;
+/// const ROUTES: &[&str] = &["/api/users", "/api/users/:id", "/health"];
+///
+/// let mut router = Router::default();
+/// router.add("/health", 1).unwrap();
+/// router.add("/api/users/:id", 2).unwrap();
+/// router.add("/api/users", 3).unwrap();
+///
+/// let manifest = router.manifest();
+/// assert_eq!((manifest.len(), manifest.scope("api")), (3, 2));
+/// manifest.assert_routes(ROUTES);
+/// manifest.assert_scopes(&[("api", 2), ("health", 1)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    routes: Vec<String>,
+    scopes: BTreeMap<String, usize>,
+}
+
+impl Manifest {
+    // `routes` are `(path, name)`, the path gives the scope
+    fn new<I: IntoIterator<Item = (String, String)>>(routes: I) -> Manifest {
+        let mut manifest = Manifest::default();
+        for (path, name) in routes {
+            let scope = path.trim_start_matches('/').split('/').next().unwrap_or("");
+            *manifest.scopes.entry(String::from(scope)).or_insert(0) += 1;
+            manifest.routes.push(name);
+        }
+        manifest.routes.sort();
+        manifest
+    }
+
+    /// number of routes
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// the routes in sorted order
+    pub fn routes(&self) -> &[String] {
+        &self.routes
+    }
+
+    /// number of routes in the scope, 0 for an unknown scope
+    pub fn scope(&self, scope: &str) -> usize {
+        self.scopes.get(scope).copied().unwrap_or(0)
+    }
+
+    /// the scopes in sorted order with their number of routes
+    pub fn scopes(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.scopes.iter().map(|(s, &n)| (s.as_str(), n))
+    }
+
+    /// panic unless the routes are exactly `expected`, in any order
+    ///
+    /// the message lists the missing and the unexpected routes
+    pub fn assert_routes(&self, expected: &[&str]) {
+        let mut expected = expected.to_vec();
+        expected.sort_unstable();
+        let missing = expected
+            .iter()
+            .filter(|r| self.routes.binary_search_by(|x| x.as_str().cmp(r)).is_err())
+            .collect::<Vec<_>>();
+        let unexpected = self
+            .routes
+            .iter()
+            .filter(|r| expected.binary_search(&r.as_str()).is_err())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() || !unexpected.is_empty() {
+            panic!(
+                "route table changed\n  missing: {:?}\n  unexpected: {:?}",
+                missing, unexpected
+            );
+        }
+    }
+
+    /// panic unless every scope has the expected number of routes, scopes
+    /// missing from `expected` must be empty
+    pub fn assert_scopes(&self, expected: &[(&str, usize)]) {
+        let expected = expected
+            .iter()
+            .copied()
+            .filter(|&(_, n)| n > 0)
+            .collect::<BTreeMap<_, _>>();
+        let actual = self.scopes().collect::<BTreeMap<_, _>>();
+        if expected != actual {
+            panic!(
+                "route counts per scope changed\n  expected: {:?}\n  actual: {:?}",
+                expected, actual
+            );
+        }
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for route in self.routes.iter() {
+            writeln!(f, "{}", route)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Router<T> {
+    /// summary of the routes, see `Manifest`
+    pub fn manifest(&self) -> Manifest {
+        Manifest::new(self.list_routes().into_iter().map(|r| (r.clone(), r)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ShardedRouter<T> {
+    /// summary of the routes of every shard, see `Manifest`
+    pub fn manifest(&self) -> Manifest {
+        Manifest::new(self.list_routes().into_iter().map(|r| (r.clone(), r)))
+    }
+}
+
+impl<T> MethodRouter<T> {
+    /// summary of the routes, named like `GET /users`, see `Manifest`
+    pub fn manifest(&self) -> Manifest {
+        Manifest::new(
+            self.list_routes()
+                .into_iter()
+                .map(|(method, r)| (r.clone(), format!("{} {}", method, r))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUTES: &[&str] = &["/", "/a/:id", "/a/b", "/c/*rest"];
+
+    #[test]
+    fn manifest() {
+        let mut router = Router::default();
+        for (i, route) in ROUTES.iter().rev().enumerate() {
+            router.add(route, i).unwrap();
+        }
+        let manifest = router.manifest();
+        assert_eq!(manifest.routes(), ROUTES);
+        assert_eq!(
+            manifest.scopes().collect::<Vec<_>>(),
+            [("", 1), ("a", 2), ("c", 1)]
+        );
+        manifest.assert_routes(ROUTES);
+        manifest.assert_scopes(&[("", 1), ("a", 2), ("c", 1), ("d", 0)]);
+        assert_eq!(manifest.to_string(), "/\n/a/:id\n/a/b\n/c/*rest\n");
+
+        let mut methods = MethodRouter::new();
+        methods.get("/a/b", 1).unwrap();
+        methods.post("/a/b", 2).unwrap();
+        let manifest = methods.manifest();
+        assert_eq!(manifest.routes(), ["GET /a/b", "POST /a/b"]);
+        assert_eq!(manifest.scope("a"), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing: [\"/a/b\"]")]
+    fn removed_route() {
+        let mut router = Router::default();
+        router.add("/a/:id", 1).unwrap();
+        router.manifest().assert_routes(&["/a/:id", "/a/b"]);
+    }
+}
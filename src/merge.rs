@@ -0,0 +1,755 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{
+    Collation, Constraint, Error, NodeKind, ParsedRoute, Reason, RouteInfo, Router, Segment,
+};
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+// info and data of a route on one side of a three-way merge
+type Side<'a, T> = Option<(&'a RouteInfo, &'a T)>;
+
+// the segments of the prefix of a fallback with its data
+pub(crate) type Fallback<T> = (Vec<(Segment<String>, Option<Constraint>)>, T);
+
+/// routes of a merged router which couldn't be grafted
+///
+/// the other routes are merged, the rejected ones are handed back with their
+/// data
+#[derive(Debug)]
+pub struct MergeError<T> {
+    pub conflicts: Vec<MergeConflict<T>>,
+}
+
+/// a route colliding with routes of the router it is merged into
+#[derive(Debug)]
+pub struct MergeConflict<T> {
+    pub route: String,
+    /// routes of the router which collide with `route`
+    pub colliding: Vec<String>,
+    pub info: RouteInfo,
+    pub data: T,
+    /// whether `route` is the prefix of a fallback, colliding with the
+    /// fallback or the sub route of the same prefix
+    pub fallback: bool,
+}
+
+impl<T> fmt::Display for MergeError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} conflicting routes", self.conflicts.len())?;
+        for c in self.conflicts.iter() {
+            let kind = if c.fallback { "fallback " } else { "" };
+            write!(
+                f,
+                "\n  {}{} collides with {}",
+                kind,
+                c.route,
+                c.colliding.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> error::Error for MergeError<T> {}
+
+/// error of `Router::mount`
+#[derive(Debug)]
+pub enum MountError<T> {
+    /// the prefix is not a valid sub route
+    Prefix(Error),
+    Conflicts(MergeError<T>),
+}
+
+impl<T> fmt::Display for MountError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MountError::Prefix(e) => write!(f, "prefix: {}", e),
+            MountError::Conflicts(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> error::Error for MountError<T> {}
+
+/// routes a three-way merge couldn't resolve
+#[derive(Debug)]
+pub struct Merge3Error<T> {
+    pub conflicts: Vec<Merge3Conflict<T>>,
+}
+
+/// a route changed differently on both sides of a three-way merge, or a
+/// route colliding with the merged routes
+#[derive(Debug)]
+pub struct Merge3Conflict<T> {
+    pub route: String,
+    /// data of the route in each router, `None` where it's missing
+    pub base: Option<T>,
+    pub ours: Option<T>,
+    pub theirs: Option<T>,
+    /// merged routes which collide with `route`, empty if both sides changed
+    /// the route
+    pub colliding: Vec<String>,
+}
+
+impl<T> fmt::Display for Merge3Error<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} conflicting routes", self.conflicts.len())?;
+        for c in self.conflicts.iter() {
+            if c.colliding.is_empty() {
+                write!(f, "\n  {} changed on both sides", c.route)?;
+            } else {
+                write!(
+                    f,
+                    "\n  {} collides with {}",
+                    c.route,
+                    c.colliding.join(", ")
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> error::Error for Merge3Error<T> {}
+
+impl<T> Router<T> {
+    /// graft all routes of another router into this one
+    ///
+    /// routes are taken with their data and schema references, a route that
+    /// already exists here or conflicts with an existing route is rejected,
+    /// the error lists the colliding routes; fallbacks are taken too, a
+    /// fallback of a prefix which already has one is rejected
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/users/:id", 1).unwrap();
+    ///
+    /// let mut billing = Router::default();
+    /// billing.add("/billing/invoices", 2).unwrap();
+    /// billing.add("/users/:uid", 3).unwrap();
+    ///
+    /// let err = router.merge(billing).unwrap_err();
+    /// assert_eq!(err.conflicts[0].route, "/users/:uid");
+    /// assert_eq!(err.conflicts[0].colliding, vec!["/users/:id"]);
+    /// assert_eq!(err.conflicts[0].data, 3);
+    /// assert_eq!(*router.recognize("/billing/invoices").unwrap().data, 2);
+    /// ```
+    pub fn merge(&mut self, other: Router<T>) -> Result<(), MergeError<T>> {
+        let (mut routes, mut fallbacks) = (vec![], vec![]);
+        other.into_routes(vec![], &mut routes, &mut fallbacks);
+        self.graft(routes, fallbacks)
+    }
+
+    /// graft all routes of another router under a prefix, like `merge` with
+    /// the prefix written before every route
+    ///
+    /// the prefix follows the rules of `sub_route`, it may have parameters
+    /// and a catchall but can't end with an optional parameter; conflicts
+    /// name the routes with the prefix, an invalid prefix drops the other
+    /// router
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{MountError, Router};
+    /// let mut api = Router::default();
+    /// api.add("/users/:id", 1).unwrap();
+    /// api.add("/files/*path", 2).unwrap();
+    ///
+    /// let mut router = Router::default();
+    /// router.add("/v1/:tenant/files/:name", 3).unwrap();
+    /// let err = router.mount("/v1/:tenant", api).unwrap_err();
+    /// match err {
+    ///     MountError::Conflicts(e) => assert_eq!(e.conflicts[0].route, "/v1/:tenant/files/*path"),
+    ///     MountError::Prefix(_) => unreachable!(),
+    /// }
+    /// let m = router.recognize("/v1/acme/users/7").unwrap();
+    /// assert_eq!((&m.params["tenant"][..], &m.params["id"][..]), ("acme", "7"));
+    /// ```
+    pub fn mount(&mut self, prefix: &str, other: Router<T>) -> Result<(), MountError<T>> {
+        let (segments, optional) = self.parse_route(prefix, None).map_err(MountError::Prefix)?;
+        if optional {
+            let error = Error::new(Reason::MisplacedOptional).of(prefix);
+            return Err(MountError::Prefix(error));
+        }
+        let prefix = segments;
+        let (mut routes, mut fallbacks) = (vec![], vec![]);
+        other.into_routes(vec![], &mut routes, &mut fallbacks);
+        let all = routes.iter_mut().map(|((segments, _), _, _)| segments);
+        for segments in all.chain(fallbacks.iter_mut().map(|(segments, _)| segments)) {
+            segments.splice(..0, prefix.iter().cloned());
+        }
+        self.graft(routes, fallbacks).map_err(MountError::Conflicts)
+    }
+
+    // insert routes and fallbacks taken from another router
+    pub(crate) fn graft(
+        &mut self,
+        routes: Vec<(ParsedRoute, RouteInfo, T)>,
+        fallbacks: Vec<Fallback<T>>,
+    ) -> Result<(), MergeError<T>> {
+        let collation = self.options.collation;
+        let mut rejected = vec![];
+        for (route, info, data) in routes {
+            // a route turned down mustn't leave the nodes made for it behind
+            if self
+                .find_conflict(&route.0, route.1, &collation.for_keys())
+                .is_some()
+            {
+                rejected.push((route, info, data));
+                continue;
+            }
+            match self.insert_node(route.0.clone(), route.1, &collation.for_keys()) {
+                Ok(node) if node.data.is_none() => {
+                    node.info = info;
+                    node.set_data(data);
+                }
+                _ => rejected.push((route, info, data)),
+            }
+        }
+        let mut rejected_fallbacks = vec![];
+        for (prefix, data) in fallbacks {
+            match self.sub_route_node(prefix.clone()) {
+                Ok(node) if node.fallback.is_none() => node.fallback = Some(data),
+                _ => rejected_fallbacks.push((prefix, data)),
+            }
+        }
+        if rejected.is_empty() && rejected_fallbacks.is_empty() {
+            return Ok(());
+        }
+
+        let mut existing = vec![];
+        self.parsed_routes(vec![], &mut existing);
+        let conflicts = rejected
+            .into_iter()
+            .map(|(route, info, data)| MergeConflict {
+                colliding: existing
+                    .iter()
+                    .filter(|(e, _)| collides(e, &route, collation))
+                    .map(|(e, _)| render_route(e))
+                    .collect(),
+                route: render_route(&route),
+                info,
+                data,
+                fallback: false,
+            })
+            .chain(rejected_fallbacks.into_iter().map(|(prefix, data)| {
+                let prefix = render_route(&(prefix, false));
+                MergeConflict {
+                    colliding: vec![prefix.clone()],
+                    route: prefix,
+                    info: RouteInfo::default(),
+                    data,
+                    fallback: true,
+                }
+            }))
+            .collect();
+        Err(MergeError { conflicts })
+    }
+
+    // segments of this node, parameter names are left empty
+    fn node_segments(&self) -> Vec<(Segment<String>, Option<Constraint>)> {
+        match self.kind {
+            NodeKind::Static => core::iter::once(&self.text)
+                .chain(self.tail.iter())
+                .map(|k| (Segment::Static(k.clone()), None))
+                .collect(),
+            NodeKind::Param => vec![(Segment::Param(String::new()), self.constraint.clone())],
+            NodeKind::CatchAll => vec![(Segment::CatchAll(String::new()), None)],
+        }
+    }
+
+    // routes below the node with their data nodes in `list_routes` order
+    pub(crate) fn parsed_routes<'a>(
+        &'a self,
+        pre: Vec<(Segment<String>, Option<Constraint>)>,
+        out: &mut Vec<(ParsedRoute, &'a Router<T>)>,
+    ) {
+        if self.data.is_some() {
+            out.push(((named(pre.clone(), &self.params), self.optional), self));
+        }
+        for node in self.children() {
+            let mut cur = pre.clone();
+            cur.extend(node.node_segments());
+            node.parsed_routes(cur, out);
+        }
+    }
+
+    // fallbacks below the node with their data in `list_routes` order
+    pub(crate) fn parsed_fallbacks<'a>(
+        &'a self,
+        pre: Vec<(Segment<String>, Option<Constraint>)>,
+        out: &mut Vec<Fallback<&'a T>>,
+    ) {
+        if let Some(ref fallback) = self.fallback {
+            out.push((named(pre.clone(), &self.base), fallback));
+        }
+        for node in self.children() {
+            let mut cur = pre.clone();
+            cur.extend(node.node_segments());
+            node.parsed_fallbacks(cur, out);
+        }
+    }
+
+    // take the routes and the fallbacks out of the router, the parameters
+    // of a fallback prefix are named like the sub route it's set on
+    pub(crate) fn into_routes(
+        self,
+        pre: Vec<(Segment<String>, Option<Constraint>)>,
+        out: &mut Vec<(ParsedRoute, RouteInfo, T)>,
+        fallbacks: &mut Vec<Fallback<T>>,
+    ) {
+        if let Some(data) = self.data {
+            out.push((
+                (named(pre.clone(), &self.params), self.optional),
+                self.info,
+                data,
+            ));
+        }
+        if let Some(fallback) = self.fallback {
+            fallbacks.push((named(pre.clone(), &self.base), fallback));
+        }
+        let children = self
+            .static_children
+            .into_iter()
+            .chain(self.param_children)
+            .chain(self.catch_all_child.map(|n| *n));
+        for node in children {
+            let mut cur = pre.clone();
+            cur.extend(node.node_segments());
+            node.into_routes(cur, out, fallbacks);
+        }
+    }
+
+    fn children(&self) -> impl Iterator<Item = &Router<T>> {
+        self.static_children
+            .iter()
+            .chain(self.param_children.iter())
+            .chain(self.catch_all_child.as_deref())
+    }
+}
+
+impl<T: Clone + PartialEq> Router<T> {
+    /// three-way merge of two routers edited from a common base, like a
+    /// merge in version control
+    ///
+    /// a route is matched by its pattern, a route added, removed or changed
+    /// on one side only takes that change, a route changed the same way on
+    /// both sides is taken once, a route whose data or info changed
+    /// differently on both sides is a conflict, so are routes which can't
+    /// live together after the merge, e.g. `/a/:x` added on one side and
+    /// `/a/:y` on the other, the options of `ours` are kept
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut base = Router::default();
+    /// base.add("/users", 1).unwrap();
+    /// base.add("/posts", 2).unwrap();
+    ///
+    /// let mut ours = Router::default();
+    /// ours.add("/users", 10).unwrap();
+    /// ours.add("/posts", 2).unwrap();
+    /// let mut theirs = Router::default();
+    /// theirs.add("/users", 1).unwrap();
+    /// theirs.add("/tags", 3).unwrap();
+    ///
+    /// let merged = Router::merge3(&base, &ours, &theirs).unwrap();
+    /// assert_eq!(merged.list_routes(), vec!["/tags", "/users"]);
+    /// assert_eq!(*merged.recognize("/users").unwrap().data, 10);
+    ///
+    /// ours.add("/tags", 30).unwrap();
+    /// let err = Router::merge3(&base, &ours, &theirs).unwrap_err();
+    /// assert_eq!(err.conflicts[0].route, "/tags");
+    /// assert_eq!((err.conflicts[0].ours, err.conflicts[0].theirs), (Some(30), Some(3)));
+    /// ```
+    pub fn merge3(
+        base: &Router<T>,
+        ours: &Router<T>,
+        theirs: &Router<T>,
+    ) -> Result<Router<T>, Merge3Error<T>> {
+        let mut table = BTreeMap::<String, (ParsedRoute, [Side<T>; 3])>::new();
+        for (i, router) in [base, ours, theirs].iter().enumerate() {
+            let mut routes = vec![];
+            router.parsed_routes(vec![], &mut routes);
+            for (route, node) in routes {
+                let side = Some((&node.info, node.data.as_ref().expect("data node")));
+                table
+                    .entry(route_key(&route))
+                    .or_insert_with(|| (route, [None; 3]))
+                    .1[i] = side;
+            }
+        }
+
+        let collation = ours.options.collation;
+        let mut router = Router::empty(Arc::clone(&ours.options));
+        let mut conflicts = vec![];
+        let mut rejected = vec![];
+        for (route, [b, o, t]) in table.into_values() {
+            let taken = if o == t || t == b {
+                o
+            } else if o == b {
+                t
+            } else {
+                conflicts.push(Merge3Conflict {
+                    route: render_route(&route),
+                    base: b.map(|(_, d)| d.clone()),
+                    ours: o.map(|(_, d)| d.clone()),
+                    theirs: t.map(|(_, d)| d.clone()),
+                    colliding: vec![],
+                });
+                continue;
+            };
+            if let Some((info, data)) = taken {
+                if router
+                    .find_conflict(&route.0, route.1, &collation.for_keys())
+                    .is_some()
+                {
+                    rejected.push((route, [b, o, t]));
+                    continue;
+                }
+                match router.insert_node(route.0.clone(), route.1, &collation.for_keys()) {
+                    Ok(node) if node.data.is_none() => {
+                        node.info = info.clone();
+                        node.set_data(data.clone());
+                    }
+                    _ => rejected.push((route, [b, o, t])),
+                }
+            }
+        }
+        if conflicts.is_empty() && rejected.is_empty() {
+            return Ok(router);
+        }
+
+        let mut merged = vec![];
+        router.parsed_routes(vec![], &mut merged);
+        for (route, [b, o, t]) in rejected {
+            conflicts.push(Merge3Conflict {
+                colliding: merged
+                    .iter()
+                    .filter(|(e, _)| collides(e, &route, collation))
+                    .map(|(e, _)| render_route(e))
+                    .collect(),
+                route: render_route(&route),
+                base: b.map(|(_, d)| d.clone()),
+                ours: o.map(|(_, d)| d.clone()),
+                theirs: t.map(|(_, d)| d.clone()),
+            });
+        }
+        Err(Merge3Error { conflicts })
+    }
+}
+
+// routes with the same key are the same route on each side of a merge
+pub(crate) fn route_key(route: &ParsedRoute) -> String {
+    let mut key = render_route(route);
+    for (_, constraint) in route.0.iter() {
+        if let Some(re) = constraint.as_ref().and_then(Constraint::regex_source) {
+            key.push(' ');
+            key.push_str(re);
+        }
+    }
+    key
+}
+
+fn named(
+    mut segments: Vec<(Segment<String>, Option<Constraint>)>,
+    params: &[Arc<str>],
+) -> Vec<(Segment<String>, Option<Constraint>)> {
+    let mut params = params.iter();
+    for (segment, constraint) in segments.iter_mut() {
+        if let Segment::Param(name) | Segment::CatchAll(name) = segment {
+            *name = params
+                .next()
+                .map_or_else(String::new, |p| String::from(&**p));
+            if let Some(Constraint::Template(t)) = constraint {
+                let names = t.names_mut();
+                names[0] = name.clone();
+                for rest in names[1..].iter_mut() {
+                    *rest = params
+                        .next()
+                        .map_or_else(String::new, |p| String::from(&**p));
+                }
+            }
+        }
+    }
+    segments
+}
+
+// whether two routes can't live in one router
+pub(crate) fn collides(a: &ParsedRoute, b: &ParsedRoute, collation: Collation) -> bool {
+    let collate = collation.for_keys();
+    let mut router = Router::<()>::default();
+    if router.insert(a.0.clone(), a.1, (), &collate).is_err() {
+        return false;
+    }
+    match router.insert_node(b.0.clone(), b.1, &collate) {
+        Ok(node) => node.data.is_some(),
+        Err(_) => true,
+    }
+}
+
+pub(crate) fn render_route(route: &ParsedRoute) -> String {
+    let mut result = String::new();
+    for (segment, constraint) in route.0.iter() {
+        result.push('/');
+        match segment {
+            Segment::Static(text) => result.push_str(text),
+            Segment::Param(name) => match constraint {
+                Some(Constraint::Template(t)) => result.push_str(&t.to_string()),
+                Some(Constraint::Type(ty)) => result.push_str(&format!(":{}<{}>", name, ty.name())),
+                Some(Constraint::Custom(m)) => result.push_str(&format!(":{}<{}>", name, m.name())),
+                _ => {
+                    result.push(':');
+                    result.push_str(name);
+                }
+            },
+            Segment::CatchAll(name) => {
+                result.push('*');
+                result.push_str(name);
+            }
+        }
+    }
+    if result.is_empty() {
+        result.push('/');
+    }
+    if route.1 {
+        result.push('?');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_routers() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/users/:id", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+
+        let mut admin = Router::default();
+        admin.add("/admin/users/:id<u32>", 10).unwrap();
+        admin
+            .add_with_info(
+                "/admin/stats",
+                RouteInfo {
+                    response_schema: Some(String::from("#/Stats")),
+                    ..RouteInfo::default()
+                },
+                11,
+            )
+            .unwrap();
+        admin.add("/users/:id/posts/:post?", 12).unwrap();
+        router.merge(admin).unwrap();
+
+        assert_eq!(*router.recognize("/admin/users/7").unwrap().data, 10);
+        assert!(router.recognize("/admin/users/x").is_none());
+        let stats = router.recognize("/admin/stats").unwrap();
+        assert_eq!(stats.route.response_schema.as_deref(), Some("#/Stats"));
+        assert_eq!(*router.recognize("/users/1/posts").unwrap().data, 12);
+
+        let mut other = Router::default();
+        other.add("/", 20).unwrap();
+        other.add("/files/:name", 21).unwrap();
+        other.add("/about", 22).unwrap();
+        let err = router.merge(other).unwrap_err();
+        assert_eq!(
+            err.conflicts
+                .iter()
+                .map(|c| (c.route.as_str(), c.colliding.clone(), c.data))
+                .collect::<Vec<_>>(),
+            vec![
+                ("/", vec![String::from("/")], 20),
+                ("/files/:name", vec![String::from("/files/*path")], 21),
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "2 conflicting routes\n  / collides with /\n  /files/:name collides with /files/*path"
+        );
+        assert_eq!(*router.recognize("/").unwrap().data, 0);
+        assert_eq!(*router.recognize("/about").unwrap().data, 22);
+    }
+
+    #[test]
+    fn rejected_graft() {
+        let mut router = Router::default();
+        router.add("/files/*path", 1).unwrap();
+        router.add("/users/:id", 2).unwrap();
+        let before = format!("{:?}", router);
+
+        let mut other = Router::default();
+        other.add("/files/:name/raw", 10).unwrap();
+        other.add("/users/:id/:tab?", 11).unwrap();
+        let err = router.merge(other).unwrap_err();
+        assert_eq!(err.conflicts.len(), 2);
+        assert_eq!(format!("{:?}", router), before);
+    }
+
+    #[test]
+    fn mount_routers() {
+        let mut api = Router::default();
+        api.add("/", 0).unwrap();
+        api.add("/users/:id/:tab?", 1).unwrap();
+        api.add("/files/*path/raw", 2).unwrap();
+        api.add("/v:major.:minor", 3).unwrap();
+
+        let mut router = Router::default();
+        router.add("/t/:tenant/users/me", 10).unwrap();
+        router.add("/t/:tenant/v1.0", 11).unwrap();
+        router.mount("/t/:tenant", api).unwrap();
+        assert_eq!(
+            router.list_routes(),
+            [
+                "/t/:tenant",
+                "/t/:tenant/files/*path/raw",
+                "/t/:tenant/users/me",
+                "/t/:tenant/users/:id/:tab?",
+                "/t/:tenant/v1.0",
+                "/t/:tenant/v:major.:minor",
+            ]
+        );
+        let m = router.recognize("/t/acme/users/7/repos").unwrap();
+        assert_eq!((*m.data, &m.params["tenant"][..]), (1, "acme"));
+        assert_eq!((&m.params["id"][..], &m.params["tab"][..]), ("7", "repos"));
+        let m = router.recognize("/t/acme/files/a/b/raw").unwrap();
+        assert_eq!((*m.data, &m.params["path"][..]), (2, "a/b"));
+        assert_eq!(*router.recognize("/t/acme/v2.1").unwrap().data, 3);
+        assert_eq!(*router.recognize("/t/acme/users/me").unwrap().data, 10);
+
+        let mut other = Router::default();
+        other.add("/a", 20).unwrap();
+        other.add("/users/:uid", 21).unwrap();
+        let err = router.mount("/t/:tenant", other).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1 conflicting routes\n  /t/:tenant/users/:uid collides with /t/:tenant/users/:id/:tab?"
+        );
+        assert_eq!(*router.recognize("/t/x/a").unwrap().data, 20);
+
+        let mut other = Router::default();
+        other.add("/b", 30).unwrap();
+        for prefix in ["/t/:x?", "t"] {
+            assert!(matches!(
+                router.mount(prefix, Router::default()),
+                Err(MountError::Prefix(Error {
+                    reason: Reason::MisplacedOptional | Reason::NoLeadingSlash,
+                    ..
+                }))
+            ));
+        }
+        router.mount("/", other).unwrap();
+        assert_eq!(*router.recognize("/b").unwrap().data, 30);
+    }
+
+    #[test]
+    fn merged_fallbacks() {
+        let mut api = Router::default();
+        api.add("/api/users/:id", 1).unwrap();
+        api.set_fallback("/api", 9).unwrap();
+        api.sub_route("/t/:tenant")
+            .unwrap()
+            .set_fallback("/files", 8)
+            .unwrap();
+
+        let mut merged = Router::default();
+        merged.merge(api.clone()).unwrap();
+        assert_eq!(*merged.recognize("/api/zz").unwrap().data, 9);
+        assert_eq!(*merged.recognize("/t/acme/files/x").unwrap().data, 8);
+        let mut other = Router::default();
+        other.set_fallback("/api", 7).unwrap();
+        let err = merged.merge(other).unwrap_err();
+        assert_eq!(
+            (err.conflicts[0].fallback, err.conflicts[0].data),
+            (true, 7)
+        );
+        assert_eq!(
+            err.to_string(),
+            "1 conflicting routes\n  fallback /api collides with /api"
+        );
+
+        let mut mounted = Router::default();
+        mounted.mount("/v1", api.clone()).unwrap();
+        assert_eq!(*mounted.recognize("/v1/api/zz").unwrap().data, 9);
+        assert!(mounted.recognize("/api/zz").is_none());
+
+        let prefixed = api.clone().with_prefix("/v2").unwrap();
+        assert_eq!(*prefixed.recognize("/v2/api/zz").unwrap().data, 9);
+        assert_eq!(*prefixed.recognize("/v2/t/a/files/x").unwrap().data, 8);
+        let stripped = prefixed.strip_prefix("/v2/api").unwrap();
+        assert_eq!(*stripped.recognize("/zz").unwrap().data, 9);
+        assert_eq!(*stripped.recognize("/t/a/files/x").unwrap().data, 9);
+        assert_eq!(stripped.list_routes(), ["/users/:id"]);
+    }
+
+    #[test]
+    fn merge3_routers() {
+        let routes = |rs: &[(&str, usize)]| {
+            let mut router = Router::default();
+            for (route, data) in rs {
+                router.add(route, *data).unwrap();
+            }
+            router
+        };
+        let base = routes(&[("/a", 1), ("/b", 2), ("/c", 3), ("/d/:id", 4), ("/g", 8)]);
+        let mut ours = routes(&[("/a", 10), ("/b", 2), ("/c", 30), ("/e", 5)]);
+        let summary = RouteInfo {
+            summary: Some(String::from("g")),
+            ..RouteInfo::default()
+        };
+        ours.add_with_info("/g", summary.clone(), 8).unwrap();
+        let theirs = routes(&[
+            ("/a", 1),
+            ("/c", 30),
+            ("/d/:id", 4),
+            ("/e", 5),
+            ("/f/:x", 6),
+            ("/g", 8),
+        ]);
+
+        let merged = Router::merge3(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged.list_routes(), ["/a", "/c", "/e", "/f/:x", "/g"]);
+        assert_eq!(*merged.recognize("/a").unwrap().data, 10);
+        assert_eq!(*merged.recognize("/c").unwrap().data, 30);
+        assert_eq!(*merged.recognize("/g").unwrap().route, summary);
+
+        // removed on one side and changed on the other, and a collision
+        let ours = routes(&[("/a", 1), ("/c", 3), ("/d/:id", 4), ("/f/:y", 7)]);
+        let theirs = routes(&[
+            ("/a", 1),
+            ("/b", 20),
+            ("/c", 3),
+            ("/d/:id", 4),
+            ("/f/:x", 6),
+        ]);
+        let err = Router::merge3(&base, &ours, &theirs).unwrap_err();
+        assert_eq!(
+            err.conflicts
+                .iter()
+                .map(|c| (c.route.as_str(), c.base, c.ours, c.theirs))
+                .collect::<Vec<_>>(),
+            [
+                ("/b", Some(2), None, Some(20)),
+                ("/f/:y", None, Some(7), None)
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "2 conflicting routes\n  /b changed on both sides\n  /f/:y collides with /f/:x"
+        );
+    }
+}
@@ -0,0 +1,481 @@
+use crate::keyed::set_slot;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::resolution::Landing;
+use crate::{route_params, Error, Match, Options, Reason, Resolution, RouteInfo, Router, Trail};
+use core::fmt;
+use core::str::FromStr;
+
+/// HTTP request method
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Head,
+    Options,
+    Connect,
+    Patch,
+    Trace,
+    /// extension method like `PURGE`
+    Other(String),
+}
+
+impl Method {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Head => "HEAD",
+            Method::Options => "OPTIONS",
+            Method::Connect => "CONNECT",
+            Method::Patch => "PATCH",
+            Method::Trace => "TRACE",
+            Method::Other(ref m) => m,
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = Error;
+
+    /// method names are case sensitive, empty names are invalid
+    fn from_str(s: &str) -> Result<Method, Error> {
+        let method = match s {
+            "GET" => Method::Get,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "HEAD" => Method::Head,
+            "OPTIONS" => Method::Options,
+            "CONNECT" => Method::Connect,
+            "PATCH" => Method::Patch,
+            "TRACE" => Method::Trace,
+            "" => return Err(Error::new(Reason::InvalidFormat)),
+            _ => Method::Other(String::from(s)),
+        };
+        Ok(method)
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// outcome of routing a request by method and path
+#[derive(Debug)]
+pub enum MethodMatch<'a, T> {
+    /// a route matched the path and has data for the method
+    Found(Match<'a, T>),
+    /// no route matched the path, the fallback of a prefix has data for the
+    /// method
+    Fallback(Match<'a, T>),
+    /// routes matched the path but not the method, `allowed` lists their
+    /// methods, the methods of a route in the order they were added, e.g. for
+    /// the `Allow` header of a 405 response
+    MethodNotAllowed { allowed: Vec<Method> },
+    /// no route matched the path, and no fallback has data for the method
+    NotFound,
+}
+
+/// A path router storing data per HTTP method on each route
+///
+/// `(method, path)` is resolved with a single lookup of the path
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Method, MethodRouter};
+/// let mut router = MethodRouter::default();
+/// router.get("/user/:id", 1).unwrap();
+/// router.post("/user", 2).unwrap();
+/// router.delete("/user/:id", 3).unwrap();
+///
+/// let m = router.recognize(&Method::Get, "/user/42").unwrap();
+/// assert_eq!((*m.data, m.params["id"].as_str()), (1, "42"));
+/// assert_eq!(*router.recognize(&Method::Delete, "/user/42").unwrap().data, 3);
+/// assert!(router.recognize(&Method::Post, "/user/42").is_none());
+/// ```
+pub struct MethodRouter<T> {
+    router: Router<Vec<(Method, T)>>,
+}
+
+impl<T> Default for MethodRouter<T> {
+    fn default() -> MethodRouter<T> {
+        MethodRouter {
+            router: Router::default(),
+        }
+    }
+}
+
+impl<T> MethodRouter<T> {
+    pub fn new() -> MethodRouter<T> {
+        MethodRouter::default()
+    }
+
+    /// a router with options, see `Router::with_options`
+    pub fn with_options(options: Options) -> MethodRouter<T> {
+        MethodRouter {
+            router: Router::with_options(options),
+        }
+    }
+
+    /// set a route with data for a method
+    ///
+    /// routes follow the rules of `Router::add`, setting the same method on a
+    /// route again replaces its data
+    pub fn add(&mut self, method: Method, route: &str, data: T) -> Result<&mut T, Error> {
+        let node = self.router.add_node(route, None)?;
+        Ok(set_slot(
+            node.data.get_or_insert_with(Vec::new),
+            method,
+            data,
+        ))
+    }
+
+    /// set the data of a method for paths which start with the prefix but
+    /// miss every route, see `Router::set_fallback`
+    pub fn set_fallback(&mut self, prefix: &str, method: Method, data: T) -> Result<&mut T, Error> {
+        let node = self.router.fallback_node(prefix)?;
+        Ok(set_slot(
+            node.fallback.get_or_insert_with(Vec::new),
+            method,
+            data,
+        ))
+    }
+
+    /// set a route with data for a method and information returned in its
+    /// matches, the information is shared by all methods of the route
+    pub fn add_with_info(
+        &mut self,
+        method: Method,
+        route: &str,
+        info: RouteInfo,
+        data: T,
+    ) -> Result<&mut T, Error> {
+        self.router.add_node(route, None)?.info = info;
+        self.add(method, route, data)
+    }
+
+    pub fn get(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Get, route, data)
+    }
+
+    pub fn post(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Post, route, data)
+    }
+
+    pub fn put(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Put, route, data)
+    }
+
+    pub fn delete(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Delete, route, data)
+    }
+
+    pub fn patch(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Patch, route, data)
+    }
+
+    pub fn head(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Head, route, data)
+    }
+
+    pub fn options(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        self.add(Method::Options, route, data)
+    }
+
+    /// recognize a path for a method, a fallback of the method takes the
+    /// paths every route misses
+    ///
+    /// use `route` to find out why a request isn't recognized
+    pub fn recognize<'a>(&'a self, method: &Method, path: &str) -> Option<Match<'a, &'a T>> {
+        match self.route(method, path) {
+            MethodMatch::Found(m) | MethodMatch::Fallback(m) => Some(m),
+            MethodMatch::MethodNotAllowed { .. } | MethodMatch::NotFound => None,
+        }
+    }
+
+    /// route a request, telling apart an unknown path from a known path
+    /// requested with a method it doesn't handle
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Method, MethodMatch, MethodRouter};
+    /// let mut router = MethodRouter::default();
+    /// router.get("/user/:id", 1).unwrap();
+    /// router.delete("/user/:id", 2).unwrap();
+    ///
+    /// match router.route(&Method::Post, "/user/42") {
+    ///     MethodMatch::MethodNotAllowed { allowed } => {
+    ///         assert_eq!(allowed, vec![Method::Get, Method::Delete])
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn route<'a>(&'a self, method: &Method, path: &str) -> MethodMatch<'a, &'a T> {
+        if let Some(m) = self.hit(method, path) {
+            return MethodMatch::Found(m);
+        }
+        let allowed = self.allowed(path);
+        if !allowed.is_empty() {
+            return MethodMatch::MethodNotAllowed { allowed };
+        }
+        let m = match self.router.resolve(path) {
+            Resolution::Fallback(m) => m,
+            _ => return MethodMatch::NotFound,
+        };
+        match m.data.iter().find(|(m, _)| m == method) {
+            Some((_, data)) => MethodMatch::Fallback(Match {
+                data,
+                params: m.params,
+                route: m.route,
+            }),
+            None => MethodMatch::NotFound,
+        }
+    }
+
+    // the first route taking the path with data for the method, a route
+    // without the method is a dead end the search goes back from
+    pub(crate) fn hit<'a>(&'a self, method: &Method, path: &str) -> Option<Match<'a, &'a T>> {
+        let router = &self.router;
+        let normalized = router.options.normalize_path(path).ok()?;
+        let has_method = |methods: &Vec<(Method, T)>| methods.iter().any(|(m, _)| m == method);
+        let mut trail = Trail::new(true);
+        trail.accept = Some(&has_method);
+        let found = router.find(&normalized, &mut trail)?;
+        let route = found.data_node()?;
+        if !matches!(
+            router.landing(path, &normalized, Some(route), false),
+            Landing::Route
+        ) {
+            return None;
+        }
+        // the nodes found live as long as the method, they are reached
+        // again by their steps, the data may be in the optional child
+        let optional = !core::ptr::eq(found, route);
+        let Trail { values, steps, .. } = trail;
+        let mut node = *router.nodes(&steps.unwrap_or_default()).last()?;
+        if optional {
+            node = node.optional_child()?;
+        }
+        let (_, data) = node.data.as_ref()?.iter().find(|(m, _)| m == method)?;
+        Some(Match {
+            data,
+            params: route_params(&node.params, &node.info, values),
+            route: &node.info,
+        })
+    }
+
+    // the methods of every route taking the path
+    pub(crate) fn allowed(&self, path: &str) -> Vec<Method> {
+        let mut allowed = vec![];
+        for m in self.router.recognize_all(path) {
+            for (method, _) in m.data.iter() {
+                if !allowed.contains(method) {
+                    allowed.push(method.clone());
+                }
+            }
+        }
+        allowed
+    }
+
+    /// route a request by its raw method and request target, like the
+    /// `:method` and `:path` pseudo-headers of HTTP/2 or an HTTP/1 request
+    /// line
+    ///
+    /// the query and the fragment of the target are ignored, an invalid
+    /// method or a target not starting with '/' is not found
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{MethodMatch, MethodRouter};
+    /// let mut router = MethodRouter::default();
+    /// router.get("/search", 1).unwrap();
+    ///
+    /// assert!(matches!(router.route_target("GET", "/search?q=a"), MethodMatch::Found(_)));
+    /// assert!(matches!(
+    ///     router.route_target("PUT", "/search"),
+    ///     MethodMatch::MethodNotAllowed { .. }
+    /// ));
+    /// ```
+    pub fn route_target<'a>(&'a self, method: &str, target: &str) -> MethodMatch<'a, &'a T> {
+        let method = match method.parse::<Method>() {
+            Ok(method) => method,
+            Err(_) => return MethodMatch::NotFound,
+        };
+        let path = match target.find(['?', '#']) {
+            Some(i) => &target[..i],
+            None => target,
+        };
+        if !path.starts_with('/') {
+            return MethodMatch::NotFound;
+        }
+        self.route(&method, path)
+    }
+
+    /// list routes with their methods in the order they were added
+    pub fn list_routes(&self) -> Vec<(Method, String)> {
+        let mut result = vec![];
+        for (route, node) in self.router.list_sub_nodes(&[]) {
+            if let Some(ref methods) = node.data {
+                for (method, _) in methods.iter() {
+                    result.push((method.clone(), route.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    /// the inner router keyed by path, holding the data of every method
+    pub fn router(&self) -> &Router<Vec<(Method, T)>> {
+        &self.router
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_method() {
+        assert_eq!("GET".parse::<Method>().unwrap(), Method::Get);
+        assert_eq!(
+            "PURGE".parse::<Method>().unwrap(),
+            Method::Other(String::from("PURGE"))
+        );
+        assert_eq!("get".parse::<Method>().unwrap().as_str(), "get");
+        assert!("".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn method_router() {
+        let mut router = MethodRouter::default();
+        router.get("/user/:id", 1).unwrap();
+        router.put("/user/:id", 2).unwrap();
+        router.get("/user/:id", 3).unwrap();
+        router.post("/user", 4).unwrap();
+        router
+            .add(Method::Other(String::from("PURGE")), "/cache/*key", 5)
+            .unwrap();
+        assert!(router.get("/user/:uid", 6).is_err());
+        assert!(router.get("/user/", 6).is_err());
+
+        let m = router.recognize(&Method::Get, "/user/42").unwrap();
+        assert_eq!(*m.data, 3);
+        assert_eq!(m.params["id"], "42");
+        assert_eq!(*router.recognize(&Method::Put, "/user/42").unwrap().data, 2);
+        assert_eq!(*router.recognize(&Method::Post, "/user").unwrap().data, 4);
+        let purge = "PURGE".parse().unwrap();
+        assert_eq!(*router.recognize(&purge, "/cache/a/b").unwrap().data, 5);
+        assert!(router.recognize(&Method::Get, "/user").is_none());
+        assert!(router.recognize(&Method::Get, "/users").is_none());
+
+        assert_eq!(
+            router.list_routes(),
+            vec![
+                (
+                    Method::Other(String::from("PURGE")),
+                    String::from("/cache/*key")
+                ),
+                (Method::Post, String::from("/user")),
+                (Method::Get, String::from("/user/:id")),
+                (Method::Put, String::from("/user/:id")),
+            ]
+        );
+    }
+
+    #[test]
+    fn method_not_allowed() {
+        let mut router = MethodRouter::default();
+        router.put("/user/:id", 1).unwrap();
+        router.get("/user/:id", 2).unwrap();
+
+        match router.route(&Method::Get, "/user/1") {
+            MethodMatch::Found(m) => assert_eq!(*m.data, 2),
+            other => panic!("{:?}", other),
+        }
+        match router.route(&Method::Head, "/user/1") {
+            MethodMatch::MethodNotAllowed { allowed } => {
+                assert_eq!(allowed, vec![Method::Put, Method::Get])
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            router.route(&Method::Get, "/users/1"),
+            MethodMatch::NotFound
+        ));
+
+        match router.route_target("GET", "/user/1?tab=posts#top") {
+            MethodMatch::Found(m) => assert_eq!(m.params["id"], "1"),
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            router.route_target("", "/user/1"),
+            MethodMatch::NotFound
+        ));
+        assert!(matches!(
+            router.route_target("OPTIONS", "*"),
+            MethodMatch::NotFound
+        ));
+    }
+
+    #[test]
+    fn method_backtracking() {
+        let mut router = MethodRouter::default();
+        router.get("/user/admin", 1).unwrap();
+        router.post("/user/:id", 2).unwrap();
+        router.delete("/user/:id", 3).unwrap();
+        router.set_fallback("/user", Method::Put, 4).unwrap();
+
+        // a route without the method goes on to the next candidate
+        match router.route(&Method::Post, "/user/admin") {
+            MethodMatch::Found(m) => assert_eq!((*m.data, &m.params["id"][..]), (2, "admin")),
+            other => panic!("{:?}", other),
+        }
+        match router.route(&Method::Delete, "/user/admin") {
+            MethodMatch::Found(m) => assert_eq!((*m.data, &m.params["id"][..]), (3, "admin")),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(
+            *router.recognize(&Method::Get, "/user/admin").unwrap().data,
+            1
+        );
+        assert!(matches!(
+            router.resolve(&Method::Post, "/user/admin"),
+            Resolution::Hit(_)
+        ));
+
+        // the methods of every candidate are allowed once all of them fail
+        match router.route(&Method::Patch, "/user/admin") {
+            MethodMatch::MethodNotAllowed { allowed } => {
+                assert_eq!(allowed, [Method::Get, Method::Post, Method::Delete])
+            }
+            other => panic!("{:?}", other),
+        }
+        match router.resolve(&Method::Put, "/user/bob") {
+            Resolution::MethodNotAllowed { allowed } => {
+                assert_eq!(allowed, [Method::Post, Method::Delete])
+            }
+            other => panic!("{:?}", other),
+        }
+
+        // a fallback isn't a route match
+        match router.route(&Method::Put, "/user") {
+            MethodMatch::Fallback(m) => assert_eq!(*m.data, 4),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(*router.recognize(&Method::Put, "/user").unwrap().data, 4);
+        assert!(matches!(
+            router.route(&Method::Get, "/user"),
+            MethodMatch::NotFound
+        ));
+    }
+}
@@ -0,0 +1,53 @@
+/// a sink for the metrics of a router
+///
+/// the crate reports through this trait only, so counts can go to statsd,
+/// OpenTelemetry or anything else without the crate depending on them;
+/// every operation does nothing by default, implement the ones the sink
+/// takes
+///
+/// # Example
+///
+/// ```
+/// use path_router::Recorder;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// #[derive(Default)]
+/// struct Hits(AtomicU64);
+///
+/// impl Recorder for Hits {
+///     fn counter(&self, name: &str, value: u64) {
+///         if name == "path_router.cache.hits" {
+///             self.0.fetch_add(value, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// let hits = Hits::default();
+/// hits.counter("path_router.cache.hits", 2);
+/// hits.histogram("ignored", 1.5);
+/// assert_eq!(hits.0.load(Ordering::Relaxed), 2);
+/// ```
+pub trait Recorder: Send + Sync {
+    /// add `value` to the counter `name`
+    fn counter(&self, name: &str, value: u64) {
+        let _ = (name, value);
+    }
+
+    /// set the gauge `name` to `value`
+    fn gauge(&self, name: &str, value: f64) {
+        let _ = (name, value);
+    }
+
+    /// record one observation of `value` in the histogram `name`
+    fn histogram(&self, name: &str, value: f64) {
+        let _ = (name, value);
+    }
+
+    /// a handler panicked while serving a request of `route`, `None` for the
+    /// handler of requests no route takes; adds 1 to the counter
+    /// `path_router.handler.panics` by default
+    fn handler_panicked(&self, route: Option<&str>) {
+        let _ = route;
+        self.counter("path_router.handler.panics", 1);
+    }
+}
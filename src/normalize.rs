@@ -0,0 +1,549 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::text::Text;
+use crate::{AmbiguityHook, RouteParser, SegmentMatcher, TieBreaker, Transform};
+use alloc::borrow::Cow;
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// options of a router
+///
+/// only the options of the router where adding or recognizing starts take
+/// effect, a sub route shares the options its router had when it was taken
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Options {
+    /// map fullwidth ASCII forms like `／` and `．` and the ideographic space
+    /// to their ASCII equivalents in routes and paths
+    pub fullwidth: bool,
+    /// percent-decode every segment before matching and capturing
+    pub percent_decode: PercentDecode,
+    /// order of static segments, set it before adding routes, a sub route
+    /// shares the collation of its parent; only the built in collations are
+    /// serialized, by name
+    pub collation: Collation,
+    /// warn about added routes making the routes beneath a prefix much more
+    /// ambiguous
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_ambiguity: Option<AmbiguityHook>,
+    /// pick the winner of routes tied for a path
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub tie_breaker: Option<TieBreaker>,
+    /// what a catchall ending a route does with the trailing slashes of the
+    /// path, `RouteInfo::catch_all_slash` overrides it per route
+    pub catch_all_slash: TrailingSlash,
+    /// longest rest of a path in bytes a catchall takes, as written before
+    /// decoding, a longer rest misses the route
+    pub max_catch_all: Option<usize>,
+    /// what recognizing does with the `.` and `..` segments of paths
+    pub dot_segments: DotSegments,
+    /// transformations of every segment of routes and paths after
+    /// percent-decoding, in order, set them before adding routes; the values
+    /// of parameters are caught transformed
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub segment_transforms: Vec<Transform>,
+    /// kinds of parameters written inline in routes like `:day<weekday>`,
+    /// see `SegmentMatcher`, register them before adding routes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub segment_matchers: Vec<Arc<dyn SegmentMatcher>>,
+    /// paths with a trailing slash miss the routes in every lookup, a route
+    /// taking the path without it makes `Router::resolve` and
+    /// `Router::try_recognize` hint the redirect with `Miss::TslRedirect`;
+    /// catchalls still take trailing slashes
+    pub strict_slash: bool,
+    /// a path missing every route which a route takes when the ASCII case
+    /// of static segments is ignored makes `Router::resolve` and
+    /// `Router::try_recognize` suggest the path cased like the route with
+    /// `Miss::CaseRedirect`, ahead of the fallbacks
+    pub case_redirect: bool,
+    /// what recognizing does with the encoded slashes `%2F` of paths
+    pub encoded_slash: EncodedSlash,
+    /// characters allowed in the names of parameters and catchalls, ASCII
+    /// letters, digits and `_` by default
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub name_chars: NameChars,
+    /// the syntax of route patterns, `ColonSyntax` when none, set it before
+    /// adding routes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub parser: Option<Arc<dyn RouteParser>>,
+}
+
+/// comparator ordering and searching the static segments of a router
+///
+/// segments comparing equal are the same segment, a case folding collation
+/// makes static segments case insensitive, the comparator must be a total
+/// order, the generic keys API always orders keys by `Ord`
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Collation, Router};
+/// let mut router = Router::default();
+/// router.options_mut().collation = Collation::ascii_case_insensitive();
+/// router.add("/Users/:id", 1).unwrap();
+/// assert!(router.add("/users/:uid", 2).is_err());
+///
+/// assert_eq!(*router.recognize("/USERS/42").unwrap().data, 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Collation {
+    name: &'static str,
+    compare: fn(&str, &str) -> Ordering,
+    bytewise: bool, // equal segments are equal bytes, they can be hashed
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    builtin: bool, // one of the collations below, serialized by its name
+}
+
+impl Collation {
+    /// a named collation, the name is only used by `Debug`
+    pub fn new(name: &'static str, compare: fn(&str, &str) -> Ordering) -> Collation {
+        Collation {
+            name,
+            compare,
+            bytewise: false,
+            builtin: false,
+        }
+    }
+
+    /// byte-wise order, the default
+    pub fn bytewise() -> Collation {
+        Collation {
+            bytewise: true,
+            builtin: true,
+            ..Collation::new("bytewise", |a, b| a.cmp(b))
+        }
+    }
+
+    /// byte-wise order with ASCII letters folded to lowercase
+    pub fn ascii_case_insensitive() -> Collation {
+        Collation {
+            builtin: true,
+            ..Collation::new("ascii_case_insensitive", |a, b| {
+                let a = a.bytes().map(|c| c.to_ascii_lowercase());
+                a.cmp(b.bytes().map(|c| c.to_ascii_lowercase()))
+            })
+        }
+    }
+
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        (self.compare)(a, b)
+    }
+
+    pub(crate) fn is_bytewise(&self) -> bool {
+        self.bytewise
+    }
+
+    pub(crate) fn for_keys(self) -> impl Fn(&String, &String) -> Ordering {
+        move |a, b| self.compare(a, b)
+    }
+}
+
+impl Default for Collation {
+    fn default() -> Collation {
+        Collation::bytewise()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Collation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.builtin {
+            let error = format!("collation {} isn't built in", self.name);
+            return Err(serde::ser::Error::custom(error));
+        }
+        serializer.serialize_str(self.name)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Collation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Collation, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match name.as_str() {
+            "bytewise" => Ok(Collation::bytewise()),
+            "ascii_case_insensitive" => Ok(Collation::ascii_case_insensitive()),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown collation {}",
+                name
+            ))),
+        }
+    }
+}
+
+impl fmt::Debug for Collation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Collation({})", self.name)
+    }
+}
+
+/// characters allowed in the names of parameters, a route naming a
+/// parameter with other characters is rejected with `Reason::InvalidName`
+///
+/// # Example
+///
+/// ```
+/// use path_router::{NameChars, Router};
+/// let mut router = Router::default();
+/// assert!(router.add("/users/:user-id", 1).is_err());
+///
+/// router.options_mut().name_chars = NameChars::new("kebab", |c| c.is_ascii_alphanumeric() || c == '-');
+/// router.add("/users/:user-id", 1).unwrap();
+/// assert_eq!(router.recognize("/users/7").unwrap().params["user-id"], "7");
+/// ```
+#[derive(Clone, Copy)]
+pub struct NameChars {
+    name: &'static str,
+    allows: fn(char) -> bool,
+}
+
+impl NameChars {
+    /// a named set of characters, the name is only used by `Debug`
+    pub fn new(name: &'static str, allows: fn(char) -> bool) -> NameChars {
+        NameChars { name, allows }
+    }
+
+    /// ASCII letters, digits and `_`, the default
+    pub fn word() -> NameChars {
+        NameChars::new("word", |c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// every character the syntax of routes leaves to names
+    pub fn any() -> NameChars {
+        NameChars::new("any", |_| true)
+    }
+
+    pub fn allows(&self, c: char) -> bool {
+        (self.allows)(c)
+    }
+}
+
+impl Default for NameChars {
+    fn default() -> NameChars {
+        NameChars::word()
+    }
+}
+
+impl fmt::Debug for NameChars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NameChars({})", self.name)
+    }
+}
+
+/// how to percent-decode the segments of routes and paths
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PercentDecode {
+    /// keep segments as they are
+    #[default]
+    Off,
+    /// decode segments, a segment with invalid escapes or decoding to
+    /// invalid UTF-8 is kept as it is
+    PassThrough,
+    /// decode segments, a path with invalid escapes misses and a route with
+    /// invalid escapes is rejected
+    Strict,
+}
+
+/// trailing slashes of the value of a catchall, like `x/y/z/`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TrailingSlash {
+    #[default]
+    Keep,
+    /// `x/y/z/` is caught as `x/y/z`
+    Strip,
+}
+
+/// what to do with the `.` and `..` segments of a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DotSegments {
+    /// match them like other segments
+    #[default]
+    Keep,
+    /// resolve them like RFC 3986 before matching, `/a/./b/../c` is `/a/c`
+    Resolve,
+    /// the path misses with `Miss::DotSegments`
+    Reject,
+}
+
+/// what to do with the encoded slashes `%2F` of a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EncodedSlash {
+    /// keep them in their segment, a parameter catches `a%2Fb` whole, as
+    /// `a/b` when segments are percent-decoded
+    #[default]
+    Data,
+    /// split segments at them like at `/`
+    Separator,
+    /// the path misses with `Miss::EncodedSlash`
+    Reject,
+}
+
+/// why a path misses, see `Router::try_recognize`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Miss {
+    /// no route takes the path
+    NotFound,
+    /// the path has `.` or `..` segments and `Options::dot_segments` rejects
+    /// them
+    DotSegments,
+    /// the path has encoded slashes and `Options::encoded_slash` rejects them
+    EncodedSlash,
+    /// the path has a trailing slash and `Options::strict_slash` is set, a
+    /// route takes the path without it, e.g. to answer a 308 redirect
+    TslRedirect(String),
+    /// the path differs from a route only by the ASCII case of its static
+    /// segments and `Options::case_redirect` is set, the path cased like the
+    /// route
+    CaseRedirect(String),
+}
+
+impl Options {
+    /// apply the enabled normalizations to a route or a path
+    pub fn normalize<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        if self.fullwidth {
+            normalize_fullwidth(path)
+        } else {
+            Cow::Borrowed(path)
+        }
+    }
+
+    // normalize a path to recognize, Err if it misses whatever the routes
+    pub(crate) fn normalize_path<'a, S: Text + ?Sized>(
+        &self,
+        path: &'a S,
+    ) -> Result<Cow<'a, S>, Miss> {
+        let mut path = match self.fullwidth {
+            true => path.fullwidth(),
+            false => Cow::Borrowed(path),
+        };
+        match self.encoded_slash {
+            EncodedSlash::Data => {}
+            _ if !has_encoded_slash(path.as_bytes()) => {}
+            EncodedSlash::Separator => {
+                path = Cow::Owned(S::from_segments(split_encoded_slashes(path.as_bytes())))
+            }
+            EncodedSlash::Reject => return Err(Miss::EncodedSlash),
+        }
+        match self.dot_segments {
+            DotSegments::Keep => Ok(path),
+            _ if !has_dot_segments(path.as_bytes()) => Ok(path),
+            DotSegments::Resolve => Ok(Cow::Owned(S::from_segments(resolve_dots(path.as_bytes())))),
+            DotSegments::Reject => Err(Miss::DotSegments),
+        }
+    }
+
+    // whether a catchall takes a rest of `len` bytes
+    pub(crate) fn takes_catch_all(&self, len: usize) -> bool {
+        self.max_catch_all.map_or(true, |max| len <= max)
+    }
+
+    // decode and transform a segment as configured, None if it must be
+    // rejected
+    pub(crate) fn decode<'a>(&self, segment: &'a str) -> Option<Cow<'a, str>> {
+        let decoded = match self.percent_decode {
+            PercentDecode::Off => Cow::Borrowed(segment),
+            PercentDecode::PassThrough => percent_decode(segment).unwrap_or(Cow::Borrowed(segment)),
+            PercentDecode::Strict => percent_decode(segment)?,
+        };
+        Some(self.transform(decoded))
+    }
+
+    // apply the segment transforms to a decoded segment
+    pub(crate) fn transform<'a>(&self, segment: Cow<'a, str>) -> Cow<'a, str> {
+        self.segment_transforms
+            .iter()
+            .fold(segment, |s, t| Cow::Owned(t.apply(&s)))
+    }
+}
+
+/// percent-decode a string
+///
+/// None if it has invalid escapes or decodes to invalid UTF-8
+///
+/// # Example
+///
+/// ```
+/// use path_router::percent_decode;
+/// assert_eq!(percent_decode("john%20doe").unwrap(), "john doe");
+/// assert_eq!(percent_decode("%E5%B1%B1").unwrap(), "山");
+/// assert!(percent_decode("100%").is_none());
+/// assert!(percent_decode("%ff").is_none());
+/// ```
+pub fn percent_decode(s: &str) -> Option<Cow<'_, str>> {
+    match percent_decode_bytes(s.as_bytes())? {
+        Cow::Borrowed(_) => Some(Cow::Borrowed(s)),
+        Cow::Owned(decoded) => String::from_utf8(decoded).ok().map(Cow::Owned),
+    }
+}
+
+// percent-decode bytes, None if they have invalid escapes
+pub(crate) fn percent_decode_bytes(s: &[u8]) -> Option<Cow<'_, [u8]>> {
+    if !s.contains(&b'%') {
+        return Some(Cow::Borrowed(s));
+    }
+
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut decoded = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] == b'%' {
+            let hi = hex(*s.get(i + 1)?)?;
+            let lo = hex(*s.get(i + 2)?)?;
+            decoded.push(hi << 4 | lo);
+            i += 3;
+        } else {
+            decoded.push(s[i]);
+            i += 1;
+        }
+    }
+    Some(Cow::Owned(decoded))
+}
+
+pub(crate) fn has_dot_segments(path: &[u8]) -> bool {
+    path.split(|&b| b == b'/').any(|s| dots(s) > 0)
+}
+
+// 1 for a `.` segment, 2 for a `..` one, their dots may be encoded as `%2E`
+// since they decode to the same segment, 0 for other segments
+fn dots(segment: &[u8]) -> usize {
+    let mut rest = segment;
+    let mut count = 0;
+    while !rest.is_empty() && count < 3 {
+        rest = match rest {
+            [b'.', rest @ ..] => rest,
+            [b'%', b'2', b'e' | b'E', rest @ ..] => rest,
+            _ => return 0,
+        };
+        count += 1;
+    }
+    match rest.is_empty() && count < 3 {
+        true => count,
+        false => 0,
+    }
+}
+
+fn has_encoded_slash(path: &[u8]) -> bool {
+    path.windows(3).any(|w| w.eq_ignore_ascii_case(b"%2f"))
+}
+
+// the path with its encoded slashes turned into '/'
+fn split_encoded_slashes(path: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        match path.get(i..i + 3) {
+            Some(w) if w.eq_ignore_ascii_case(b"%2f") => {
+                out.push(b'/');
+                i += 3;
+            }
+            _ => {
+                out.push(path[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// remove the `.` and `..` segments of a path starting with '/' like RFC 3986,
+/// a `..` past the root is dropped, dots encoded as `%2E` count as dots
+///
+/// # Example
+///
+/// ```
+/// use path_router::remove_dot_segments;
+/// assert_eq!(remove_dot_segments("/a/./b/../c"), "/a/c");
+/// assert_eq!(remove_dot_segments("/a/b/.."), "/a/");
+/// assert_eq!(remove_dot_segments("/../a"), "/a");
+/// assert_eq!(remove_dot_segments("/a/.b/..c"), "/a/.b/..c");
+/// assert_eq!(remove_dot_segments("/a/b/%2e%2E/c"), "/a/c");
+/// ```
+pub fn remove_dot_segments(path: &str) -> Cow<'_, str> {
+    match has_dot_segments(path.as_bytes()) {
+        true => Cow::Owned(str::from_segments(resolve_dots(path.as_bytes()))),
+        false => Cow::Borrowed(path),
+    }
+}
+
+// `remove_dot_segments` of bytes
+fn resolve_dots(path: &[u8]) -> Vec<u8> {
+    let segments = path.split(|&b| b == b'/').collect::<Vec<_>>();
+    let mut output: Vec<&[u8]> = vec![];
+    for (i, &segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match dots(segment) {
+            0 => output.push(segment),
+            n => {
+                // the first segment, empty before the leading '/', stays
+                if n == 2 && output.len() > 1 {
+                    output.pop();
+                }
+                if last {
+                    output.push(b"");
+                }
+            }
+        }
+    }
+    output.join(&b'/')
+}
+
+/// map fullwidth ASCII forms (U+FF01 to U+FF5E) and the ideographic space
+/// (U+3000) to their ASCII equivalents
+///
+/// # Example
+///
+/// ```
+/// use path_router::normalize_fullwidth;
+/// assert_eq!(normalize_fullwidth("／ｕｓｅｒ／４２．json"), "/user/42.json");
+/// assert_eq!(normalize_fullwidth("/用户/42"), "/用户/42");
+/// ```
+pub fn normalize_fullwidth(path: &str) -> Cow<'_, str> {
+    let fullwidth = |c: char| c == '\u{3000}' || ('\u{ff01}'..='\u{ff5e}').contains(&c);
+    if !path.chars().any(fullwidth) {
+        return Cow::Borrowed(path);
+    }
+
+    let path = path
+        .chars()
+        .map(|c| match c {
+            '\u{3000}' => ' ',
+            '\u{ff01}'..='\u{ff5e}' => core::char::from_u32(c as u32 - 0xfee0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    Cow::Owned(path)
+}
+
+// `normalize_fullwidth` of bytes, which may not be valid UTF-8
+pub(crate) fn normalize_fullwidth_bytes(path: &[u8]) -> Cow<'_, [u8]> {
+    let ascii = |c: &[u8]| match *c {
+        [0xe3, 0x80, 0x80] => Some(b' '),
+        [0xef, 0xbc, b @ 0x81..=0xbf] => Some(b - 0x60),
+        [0xef, 0xbd, b @ 0x80..=0x9e] => Some(b - 0x20),
+        _ => None,
+    };
+    if !path.windows(3).any(|w| ascii(w).is_some()) {
+        return Cow::Borrowed(path);
+    }
+
+    let mut out = Vec::with_capacity(path.len());
+    let mut i = 0;
+    while i < path.len() {
+        match path.get(i..i + 3).and_then(ascii) {
+            Some(b) => {
+                out.push(b);
+                i += 3;
+            }
+            None => {
+                out.push(path[i]);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(out)
+}
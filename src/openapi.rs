@@ -0,0 +1,308 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Constraint, Method, MethodRouter, ParamType, RouteInfo, Router, Segment};
+use core::fmt;
+
+// just enough JSON for the paths object
+#[derive(Clone)]
+enum Json {
+    Str(String),
+    Bool(bool),
+    Int(i64),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Str(s) => write_str(f, s),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Int(i) => write!(f, "{}", i),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+fn string(s: &str) -> Json {
+    Json::Str(String::from(s))
+}
+
+fn object(fields: Vec<(&str, Json)>) -> Json {
+    Json::Object(
+        fields
+            .into_iter()
+            .map(|(k, v)| (String::from(k), v))
+            .collect(),
+    )
+}
+
+fn param_schema(constraint: Option<&Constraint>) -> Json {
+    let ty = match constraint {
+        Some(Constraint::Type(ty)) => *ty,
+        _ => {
+            let mut schema = vec![("type", string("string"))];
+            if let Some(re) = constraint.and_then(Constraint::regex_source) {
+                schema.push(("pattern", string(&format!("^(?:{})$", re))));
+            }
+            return object(schema);
+        }
+    };
+    match ty {
+        ParamType::Bool => object(vec![("type", string("boolean"))]),
+        ParamType::Date => object(vec![("type", string("string")), ("format", string("date"))]),
+        ParamType::U8 | ParamType::U16 | ParamType::U32 | ParamType::U64 | ParamType::Usize => {
+            object(vec![("type", string("integer")), ("minimum", Json::Int(0))])
+        }
+        _ => object(vec![("type", string("integer"))]),
+    }
+}
+
+// the transformations of the value are the description
+fn path_parameter(name: &str, constraint: Option<&Constraint>, info: &RouteInfo) -> Json {
+    let mut parameter = vec![
+        ("name", string(name)),
+        ("in", string("path")),
+        ("required", Json::Bool(true)),
+        ("schema", param_schema(constraint)),
+    ];
+    let transforms = info
+        .transforms
+        .iter()
+        .filter(|(n, _)| n == name)
+        .map(|(_, t)| t.description())
+        .collect::<Vec<_>>();
+    if !transforms.is_empty() {
+        parameter.push(("description", string(&transforms.join(", "))));
+    }
+    object(parameter)
+}
+
+fn content(schema: &str) -> Json {
+    object(vec![(
+        "application/json",
+        object(vec![("schema", object(vec![("$ref", string(schema))]))]),
+    )])
+}
+
+fn operation(info: &RouteInfo) -> Json {
+    let mut op = vec![];
+    if let Some(ref summary) = info.summary {
+        op.push(("summary", string(summary)));
+    }
+    if !info.tags.is_empty() {
+        op.push((
+            "tags",
+            Json::Array(info.tags.iter().map(|t| string(t)).collect()),
+        ));
+    }
+    if let Some(ref schema) = info.request_schema {
+        op.push(("requestBody", object(vec![("content", content(schema))])));
+    }
+    let mut response = vec![("description", string("response"))];
+    if let Some(ref schema) = info.response_schema {
+        response.push(("content", content(schema)));
+    }
+    op.push(("responses", object(vec![("default", object(response))])));
+    object(op)
+}
+
+// path items of the routes, an optional trailing parameter gives a path
+// without and a path with the parameter
+fn path_items<T>(
+    router: &Router<T>,
+    operations: impl Fn(&Router<T>) -> Vec<(String, Json)>,
+) -> String {
+    let mut routes = vec![];
+    router.parsed_routes(vec![], &mut routes);
+    let mut paths = vec![];
+    for ((segments, optional), node) in routes {
+        let mut template = String::new();
+        let mut parameters = vec![];
+        let mut templates = vec![];
+        for (i, (segment, constraint)) in segments.iter().enumerate() {
+            if optional && i + 1 == segments.len() {
+                templates.push((template.clone(), parameters.len()));
+            }
+            template.push('/');
+            match segment {
+                Segment::Static(text) => template.push_str(text),
+                Segment::Param(name) | Segment::CatchAll(name) => match constraint {
+                    Some(Constraint::Template(t)) => {
+                        template.push_str(&t.render(|name| format!("{{{}}}", name)));
+                        for name in t.names() {
+                            parameters.push(path_parameter(name, None, &node.info));
+                        }
+                    }
+                    _ => {
+                        template.push_str(&format!("{{{}}}", name));
+                        parameters.push(path_parameter(name, constraint.as_ref(), &node.info));
+                    }
+                },
+            }
+        }
+        if template.is_empty() {
+            template.push('/');
+        }
+        templates.push((template, parameters.len()));
+
+        for (template, n) in templates {
+            let mut item = vec![];
+            if let Some(ref summary) = node.info.summary {
+                item.push((String::from("summary"), string(summary)));
+            }
+            if n > 0 {
+                item.push((
+                    String::from("parameters"),
+                    Json::Array(parameters[..n].to_vec()),
+                ));
+            }
+            item.extend(operations(node));
+            paths.push((template, Json::Object(item)));
+        }
+    }
+    Json::Object(paths).to_string()
+}
+
+impl<T> Router<T> {
+    /// export the routes as an OpenAPI paths object in JSON
+    ///
+    /// parameters and catchalls become templated segments like `{id}`, typed
+    /// and regex constrained parameters get a schema, a route with an optional
+    /// trailing parameter gives two paths, the route summary goes to the path
+    /// item, the router has no methods so the items have no operations, see
+    /// `MethodRouter::to_openapi_paths`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/users/:id<u32>", 1).unwrap();
+    ///
+    /// assert_eq!(
+    ///     router.to_openapi_paths(),
+    ///     r#"{"/users/{id}":{"parameters":[{"name":"id","in":"path","required":true,"schema":{"type":"integer","minimum":0}}]}}"#
+    /// );
+    /// ```
+    pub fn to_openapi_paths(&self) -> String {
+        path_items(self, |_| vec![])
+    }
+}
+
+impl<T> MethodRouter<T> {
+    /// export the routes as an OpenAPI paths object in JSON with an operation
+    /// per method
+    ///
+    /// operations carry the summary and the schema references of the route,
+    /// methods OpenAPI doesn't know like `CONNECT` are left out
+    pub fn to_openapi_paths(&self) -> String {
+        path_items(self.router(), |node| {
+            let methods = node.data.as_ref().map(|m| &m[..]).unwrap_or(&[]);
+            methods
+                .iter()
+                .filter(|(m, _)| !matches!(m, Method::Connect | Method::Other(_)))
+                .map(|(m, _)| (m.as_str().to_lowercase(), operation(&node.info)))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Transform;
+
+    #[test]
+    fn openapi_paths() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/files/*path", 1).unwrap();
+        router
+            .add_with_info(
+                "/posts/:day<yyyy-mm-dd>/:slug?",
+                RouteInfo {
+                    summary: Some(String::from("posts \"of\" a day")),
+                    transforms: vec![
+                        (String::from("slug"), Transform::Trim),
+                        (String::from("slug"), Transform::Lowercase),
+                    ],
+                    ..RouteInfo::default()
+                },
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            router.to_openapi_paths(),
+            concat!(
+                r#"{"/":{},"#,
+                r#""/files/{path}":{"parameters":[{"name":"path","in":"path","required":true,"schema":{"type":"string"}}]},"#,
+                r#""/posts/{day}":{"summary":"posts \"of\" a day","parameters":[{"name":"day","in":"path","required":true,"schema":{"type":"string","format":"date"}}]},"#,
+                r#""/posts/{day}/{slug}":{"summary":"posts \"of\" a day","parameters":[{"name":"day","in":"path","required":true,"schema":{"type":"string","format":"date"}},{"name":"slug","in":"path","required":true,"schema":{"type":"string"},"description":"trimmed, lowercased"}]}}"#,
+            )
+        );
+    }
+
+    #[test]
+    fn openapi_operations() {
+        let mut router = MethodRouter::default();
+        router.get("/users/:id<i64>", 1).unwrap();
+        router.delete("/users/:id<i64>", 2).unwrap();
+        router
+            .add(Method::Other(String::from("PURGE")), "/users/:id<i64>", 3)
+            .unwrap();
+        let info = RouteInfo {
+            request_schema: Some(String::from("#/NewUser")),
+            response_schema: Some(String::from("#/User")),
+            tags: vec![String::from("users"), String::from("admin")],
+            ..RouteInfo::default()
+        };
+        router
+            .add_with_info(Method::Post, "/users", info, 4)
+            .unwrap();
+        assert_eq!(
+            router.to_openapi_paths(),
+            concat!(
+                r##"{"/users":{"post":{"tags":["users","admin"],"requestBody":{"content":{"application/json":{"schema":{"$ref":"#/NewUser"}}}},"##,
+                r##""responses":{"default":{"description":"response","content":{"application/json":{"schema":{"$ref":"#/User"}}}}}}},"##,
+                r##""/users/{id}":{"parameters":[{"name":"id","in":"path","required":true,"schema":{"type":"integer"}}],"##,
+                r##""get":{"responses":{"default":{"description":"response"}}},"##,
+                r##""delete":{"responses":{"default":{"description":"response"}}}}}"##,
+            )
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{Constraint, Error, Options, Reason, Router, Segment};
+use alloc::sync::Arc;
+use rayon::prelude::*;
+
+// a parsed route with its position in the input
+type Parsed<T> = (usize, Vec<(Segment<String>, Option<Constraint>)>, bool, T);
+
+impl<T: Send> Router<T> {
+    /// build a router from many routes using all cores
+    ///
+    /// routes are parsed in parallel, routes sharing a first static segment
+    /// are built into one subtrie per thread and the subtries are grafted onto
+    /// the root, the result is the same as adding the routes one by one, on
+    /// errors the error of the first failing route is returned
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let routes = (0..1000).map(|i| (format!("/r{}/:id", i), i)).collect();
+    /// let router = Router::build_parallel(routes).unwrap();
+    ///
+    /// assert_eq!(*router.recognize("/r512/7").unwrap().data, 512);
+    /// ```
+    pub fn build_parallel(routes: Vec<(String, T)>) -> Result<Router<T>, Error> {
+        Router::build_parallel_with_options(Options::default(), routes)
+    }
+
+    /// build a router with options from many routes using all cores
+    pub fn build_parallel_with_options(
+        options: Options,
+        routes: Vec<(String, T)>,
+    ) -> Result<Router<T>, Error> {
+        // parsing only reads the options, a router without data can be shared
+        let options = Arc::new(options);
+        let parser = Router::<()>::empty(Arc::clone(&options));
+        let (routes, data): (Vec<_>, Vec<_>) = routes.into_iter().unzip();
+        let parsed = routes
+            .par_iter()
+            .map(|route| parser.parse_route(route, None))
+            .collect::<Vec<_>>();
+
+        // groups are sorted by the collation of their first segment
+        let collation = options.collation;
+        let mut shared = vec![];
+        let mut groups = Vec::<(String, Vec<Parsed<T>>)>::new();
+        let mut first_error = None;
+        for (i, (parsed, data)) in parsed.into_iter().zip(data).enumerate() {
+            match parsed {
+                Ok((segments, optional)) => match segments.first() {
+                    Some((Segment::Static(key), _)) => {
+                        let idx = match groups.binary_search_by(|g| collation.compare(&g.0, key)) {
+                            Ok(idx) => idx,
+                            Err(idx) => {
+                                groups.insert(idx, (key.clone(), vec![]));
+                                idx
+                            }
+                        };
+                        groups[idx].1.push((i, segments, optional, data));
+                    }
+                    _ => shared.push((i, segments, optional, data)),
+                },
+                Err(e) => {
+                    first_error = Some((i, e));
+                    break;
+                }
+            }
+        }
+
+        // name the routes of a conflict of route `i` with the trie
+        let name = |trie: &Router<T>, i: usize, e: Error| match (
+            e,
+            parser.parse_route(&routes[i], None),
+        ) {
+            (
+                Error {
+                    reason: Reason::Conflict { .. },
+                    segment: Some(segment),
+                    ..
+                },
+                Ok(parsed),
+            ) => trie.named_conflict(&routes[i], &parsed, segment),
+            (e, _) => e,
+        };
+
+        let subtries = groups
+            .into_par_iter()
+            .map(|(_, group)| {
+                let mut trie = Router::empty(Arc::clone(&options));
+                for (i, segments, optional, data) in group {
+                    if let Err(e) = trie.insert(segments, optional, data, &collation.for_keys()) {
+                        return Err((i, name(&trie, i, e)));
+                    }
+                }
+                Ok(trie.static_children.pop().expect("route of the group"))
+            })
+            .collect::<Vec<Result<Router<T>, (usize, Error)>>>();
+
+        let mut router = Router::empty(options);
+        for (i, segments, optional, data) in shared {
+            if let Err(e) = router.insert(segments, optional, data, &collation.for_keys()) {
+                first_error = earliest(first_error, (i, name(&router, i, e)));
+                break;
+            }
+        }
+        for subtrie in subtries {
+            match subtrie {
+                Ok(node) => router.static_children.push(node),
+                Err(e) => first_error = earliest(first_error, e),
+            }
+        }
+        match first_error {
+            Some((_, e)) => Err(e),
+            None => Ok(router),
+        }
+    }
+}
+
+fn earliest(error: Option<(usize, Error)>, other: (usize, Error)) -> Option<(usize, Error)> {
+    match error {
+        Some(e) if e.0 < other.0 => Some(e),
+        _ => Some(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collation;
+
+    #[test]
+    fn build_parallel() {
+        let routes = [
+            "/",
+            "/a/b",
+            "/a/:x",
+            "/a/b",
+            "/b/c/*rest",
+            "/c/d/e",
+            "/c/d/f",
+            "/:page/:id?",
+        ];
+        let mut single = Router::default();
+        for (i, route) in routes.iter().enumerate() {
+            single.add(route, i).unwrap();
+        }
+        let router = Router::build_parallel(
+            routes
+                .iter()
+                .enumerate()
+                .map(|(i, r)| (r.to_string(), i))
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(router.list_routes(), single.list_routes());
+        for path in ["/", "/a/b", "/a/z", "/b/c/d", "/c/d/f", "/x", "/x/1"] {
+            assert_eq!(
+                router.recognize(path).map(|m| (*m.data, m.params)),
+                single.recognize(path).map(|m| (*m.data, m.params)),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn build_parallel_options() {
+        let options = Options {
+            collation: Collation::ascii_case_insensitive(),
+            ..Options::default()
+        };
+        let routes = vec![(String::from("/users/me"), 1), (String::from("/:page"), 2)];
+        let router = Router::build_parallel_with_options(options, routes).unwrap();
+        assert_eq!(*router.recognize("/USERS/Me").unwrap().data, 1);
+        // the subtries share the options of the root
+        let users = &router.static_children[0];
+        assert!(Arc::ptr_eq(&users.options, &router.options));
+    }
+
+    #[test]
+    fn build_parallel_errors() {
+        let routes = |rs: &[&str]| rs.iter().map(|r| (r.to_string(), 0)).collect();
+        assert_eq!(
+            Router::build_parallel(routes(&["/a/:x", "/b/", "/a/:y"])).err(),
+            Some(Error {
+                route: String::from("/b/"),
+                segment: None,
+                reason: Reason::TrailingSlash,
+            })
+        );
+        let conflict = |route: &str, existing: &str, segment| {
+            let existing = String::from(existing);
+            Some(
+                Error::new(Reason::Conflict { existing })
+                    .at(segment)
+                    .of(route),
+            )
+        };
+        assert_eq!(
+            Router::build_parallel(routes(&["/a/:x", "/a/:y", "/b/"])).err(),
+            conflict("/a/:y", "/a/:x", 1)
+        );
+        assert_eq!(
+            Router::build_parallel(routes(&["/:x", "/*y", "/a/"])).err(),
+            conflict("/*y", "/:x", 0)
+        );
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::iter::FromIterator;
+use core::ops::Index;
+use core::slice;
+use smallvec::SmallVec;
+
+/// parameters extracted from a path, in the order of the route
+///
+/// up to 4 parameters are held inline, the names are shared with the route
+/// # Example
+///
+/// ```
+/// use path_router::Router;
+/// let mut router = Router::default();
+/// router.add("/repos/:owner/:repo", 1).unwrap();
+///
+/// let params = router.recognize("/repos/rust-lang/cargo").unwrap().params;
+/// assert_eq!(params.get("repo"), Some("cargo"));
+/// assert_eq!(params.get_index(0), Some("rust-lang"));
+/// assert_eq!(params["owner"], "rust-lang");
+/// assert_eq!(params.iter().collect::<Vec<_>>(), [("owner", "rust-lang"), ("repo", "cargo")]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    pairs: SmallVec<[(Arc<str>, String); 4]>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    /// value of the named parameter, the last one if the name repeats
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .rev()
+            .find(|(k, _)| **k == *name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// value of the parameter at the position in the route
+    pub fn get_index(&self, idx: usize) -> Option<&str> {
+        self.pairs.get(idx).map(|(_, v)| v.as_str())
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.pairs.iter().any(|(k, _)| **k == *name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// names and values in route order
+    pub fn iter(&self) -> ParamsIter<'_> {
+        ParamsIter {
+            inner: self.pairs.iter(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, name: Arc<str>, value: String) {
+        self.pairs.push((name, value));
+    }
+}
+
+impl Index<&str> for Params {
+    type Output = String;
+
+    /// panics if the parameter is missing
+    fn index(&self, name: &str) -> &String {
+        match self.pairs.iter().rev().find(|(k, _)| **k == *name) {
+            Some((_, value)) => value,
+            None => panic!("no parameter {:?}", name),
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Params {
+        Params {
+            pairs: iter.into_iter().map(|(k, v)| (Arc::from(k), v)).collect(),
+        }
+    }
+}
+
+impl IntoIterator for Params {
+    type Item = (String, String);
+    type IntoIter = alloc::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let pairs = self.pairs.into_iter().map(|(k, v)| (String::from(&*k), v));
+        pairs.collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Params {
+    type Item = (&'a str, &'a str);
+    type IntoIter = ParamsIter<'a>;
+
+    fn into_iter(self) -> ParamsIter<'a> {
+        self.iter()
+    }
+}
+
+impl From<Params> for BTreeMap<String, String> {
+    fn from(params: Params) -> BTreeMap<String, String> {
+        params.into_iter().collect()
+    }
+}
+
+/// iterator over the parameters in route order
+#[derive(Debug, Clone)]
+pub struct ParamsIter<'a> {
+    inner: slice::Iter<'a, (Arc<str>, String)>,
+}
+
+impl<'a> Iterator for ParamsIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<(&'a str, &'a str)> {
+        self.inner.next().map(|(k, v)| (&**k, v.as_str()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl ExactSizeIterator for ParamsIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let mut params = Params::new();
+        assert!(params.is_empty());
+        params.push("a".into(), "1".into());
+        params.push("b".into(), "2".into());
+        params.push("a".into(), "3".into());
+        assert_eq!(
+            (params.len(), params.get("a"), params.get("c")),
+            (3, Some("3"), None)
+        );
+        assert_eq!(
+            (params.get_index(1), params.get_index(3)),
+            (Some("2"), None)
+        );
+        assert_eq!(params.iter().map(|(k, _)| k).collect::<String>(), "aba");
+        let map = BTreeMap::from(params);
+        assert_eq!(map["a"], "3");
+    }
+
+    #[test]
+    fn shared_names() {
+        let mut router = crate::Router::default();
+        router.add("/repos/:owner/:repo", 1).unwrap();
+        let a = router.recognize("/repos/a/b").unwrap().params;
+        let b = router.recognize("/repos/c/d").unwrap().params;
+        assert!(!a.pairs.spilled());
+        assert!(Arc::ptr_eq(&a.pairs[1].0, &b.pairs[1].0));
+        assert_eq!(
+            a.into_iter().last(),
+            Some((String::from("repo"), String::from("b")))
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{Match, Resolution, Router};
+
+impl<T> Router<T> {
+    /// recognize the longest prefix of a path taken by a route, ending at a
+    /// segment boundary, with the rest of the path
+    ///
+    /// the rest starts at its `/` and is empty when the route takes the whole
+    /// path; the root route `/` leaves the whole path as the rest; fallbacks
+    /// aren't routes, so they're never a prefix; every prefix is looked up
+    /// like `recognize`, from the longest, so a miss costs a lookup per
+    /// segment
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/api", 1).unwrap();
+    /// router.add("/api/:version", 2).unwrap();
+    /// router.add("/", 0).unwrap();
+    ///
+    /// let (m, rest) = router.recognize_prefix("/api/v2/users/7").unwrap();
+    /// assert_eq!((*m.data, &m.params["version"][..], rest), (2, "v2", "/users/7"));
+    /// let (m, rest) = router.recognize_prefix("/about").unwrap();
+    /// assert_eq!((*m.data, rest), (0, "/about"));
+    /// ```
+    pub fn recognize_prefix<'a, 'p>(
+        &'a self,
+        path: &'p str,
+    ) -> Option<(Match<'a, &'a T>, &'p str)> {
+        let cuts = path.rmatch_indices('/').map(|(i, _)| i).filter(|&i| i > 0);
+        for end in Some(path.len()).into_iter().chain(cuts) {
+            if let Resolution::Hit(m) = self.resolve(&path[..end]) {
+                return Some((m, &path[end..]));
+            }
+        }
+        match path.starts_with('/') {
+            true => match self.resolve("/") {
+                Resolution::Hit(m) => Some((m, path)),
+                _ => None,
+            },
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefix<'p>(router: &Router<usize>, path: &'p str) -> Option<(usize, &'p str)> {
+        router
+            .recognize_prefix(path)
+            .map(|(m, rest)| (*m.data, rest))
+    }
+
+    #[test]
+    fn longest_prefix() {
+        let mut router = Router::default();
+        router.add("/a/b", 1).unwrap();
+        router.add("/a/b/c/:id<u32>", 2).unwrap();
+        router.add("/files/*path", 3).unwrap();
+        router.set_fallback("/x", 4).unwrap();
+
+        assert_eq!(prefix(&router, "/a/b"), Some((1, "")));
+        assert_eq!(prefix(&router, "/a/b/"), Some((1, "")));
+        assert_eq!(prefix(&router, "/a/b/c/7/d"), Some((2, "/d")));
+        assert_eq!(prefix(&router, "/a/b/c/x/d"), Some((1, "/c/x/d")));
+        assert_eq!(prefix(&router, "/files/a/b"), Some((3, "")));
+        assert_eq!(prefix(&router, "/a"), None);
+        assert_eq!(prefix(&router, "/x/y"), None);
+        assert_eq!(prefix(&router, ""), None);
+
+        router.add("/", 0).unwrap();
+        assert_eq!(prefix(&router, "/x/y"), Some((0, "/x/y")));
+        assert_eq!(prefix(&router, "/"), Some((0, "")));
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Collation, Constraint, Error, MountError, Reason, Router, Segment};
+use alloc::sync::Arc;
+
+impl<T> Router<T> {
+    /// the router with a prefix written before every route, like mounting
+    /// it under the prefix of an empty router with the same options
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/users/:id", 1).unwrap();
+    ///
+    /// let router = router.with_prefix("/v2").unwrap();
+    /// assert_eq!(router.list_routes(), ["/v2/users/:id"]);
+    /// ```
+    pub fn with_prefix(self, prefix: &str) -> Result<Router<T>, MountError<T>> {
+        let mut router = Router::empty(Arc::clone(&self.options));
+        router.mount(prefix, self)?;
+        Ok(router)
+    }
+
+    /// the router with a prefix taken off its routes
+    ///
+    /// a route is under the prefix if it starts with its segments, parameters
+    /// match parameters of the same constraint whatever their name, and the
+    /// names of the prefix leave the parameters; routes and fallbacks not
+    /// under the prefix, or only partly like an optional parameter ending at
+    /// the prefix, are left out
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/v1/users/:id", 1).unwrap();
+    /// router.add("/v1", 2).unwrap();
+    /// router.add("/health", 3).unwrap();
+    ///
+    /// let router = router.strip_prefix("/v1").unwrap().with_prefix("/v2").unwrap();
+    /// assert_eq!(router.list_routes(), ["/v2", "/v2/users/:id"]);
+    /// ```
+    pub fn strip_prefix(self, prefix: &str) -> Result<Router<T>, MountError<T>> {
+        let (segments, optional) = self.parse_route(prefix, None).map_err(MountError::Prefix)?;
+        if optional {
+            let error = Error::new(Reason::MisplacedOptional).of(prefix);
+            return Err(MountError::Prefix(error));
+        }
+        let prefix = segments;
+        let collation = self.options.collation;
+        let mut router = Router::empty(Arc::clone(&self.options));
+        let (mut routes, mut fallbacks) = (vec![], vec![]);
+        self.into_routes(vec![], &mut routes, &mut fallbacks);
+        routes.retain(|((segments, optional), _, _)| {
+            segments.len() >= prefix.len() + *optional as usize
+                && starts_with(segments, &prefix, collation)
+        });
+        fallbacks.retain(|(segments, _)| starts_with(segments, &prefix, collation));
+        let all = routes.iter_mut().map(|((segments, _), _, _)| segments);
+        for segments in all.chain(fallbacks.iter_mut().map(|(segments, _)| segments)) {
+            segments.drain(..prefix.len());
+        }
+        router
+            .graft(routes, fallbacks)
+            .map_err(MountError::Conflicts)?;
+        Ok(router)
+    }
+}
+
+// whether the segments of a route start with the segments of a prefix,
+// parameters are matched by constraint whatever their names
+pub(crate) fn starts_with(
+    route: &[(Segment<String>, Option<Constraint>)],
+    prefix: &[(Segment<String>, Option<Constraint>)],
+    collation: Collation,
+) -> bool {
+    route.len() >= prefix.len()
+        && route.iter().zip(prefix.iter()).all(|(a, b)| match (a, b) {
+            ((Segment::Static(a), _), (Segment::Static(b), _)) => collation.compare(a, b).is_eq(),
+            ((Segment::Param(_), a), (Segment::Param(_), b)) => a == b,
+            ((Segment::CatchAll(_), _), (Segment::CatchAll(_), _)) => true,
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    #[test]
+    fn rebase() {
+        let mut router = Router::default();
+        router.add("/v1", 0).unwrap();
+        router.add("/v1/:tenant/users/:id?", 1).unwrap();
+        router.add("/v1/:tenant/files/*path", 2).unwrap();
+        router.add("/v1/:t<u32>/stats", 3).unwrap();
+        router.add("/V1/about", 4).unwrap();
+        router.add("/health", 5).unwrap();
+
+        let v2 = router.clone().with_prefix("/v2").unwrap();
+        assert_eq!(
+            v2.list_routes(),
+            [
+                "/v2/V1/about",
+                "/v2/health",
+                "/v2/v1",
+                "/v2/v1/:t<u32>/stats",
+                "/v2/v1/:tenant/files/*path",
+                "/v2/v1/:tenant/users/:id?",
+            ]
+        );
+        assert_eq!(*v2.recognize("/v2/v1/a/users").unwrap().data, 1);
+
+        let stripped = router.clone().strip_prefix("/v1").unwrap();
+        assert_eq!(
+            stripped.list_routes(),
+            [
+                "/",
+                "/:t<u32>/stats",
+                "/:tenant/files/*path",
+                "/:tenant/users/:id?"
+            ]
+        );
+        let m = stripped.recognize("/acme/files/a/b").unwrap();
+        assert_eq!((*m.data, &m.params["tenant"][..]), (2, "acme"));
+
+        let stripped = router.clone().strip_prefix("/v1/:x/users").unwrap();
+        assert_eq!(stripped.list_routes(), ["/:id?"]);
+        assert_eq!(stripped.recognize("/7").unwrap().params["id"], "7");
+        let stripped = router.clone().strip_prefix("/v1/:x/users/:y").unwrap();
+        assert!(stripped.list_routes().is_empty());
+        let stripped = router.clone().strip_prefix("/v1/:n<u32>").unwrap();
+        assert_eq!(stripped.list_routes(), ["/stats"]);
+
+        assert!(matches!(
+            router.strip_prefix("/v1/:x?"),
+            Err(MountError::Prefix(Error {
+                reason: Reason::MisplacedOptional,
+                ..
+            }))
+        ));
+
+        let mut router = Router::with_options(Options {
+            collation: Collation::ascii_case_insensitive(),
+            ..Options::default()
+        });
+        router.add("/API/users", 1).unwrap();
+        let stripped = router.strip_prefix("/api").unwrap();
+        assert_eq!(*stripped.recognize("/USERS").unwrap().data, 1);
+    }
+}
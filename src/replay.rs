@@ -0,0 +1,181 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Match, Reason, Router};
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// the routes paths resolved to, recorded with `Router::record` and checked
+/// against another router with `Router::replay`
+///
+/// every distinct path is kept once with the route of its first resolution,
+/// `None` for a miss or a fallback; the log renders to a line per path, the
+/// path and the route separated by a tab, and parses back
+///
+/// # Example
+///
+/// ```
+/// use path_router::{DecisionLog, Router};
+/// let mut old = Router::default();
+/// old.add("/user/:id", 1).unwrap();
+/// let mut log = DecisionLog::new();
+/// old.record(&mut log, "/user/1");
+/// old.record(&mut log, "/user/me");
+/// old.record(&mut log, "/users");
+///
+/// let log: DecisionLog = log.to_string().parse().unwrap();
+/// let mut new = Router::default();
+/// new.add("/user/:id", 1).unwrap();
+/// new.add("/user/me", 2).unwrap();
+/// let divergence = &new.replay(&log)[0];
+/// assert_eq!(divergence.path, "/user/me");
+/// assert_eq!(divergence.recorded.as_deref(), Some("/user/:id"));
+/// assert_eq!(divergence.replayed.as_deref(), Some("/user/me"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecisionLog {
+    decisions: BTreeMap<String, Option<String>>,
+}
+
+/// a path resolving to another route on replay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub path: String,
+    pub recorded: Option<String>,
+    pub replayed: Option<String>,
+}
+
+impl DecisionLog {
+    pub fn new() -> DecisionLog {
+        DecisionLog::default()
+    }
+
+    /// number of recorded paths
+    pub fn len(&self) -> usize {
+        self.decisions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    /// the recorded paths in sorted order with their routes
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.decisions
+            .iter()
+            .map(|(path, route)| (path.as_str(), route.as_deref()))
+    }
+}
+
+impl fmt::Display for DecisionLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (path, route) in self.iter() {
+            match route {
+                Some(route) => writeln!(f, "{}\t{}", path, route)?,
+                None => writeln!(f, "{}", path)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for DecisionLog {
+    type Err = Error;
+
+    /// parse a rendered log, a path not starting with '/' or recorded twice
+    /// is an error of `Reason::InvalidFormat` naming the path
+    fn from_str(log: &str) -> Result<DecisionLog, Error> {
+        let mut decisions = BTreeMap::new();
+        for line in log.lines().filter(|l| !l.is_empty()) {
+            let (path, route) = match line.split_once('\t') {
+                Some((path, route)) => (path, Some(String::from(route))),
+                None => (line, None),
+            };
+            if !path.starts_with('/') || decisions.insert(String::from(path), route).is_some() {
+                return Err(Error::new(Reason::InvalidFormat).of(path));
+            }
+        }
+        Ok(DecisionLog { decisions })
+    }
+}
+
+impl<T> Router<T> {
+    /// recognize a path like `recognize` and record its route in `log`
+    ///
+    /// a path new to the log is resolved to its route by walking the routes,
+    /// a path seen before costs a lookup in the log
+    pub fn record<'a>(&'a self, log: &mut DecisionLog, path: &str) -> Option<Match<'a, &'a T>> {
+        if !log.decisions.contains_key(path) {
+            log.decisions
+                .insert(String::from(path), self.matched_route(path));
+        }
+        self.recognize(path)
+    }
+
+    /// the recorded paths resolving to another route than in `log`, in the
+    /// order of the log
+    pub fn replay(&self, log: &DecisionLog) -> Vec<Divergence> {
+        log.iter()
+            .filter_map(|(path, recorded)| {
+                let replayed = self.matched_route(path);
+                match recorded == replayed.as_deref() {
+                    true => None,
+                    false => Some(Divergence {
+                        path: String::from(path),
+                        recorded: recorded.map(String::from),
+                        replayed,
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_replay() {
+        let mut router = Router::default();
+        router.add("/repos/:owner/:name", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+        router.set_fallback("/api", 0).unwrap();
+
+        let mut log = DecisionLog::new();
+        for path in ["/repos/a/b", "/files/x/y", "/api/v1", "/repos/a/b", "/gone"] {
+            router.record(&mut log, path);
+        }
+        assert_eq!(log.len(), 4);
+        let text = log.to_string();
+        assert_eq!(
+            text,
+            "/api/v1\n/files/x/y\t/files/*path\n/gone\n/repos/a/b\t/repos/:owner/:name\n"
+        );
+        assert_eq!(text.parse::<DecisionLog>().unwrap(), log);
+        assert!(router.replay(&log).is_empty());
+
+        let mut next = Router::default();
+        next.add("/repos/:owner/:name", 1).unwrap();
+        next.add("/api/v1", 3).unwrap();
+        assert_eq!(
+            next.replay(&log),
+            [
+                Divergence {
+                    path: String::from("/api/v1"),
+                    recorded: None,
+                    replayed: Some(String::from("/api/v1")),
+                },
+                Divergence {
+                    path: String::from("/files/x/y"),
+                    recorded: Some(String::from("/files/*path")),
+                    replayed: None,
+                },
+            ]
+        );
+
+        for bad in ["user\t/user", "/a\n/a\t/a"] {
+            let err = bad.parse::<DecisionLog>().unwrap_err();
+            assert_eq!(err.reason, Reason::InvalidFormat);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Lint, Manifest, MethodRouter, Router};
+use alloc::collections::BTreeMap;
+use core::fmt::Write;
+
+// the lines of a report, `methods` is left out for routers without methods
+fn report(manifest: &Manifest, methods: Option<BTreeMap<String, usize>>, lints: &[Lint]) -> String {
+    let mut report = String::new();
+    let counts = |pairs: &mut dyn Iterator<Item = (String, usize)>| {
+        let counts = pairs
+            .map(|(name, n)| format!("{} {}", name, n))
+            .collect::<Vec<_>>();
+        match counts.is_empty() {
+            true => String::from("none"),
+            false => counts.join(", "),
+        }
+    };
+    let _ = writeln!(report, "routes: {}", manifest.len());
+    let mut scopes = manifest.scopes().map(|(s, n)| (format!("/{}", s), n));
+    let _ = writeln!(report, "scopes: {}", counts(&mut scopes));
+    if let Some(methods) = methods {
+        let _ = writeln!(report, "methods: {}", counts(&mut methods.into_iter()));
+    }
+    let _ = writeln!(report, "warnings: {}", lints.len());
+    for lint in lints {
+        let _ = writeln!(report, "  {}", lint);
+    }
+    report
+}
+
+impl<T> Router<T> {
+    /// a short summary of the route table to log once at startup, the number
+    /// of routes, their count per scope like `Manifest` and the warnings of
+    /// `lint`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/api/users/:id<u32>", 1).unwrap();
+    /// router.add("/api/users/:n<u8>", 2).unwrap();
+    /// router.add("/health", 3).unwrap();
+    ///
+    /// assert_eq!(
+    ///     router.startup_report(),
+    ///     "routes: 3\n\
+    ///      scopes: /api 2, /health 1\n\
+    ///      warnings: 1\n  \
+    ///      /api/users/:n<u8>: unreachable, shadowed by /api/users/:id<u32>\n"
+    /// );
+    /// ```
+    pub fn startup_report(&self) -> String {
+        report(&self.manifest(), None, &self.lint())
+    }
+}
+
+impl<T> MethodRouter<T> {
+    /// a short summary of the route table to log once at startup like
+    /// `Router::startup_report`, with the number of routes per method
+    pub fn startup_report(&self) -> String {
+        let mut methods = BTreeMap::new();
+        for (method, _) in self.list_routes() {
+            *methods.entry(method.to_string()).or_insert(0) += 1;
+        }
+        report(&self.manifest(), Some(methods), &self.router().lint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MethodRouter, Router};
+
+    #[test]
+    fn startup_reports() {
+        assert_eq!(
+            Router::<()>::default().startup_report(),
+            "routes: 0\nscopes: none\nwarnings: 0\n"
+        );
+
+        let mut router = MethodRouter::default();
+        router.get("/", 0).unwrap();
+        router.get("/users/:id", 1).unwrap();
+        router.put("/users/:id", 2).unwrap();
+        router.post("/users", 3).unwrap();
+        assert_eq!(
+            router.startup_report(),
+            "routes: 4\nscopes: / 1, /users 3\nmethods: GET 2, POST 1, PUT 1\nwarnings: 0\n"
+        );
+    }
+}
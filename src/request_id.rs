@@ -0,0 +1,115 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Error, Match, Params, Reason};
+
+// the pieces of an id template, literal text and the names in braces
+fn pieces(template: &str) -> Result<Vec<(&str, bool)>, Error> {
+    let mut pieces = vec![];
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| Error::new(Reason::InvalidFormat))?
+            + open;
+        let name = &rest[open + 1..close];
+        if name.is_empty() || name.contains('{') {
+            return Err(Error::new(Reason::InvalidFormat));
+        }
+        pieces.push((&rest[..open], false));
+        pieces.push((name, true));
+        rest = &rest[close + 1..];
+    }
+    if rest.contains('}') {
+        return Err(Error::new(Reason::InvalidFormat));
+    }
+    pieces.push((rest, false));
+    Ok(pieces)
+}
+
+// check an id template only names parameters of its route
+pub(crate) fn check_id_template<'n, I>(template: &str, names: I) -> Result<(), Error>
+where
+    I: Iterator<Item = &'n str> + Clone,
+{
+    for (piece, is_name) in pieces(template)? {
+        if is_name && !names.clone().any(|n| n == piece) {
+            return Err(Error::new(Reason::InvalidFormat));
+        }
+    }
+    Ok(())
+}
+
+fn render(template: &str, params: &Params) -> Option<String> {
+    let mut id = String::new();
+    for (piece, is_name) in pieces(template).ok()? {
+        match is_name {
+            true => id.push_str(params.get(piece)?),
+            false => id.push_str(piece),
+        }
+    }
+    Some(id)
+}
+
+impl<T> Match<'_, T> {
+    /// the id of the request rendered from `RouteInfo::id_template` with the
+    /// parameters of the match, `None` if the route has no template
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{RouteInfo, Router};
+    /// let mut router = Router::default();
+    /// let info = RouteInfo {
+    ///     id_template: Some(String::from("user-{id}-repos")),
+    ///     ..RouteInfo::default()
+    /// };
+    /// router.add_with_info("/users/:id/repos", info, 1).unwrap();
+    ///
+    /// let m = router.recognize("/users/42/repos").unwrap();
+    /// assert_eq!(m.request_id().as_deref(), Some("user-42-repos"));
+    /// ```
+    pub fn request_id(&self) -> Option<String> {
+        render(self.route.id_template.as_ref()?, &self.params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+    use crate::{Reason, RouteInfo, Router};
+
+    fn info(template: &str) -> RouteInfo {
+        RouteInfo {
+            id_template: Some(String::from(template)),
+            ..RouteInfo::default()
+        }
+    }
+
+    #[test]
+    fn request_ids() {
+        let mut router = Router::default();
+        router
+            .add_with_info(
+                "/repos/:owner/:name.:ext/*path",
+                info("{owner}/{name}:{path}"),
+                1,
+            )
+            .unwrap();
+        router.add("/plain/:id", 2).unwrap();
+        let m = router.recognize("/repos/bob/app.tar/src/main.rs").unwrap();
+        assert_eq!(m.request_id().as_deref(), Some("bob/app:src/main.rs"));
+        assert_eq!(router.recognize("/plain/1").unwrap().request_id(), None);
+
+        for bad in ["{id", "id}", "{}", "{{id}}", "{user}"] {
+            assert_eq!(
+                router
+                    .add_with_info("/users/:id", info(bad), 3)
+                    .unwrap_err()
+                    .reason,
+                Reason::InvalidFormat
+            );
+        }
+        assert!(router.recognize("/users/1").is_none());
+    }
+}
@@ -0,0 +1,306 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Match, Method, MethodRouter, Miss, NodeKind, Options, Params, Router, Trail};
+
+/// every outcome of resolving a path, see `Router::resolve` and
+/// `MethodRouter::resolve`
+#[derive(Debug)]
+pub enum Resolution<'a, T> {
+    /// a route takes the path, and the method if there's one
+    Hit(Match<'a, T>),
+    /// no route takes the path, the fallback of a prefix does
+    Fallback(Match<'a, T>),
+    /// routes or a fallback take the path but not the method, `allowed`
+    /// lists their methods, the methods of a route in the order they were
+    /// added
+    MethodNotAllowed { allowed: Vec<Method> },
+    /// nothing takes the path, and why
+    Miss(Miss),
+}
+
+impl<'a, T> Resolution<'a, T> {
+    /// the match of a hit or a fallback
+    pub fn into_match(self) -> Option<Match<'a, T>> {
+        match self {
+            Resolution::Hit(m) | Resolution::Fallback(m) => Some(m),
+            Resolution::MethodNotAllowed { .. } | Resolution::Miss(_) => None,
+        }
+    }
+}
+
+impl<T> Router<T> {
+    /// resolve a path like `recognize`, telling a fallback from a route and
+    /// why a path misses
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Miss, Resolution, Router};
+    /// let mut router = Router::default();
+    /// router.add("/api/users/:id", 1).unwrap();
+    /// router.set_fallback("/api", 0).unwrap();
+    ///
+    /// assert!(matches!(router.resolve("/api/users/1"), Resolution::Hit(_)));
+    /// assert!(matches!(router.resolve("/api/teams"), Resolution::Fallback(_)));
+    /// assert!(matches!(router.resolve("/about"), Resolution::Miss(Miss::NotFound)));
+    /// ```
+    pub fn resolve<'a>(&'a self, path: &str) -> Resolution<'a, &'a T> {
+        let normalized = match self.options.normalize_path(path) {
+            Ok(path) => path,
+            Err(miss) => return Resolution::Miss(miss),
+        };
+        let mut trail = Trail::new(false);
+        let node = self
+            .find(&normalized, &mut trail)
+            .and_then(Router::data_node);
+        match self.landing(path, &normalized, node, trail.fallback.is_some()) {
+            Landing::Route => match node.and_then(|n| Some((n, n.data.as_ref()?))) {
+                Some((node, data)) => Resolution::Hit(Match {
+                    data,
+                    params: crate::route_params(&node.params, &node.info, trail.values),
+                    route: &node.info,
+                }),
+                None => Resolution::Miss(Miss::NotFound),
+            },
+            Landing::Fallback => match trail
+                .fallback
+                .and_then(|(n, _)| Some((n, n.fallback.as_ref()?)))
+            {
+                Some((node, data)) => Resolution::Fallback(Match {
+                    data,
+                    params: Params::new(),
+                    route: &node.info,
+                }),
+                None => Resolution::Miss(Miss::NotFound),
+            },
+            Landing::Miss(miss) => Resolution::Miss(miss),
+        }
+    }
+
+    // `Options::settle` with the case of the path corrected from this router
+    pub(crate) fn landing(
+        &self,
+        path: &str,
+        normalized: &str,
+        route: Option<&Router<T>>,
+        fallback: bool,
+    ) -> Landing {
+        let route = route.map(|n| n.kind);
+        self.options.settle(path, normalized, route, fallback, || {
+            self.case_corrected(normalized)
+        })
+    }
+}
+
+// how a lookup ends once the trie is searched
+pub(crate) enum Landing {
+    Route,
+    Fallback,
+    Miss(Miss),
+}
+
+impl Options {
+    // the rules every lookup applies to what the search of a normalized path
+    // reached, `route` is the kind of the node of the route taking it: under
+    // `strict_slash` only a catchall takes a trailing slash, other routes
+    // redirect to the path without it, and under `case_redirect` a miss
+    // redirects to the path cased like a route before a fallback takes it
+    pub(crate) fn settle(
+        &self,
+        path: &str,
+        normalized: &str,
+        route: Option<NodeKind>,
+        fallback: bool,
+        case_corrected: impl FnOnce() -> Option<String>,
+    ) -> Landing {
+        if let Some(kind) = route {
+            if self.strict_slash
+                && normalized.len() > 1
+                && normalized.ends_with('/')
+                && !matches!(kind, NodeKind::CatchAll)
+            {
+                // the segments the route matched under a single leading
+                // '/', a target like `//evil.com` would leave the site
+                let segments = path.split('/').filter(|s| !s.is_empty());
+                let canonical = format!("/{}", segments.collect::<Vec<_>>().join("/"));
+                return Landing::Miss(Miss::TslRedirect(canonical));
+            }
+            return Landing::Route;
+        }
+        if self.case_redirect {
+            if let Some(path) = case_corrected() {
+                return Landing::Miss(Miss::CaseRedirect(path));
+            }
+        }
+        match fallback {
+            true => Landing::Fallback,
+            false => Landing::Miss(Miss::NotFound),
+        }
+    }
+}
+
+impl<T> MethodRouter<T> {
+    /// resolve a request by method and path, every outcome of
+    /// `Router::resolve` and `MethodNotAllowed`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Method, MethodRouter, Resolution};
+    /// let mut router = MethodRouter::default();
+    /// router.get("/user/:id", 1).unwrap();
+    ///
+    /// match router.resolve(&Method::Post, "/user/1") {
+    ///     Resolution::MethodNotAllowed { allowed } => assert_eq!(allowed, [Method::Get]),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn resolve<'a>(&'a self, method: &Method, path: &str) -> Resolution<'a, &'a T> {
+        if let Some(m) = self.hit(method, path) {
+            return Resolution::Hit(m);
+        }
+        let allowed = self.allowed(path);
+        if !allowed.is_empty() {
+            return Resolution::MethodNotAllowed { allowed };
+        }
+        let m = match self.router().resolve(path) {
+            Resolution::Fallback(m) => m,
+            Resolution::Hit(_) => return Resolution::Miss(Miss::NotFound),
+            Resolution::MethodNotAllowed { allowed } => {
+                return Resolution::MethodNotAllowed { allowed }
+            }
+            Resolution::Miss(miss) => return Resolution::Miss(miss),
+        };
+        match m.data.iter().find(|(m, _)| m == method) {
+            Some((_, data)) => Resolution::Fallback(Match {
+                data,
+                params: m.params,
+                route: m.route,
+            }),
+            None => Resolution::MethodNotAllowed {
+                allowed: m.data.iter().map(|(m, _)| m.clone()).collect(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DotSegments;
+
+    #[test]
+    fn resolutions() {
+        let mut router = MethodRouter::default();
+        router.get("/files/*path", 1).unwrap();
+        router.put("/files/*path", 2).unwrap();
+        router.set_fallback("/files", Method::Get, 0).unwrap();
+
+        match router.resolve(&Method::Put, "/files/a/b") {
+            Resolution::Hit(m) => assert_eq!((*m.data, &m.params["path"][..]), (2, "a/b")),
+            other => panic!("{:?}", other),
+        }
+        match router.resolve(&Method::Get, "/files") {
+            Resolution::Fallback(m) => assert_eq!(*m.data, 0),
+            other => panic!("{:?}", other),
+        }
+        match router.resolve(&Method::Delete, "/files/a") {
+            Resolution::MethodNotAllowed { allowed } => {
+                assert_eq!(allowed, [Method::Get, Method::Put])
+            }
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            router.resolve(&Method::Put, "/files"),
+            Resolution::MethodNotAllowed { .. }
+        ));
+        assert!(matches!(
+            router.resolve(&Method::Get, "/users"),
+            Resolution::Miss(Miss::NotFound)
+        ));
+        assert!(router
+            .resolve(&Method::Get, "/files/x")
+            .into_match()
+            .is_some());
+
+        let mut router = Router::default();
+        router.options_mut().dot_segments = DotSegments::Reject;
+        router.add("/files/*path", 1).unwrap();
+        assert!(matches!(
+            router.resolve("/files/../etc"),
+            Resolution::Miss(Miss::DotSegments)
+        ));
+        assert!(router.resolve("/users").into_match().is_none());
+    }
+
+    #[test]
+    fn trailing_slash_redirects() {
+        let mut router = Router::default();
+        router.options_mut().strict_slash = true;
+        router.add("/", 0).unwrap();
+        router.add("/about", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+        router.set_fallback("/api", 3).unwrap();
+
+        let redirect = |path| match router.resolve(path) {
+            Resolution::Miss(Miss::TslRedirect(to)) => Some(to),
+            _ => None,
+        };
+        assert_eq!(redirect("/about/").as_deref(), Some("/about"));
+        assert_eq!(redirect("/about//").as_deref(), Some("/about"));
+        assert_eq!(redirect("//about/").as_deref(), Some("/about"));
+        assert_eq!(redirect("/"), None);
+        assert_eq!(redirect("/about"), None);
+        match router.resolve("/files/a/") {
+            Resolution::Hit(m) => assert_eq!(m.params["path"], "a/"),
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(
+            router.resolve("/api/users/"),
+            Resolution::Fallback(_)
+        ));
+        assert!(matches!(
+            router.resolve("/users/"),
+            Resolution::Miss(Miss::NotFound)
+        ));
+        assert_eq!(
+            router.try_recognize("/about/").unwrap_err(),
+            Miss::TslRedirect(String::from("/about"))
+        );
+        assert!(router.recognize("/about/").is_none());
+
+        router.options_mut().case_redirect = true;
+        assert_eq!(
+            router.try_recognize("/About").unwrap_err(),
+            Miss::CaseRedirect(String::from("/about"))
+        );
+        assert!(matches!(
+            router.resolve("/API/x"),
+            Resolution::Miss(Miss::NotFound)
+        ));
+        assert!(matches!(router.resolve("/api/x"), Resolution::Fallback(_)));
+
+        // every lookup lands like `resolve`
+        assert!(router.recognize_mut("/about/").is_none());
+        assert!(router.recognize_mut("/About").is_none());
+        assert_eq!(*router.recognize_mut("/api/x").unwrap().data, 3);
+        assert!(router.recognize_lazy("/about/").is_none());
+        assert!(router.recognize_all("/about/").is_empty());
+        assert!(router.recognize_tail("/about/").is_none());
+        assert!(router.recognize_bytes(b"/about/").is_none());
+        assert_eq!(*router.recognize_tail("/files/a/").unwrap().data, 2);
+        #[cfg(feature = "cache")]
+        {
+            let cached = crate::CachedRouter::new(router.clone(), 8);
+            assert!(cached.recognize("/about/").is_none());
+            assert!(cached.recognize("/About").is_none());
+            assert_eq!(*cached.recognize("/about").unwrap().data, 1);
+        }
+        let frozen = router.freeze();
+        assert!(frozen.recognize("/about/").is_none());
+        assert!(frozen.recognize("/About").is_none());
+        assert!(frozen.recognize("/API/x").is_none());
+        assert_eq!(*frozen.recognize("/files/a/").unwrap().data, 2);
+        assert_eq!(*frozen.recognize("/api/x").unwrap().data, 3);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::merge::render_route;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Constraint, PercentDecode, Router, Segment};
+use core::fmt;
+
+/// catchalls with fewer static or parameter segments before them are shallow
+const SHALLOW_CATCH_ALL_DEPTH: usize = 2;
+
+/// a risky route found by `Router::security_report`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub route: String,
+    pub risk: Risk,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Risk {
+    /// a catchall close to the root swallows large parts of the path space,
+    /// `depth` is the number of segments before it
+    ShallowCatchAll { depth: usize },
+    /// paths are percent-decoded, so the value of `param` may contain a '/'
+    /// sent as `%2F`
+    EncodedSlash { param: String },
+    /// the value of `param` is not constrained, so it has no length limit
+    UnboundedParam { param: String },
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.risk {
+            Risk::ShallowCatchAll { depth } => {
+                write!(f, "{}: catchall at depth {}", self.route, depth)
+            }
+            Risk::EncodedSlash { ref param } => {
+                write!(f, "{}: {} accepts encoded slashes", self.route, param)
+            }
+            Risk::UnboundedParam { ref param } => {
+                write!(f, "{}: {} has no length limit", self.route, param)
+            }
+        }
+    }
+}
+
+impl<T> Router<T> {
+    /// flag risky routes for a security review, in `list_routes` order
+    ///
+    /// typed parameters are bounded and never contain a '/', regex constrained
+    /// parameters are trusted to be bounded but may match a decoded '/', routes
+    /// carry no access control information so scopes are not checked
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{Risk, Router};
+    /// let mut router = Router::default();
+    /// router.add("/user/:id<u32>", 1).unwrap();
+    /// router.add("/*rest", 2).unwrap();
+    ///
+    /// let report = router.security_report();
+    /// assert_eq!(report[0].risk, Risk::ShallowCatchAll { depth: 0 });
+    /// assert_eq!(report[0].to_string(), "/*rest: catchall at depth 0");
+    /// ```
+    pub fn security_report(&self) -> Vec<Finding> {
+        let decoded = self.options.percent_decode != PercentDecode::Off;
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+
+        let mut findings = vec![];
+        for (route, _) in routes {
+            let rendered = render_route(&route);
+            let mut finding = |risk| {
+                findings.push(Finding {
+                    route: rendered.clone(),
+                    risk,
+                })
+            };
+            for (depth, (segment, constraint)) in route.0.iter().enumerate() {
+                let (name, catch_all) = match segment {
+                    Segment::Static(_) => continue,
+                    Segment::Param(name) => (name, false),
+                    Segment::CatchAll(name) => (name, true),
+                };
+                if catch_all && depth < SHALLOW_CATCH_ALL_DEPTH {
+                    finding(Risk::ShallowCatchAll { depth });
+                }
+                let typed = matches!(constraint, Some(Constraint::Type(_)));
+                if decoded && !typed {
+                    finding(Risk::EncodedSlash {
+                        param: name.clone(),
+                    });
+                }
+                if constraint.is_none() {
+                    finding(Risk::UnboundedParam {
+                        param: name.clone(),
+                    });
+                }
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_risky_routes() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/static/css/*file", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+        router.add("/user/:id<u32>/:tab", 3).unwrap();
+        assert_eq!(
+            router
+                .security_report()
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "/files/*path: catchall at depth 1",
+                "/files/*path: path has no length limit",
+                "/static/css/*file: file has no length limit",
+                "/user/:id<u32>/:tab: tab has no length limit",
+            ]
+        );
+
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        let report = router.security_report();
+        assert_eq!(report.len(), 7);
+        assert!(report.contains(&Finding {
+            route: String::from("/user/:id<u32>/:tab"),
+            risk: Risk::EncodedSlash {
+                param: String::from("tab")
+            },
+        }));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{Error, Match, Options, Router, Segment};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A router partitioned into shards by the hash of the first segment
+///
+/// routes starting with a static segment live in the shard of that segment,
+/// the root route and routes whose first segment has a parameter live in a
+/// shared router consulted when the shard doesn't know the first segment, so
+/// paths resolve exactly like in a single `Router`
+///
+/// segments are hashed as they are, a collation where different texts
+/// compare equal only finds the spelling the route was added with
+///
+/// # Example
+///
+/// ```
+/// use path_router::ShardedRouter;
+/// let mut router = ShardedRouter::new(4);
+/// router.add("/users/:id", 1).unwrap();
+/// router.add("/:page", 2).unwrap();
+///
+/// assert_eq!(*router.recognize("/users/42").unwrap().data, 1);
+/// assert_eq!(*router.recognize("/about").unwrap().data, 2);
+/// ```
+pub struct ShardedRouter<T> {
+    shards: Vec<Router<T>>,
+    shared: Router<T>,
+    options: Options,
+}
+
+impl<T> ShardedRouter<T> {
+    /// create a router with `n` shards, at least one
+    pub fn new(n: usize) -> ShardedRouter<T> {
+        ShardedRouter::with_options(n, Options::default())
+    }
+
+    /// create a router with `n` shards sharing the options
+    pub fn with_options(n: usize, options: Options) -> ShardedRouter<T> {
+        ShardedRouter {
+            shards: (0..n.max(1))
+                .map(|_| Router::with_options(options.clone()))
+                .collect(),
+            shared: Router::with_options(options.clone()),
+            options,
+        }
+    }
+
+    fn shard_index(&self, segment: &str) -> usize {
+        let mut hasher = DefaultHasher::new();
+        segment.hash(&mut hasher);
+        (hasher.finish() % self.shards.len() as u64) as usize
+    }
+
+    // first segment of a route when it's static, parsed and decoded as the
+    // inner routers store it; a route which doesn't parse goes to the shared
+    // router, which reports the error
+    fn route_head(&self, route: &str) -> Option<String> {
+        let (segments, _) = self.shared.parse_route(route, None).ok()?;
+        match segments.into_iter().next()? {
+            (Segment::Static(head), None) if !head.is_empty() => Some(head),
+            _ => None,
+        }
+    }
+
+    /// the shard a route goes to, `None` for the shared router
+    pub fn shard_of(&self, route: &str) -> Option<usize> {
+        self.route_head(route).map(|head| self.shard_index(&head))
+    }
+
+    /// set a route with data, following the rules of `Router::add`
+    pub fn add(&mut self, route: &str, data: T) -> Result<&mut T, Error> {
+        match self.shard_of(route) {
+            Some(i) => self.shards[i].add(route, data),
+            None => self.shared.add(route, data),
+        }
+    }
+
+    /// add many routes, building every shard on its own thread
+    ///
+    /// on errors the routes before the failing one in each shard are kept
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::ShardedRouter;
+    /// let mut router = ShardedRouter::new(8);
+    /// let routes = (0..1000).map(|i| (format!("/r{}/:id", i), i));
+    /// router.extend_parallel(routes).unwrap();
+    ///
+    /// assert_eq!(*router.recognize("/r512/7").unwrap().data, 512);
+    /// ```
+    pub fn extend_parallel<I>(&mut self, routes: I) -> Result<(), Error>
+    where
+        T: Send,
+        I: IntoIterator<Item = (String, T)>,
+    {
+        let mut groups = (0..=self.shards.len())
+            .map(|_| vec![])
+            .collect::<Vec<Vec<(String, T)>>>();
+        for (route, data) in routes {
+            let i = self.shard_of(&route).unwrap_or(self.shards.len());
+            groups[i].push((route, data));
+        }
+
+        let shared = groups.pop().unwrap_or_default();
+        for (route, data) in shared {
+            self.shared.add(&route, data)?;
+        }
+        std::thread::scope(|scope| {
+            let handles = self
+                .shards
+                .iter_mut()
+                .zip(groups)
+                .map(|(shard, routes)| {
+                    scope.spawn(move || {
+                        for (route, data) in routes {
+                            shard.add(&route, data)?;
+                        }
+                        Ok(())
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .try_for_each(|h| h.join().expect("shard builder panicked"))
+        })
+    }
+
+    /// recognize a path
+    pub fn recognize<'a>(&'a self, path: &str) -> Option<Match<'a, &'a T>> {
+        let normalized = self.options.normalize_path(path).ok()?;
+        let head = normalized
+            .strip_prefix('/')
+            .and_then(|p| p.split('/').find(|s| !s.is_empty()));
+        if let Some(head) = head {
+            let head = self.options.decode(head)?;
+            let shard = &self.shards[self.shard_index(&head)];
+            // a miss below the static segment goes back to the wildcards
+            if shard.static_child(&head, self.options.collation).is_some() {
+                if let Some(m) = shard.recognize(path) {
+                    return Some(m);
+                }
+            }
+        }
+        self.shared.recognize(path)
+    }
+
+    /// list routes in the order of an equivalent single `Router`
+    pub fn list_routes(&self) -> Vec<String> {
+        let mut statics = self
+            .shards
+            .iter()
+            .flat_map(|shard| shard.list_routes())
+            .map(|route| (self.route_head(&route).unwrap_or_default(), route))
+            .collect::<Vec<_>>();
+        // routes of a first segment are all in one shard, already in order
+        let collate = self.options.collation.for_keys();
+        statics.sort_by(|a, b| collate(&a.0, &b.0));
+
+        let mut shared = self.shared.list_routes();
+        let rest = match shared.first() {
+            Some(r) if r == "/" => shared.split_off(1),
+            _ => std::mem::take(&mut shared),
+        };
+        shared.extend(statics.into_iter().map(|(_, route)| route));
+        shared.extend(rest);
+        shared
+    }
+
+    pub fn shards(&self) -> &[Router<T>] {
+        &self.shards
+    }
+
+    /// the router of the root route and of routes starting with a wildcard
+    pub fn shared(&self) -> &Router<T> {
+        &self.shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collation;
+
+    #[test]
+    fn sharded_router() {
+        const ROUTES: [&str; 9] = [
+            "/",
+            "/a/b",
+            "/a/:x",
+            "/b/c/*rest",
+            "/c",
+            "/:page",
+            "/:page/edit",
+            "/v:major",
+            "/:file.:ext",
+        ];
+        let mut sharded = ShardedRouter::new(3);
+        let mut single = Router::default();
+        for (i, route) in ROUTES.iter().enumerate() {
+            sharded.add(route, i).unwrap();
+            single.add(route, i).unwrap();
+        }
+        assert!(sharded.add("/a/:y", 9).is_err());
+        assert!(sharded.add("/:other", 9).is_err());
+        assert!(sharded.add("/v:minor", 9).is_err());
+        assert_eq!(sharded.shard_of("/v:major"), None);
+        assert_eq!(sharded.list_routes(), single.list_routes());
+
+        for path in [
+            "/",
+            "/a/b",
+            "/a/z",
+            "/a/z/y",
+            "/b/c/d/e",
+            "/b",
+            "/c",
+            "/d",
+            "/d/edit",
+            "/d/e/f",
+            "/v2",
+            "/files.json",
+        ] {
+            assert_eq!(
+                sharded.recognize(path).map(|m| (*m.data, m.params)),
+                single.recognize(path).map(|m| (*m.data, m.params)),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn collated_routes() {
+        let options = Options {
+            collation: Collation::new("reverse", |a, b| b.cmp(a)),
+            ..Options::default()
+        };
+        let mut sharded = ShardedRouter::with_options(3, options.clone());
+        let mut single = Router::with_options(options);
+        for (i, route) in ["/", "/a/b", "/b", "/c/:id", "/d", "/:page"]
+            .iter()
+            .enumerate()
+        {
+            sharded.add(route, i).unwrap();
+            single.add(route, i).unwrap();
+        }
+        assert_eq!(sharded.list_routes(), single.list_routes());
+    }
+
+    #[test]
+    fn parallel_build() {
+        let mut router = ShardedRouter::new(4);
+        let routes = (0..100).map(|i| (format!("/s{}/:id", i), i));
+        router.extend_parallel(routes).unwrap();
+        router
+            .extend_parallel(vec![(String::from("/*rest"), 100)])
+            .unwrap();
+        assert!(router.shards().iter().all(|s| !s.list_routes().is_empty()));
+        assert_eq!(router.shared().list_routes(), vec!["/*rest"]);
+        assert_eq!(*router.recognize("/s42/1").unwrap().data, 42);
+        assert_eq!(*router.recognize("/t/1").unwrap().data, 100);
+        assert!(router
+            .extend_parallel(vec![(String::from("/s1/:name"), 0)])
+            .is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::Router;
+use arc_swap::{ArcSwap, Guard};
+use std::fmt;
+use std::sync::Arc;
+
+/// a router to share between threads and swap while they read it
+///
+/// readers `load` the current router without taking a lock, writers put a
+/// new one in place with `replace` or change a copy of the current one with
+/// `update`; a reader keeps the router it loaded until it drops it, so a
+/// swap never shows half a route table
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Router, SharedRouter};
+/// let mut router = Router::default();
+/// router.add("/user/:id", 1).unwrap();
+/// let shared = SharedRouter::new(router);
+///
+/// let before = shared.load();
+/// shared.update(|r| r.add("/user/:id/posts", 2).map(drop)).unwrap();
+/// assert!(before.recognize("/user/7/posts").is_none());
+/// assert_eq!(*shared.load().recognize("/user/7/posts").unwrap().data, 2);
+///
+/// shared.replace(Router::default());
+/// assert!(shared.load().recognize("/user/7").is_none());
+/// ```
+pub struct SharedRouter<T> {
+    router: ArcSwap<Router<T>>,
+}
+
+impl<T> SharedRouter<T> {
+    pub fn new(router: Router<T>) -> SharedRouter<T> {
+        SharedRouter {
+            router: ArcSwap::from_pointee(router),
+        }
+    }
+
+    /// the current router, kept alive by the guard
+    pub fn load(&self) -> Guard<Arc<Router<T>>> {
+        self.router.load()
+    }
+
+    /// the current router as an `Arc`, to hold on to across requests
+    pub fn load_full(&self) -> Arc<Router<T>> {
+        self.router.load_full()
+    }
+
+    /// put a router in place, returning the one it replaced
+    pub fn replace(&self, router: Router<T>) -> Arc<Router<T>> {
+        self.router.swap(Arc::new(router))
+    }
+
+    /// change a copy of the current router and put it in place, returning
+    /// what `f` returned
+    ///
+    /// `f` runs again on a fresh copy when another writer swaps the router
+    /// in between, so concurrent updates aren't lost
+    pub fn update<R, F>(&self, mut f: F) -> R
+    where
+        T: Clone,
+        F: FnMut(&mut Router<T>) -> R,
+    {
+        let mut result = None;
+        self.router.rcu(|current| {
+            let mut router = Router::clone(current);
+            result = Some(f(&mut router));
+            router
+        });
+        result.expect("update runs at least once")
+    }
+
+    pub fn into_inner(self) -> Arc<Router<T>> {
+        self.router.into_inner()
+    }
+}
+
+impl<T> From<Router<T>> for SharedRouter<T> {
+    fn from(router: Router<T>) -> SharedRouter<T> {
+        SharedRouter::new(router)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedRouter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedRouter")
+            .field("router", &self.router.load())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn shared_router() {
+        let shared = Arc::new(SharedRouter::new(Router::default()));
+        let writers = (0..4)
+            .map(|w| {
+                let shared = Arc::clone(&shared);
+                thread::spawn(move || {
+                    for i in 0..25 {
+                        let route = format!("/w{}/{}", w, i);
+                        shared
+                            .update(|r| r.add(&route, w * 100 + i).map(drop))
+                            .unwrap();
+                        assert!(shared.load().recognize(&route).is_some());
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        let router = shared.load_full();
+        assert_eq!(router.list_routes().len(), 100);
+        assert_eq!(*router.recognize("/w3/24").unwrap().data, 324);
+
+        let old = shared.replace(Router::default());
+        assert!(Arc::ptr_eq(&old, &router));
+        assert!(shared.load().recognize("/w3/24").is_none());
+        assert!(old.recognize("/w3/24").is_some());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::merge::render_route;
+use crate::{Constraint, Error, Options, RouteInfo, Router, Segment};
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+// route table as it is serialized, in `list_routes` order
+#[derive(Serialize, Deserialize)]
+struct Table<D> {
+    #[serde(default)]
+    options: Options,
+    routes: Vec<Entry<D>>,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    fallbacks: Vec<FallbackEntry<D>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FallbackEntry<D> {
+    prefix: String,
+    data: D,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<D> {
+    route: String,
+    /// regular expressions constraining parameters, by parameter name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    regexes: BTreeMap<String, String>,
+    #[serde(flatten)]
+    info: RouteInfo,
+    data: D,
+}
+
+/// the router is serialized as its options and its routes with their data,
+/// like `{"options": {..}, "routes": [{"route": "/user/:id", "data": 1}]}`,
+/// and its fallbacks if it has some, like
+/// `"fallbacks": [{"prefix": "/user", "data": 0}]`
+impl<T: Serialize> Serialize for Router<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut routes = vec![];
+        self.parsed_routes(vec![], &mut routes);
+        let routes = routes
+            .into_iter()
+            .map(|(route, node)| Entry {
+                route: render_route(&route),
+                regexes: route
+                    .0
+                    .iter()
+                    .filter_map(|(s, c)| match (s, c.as_ref()?.regex_source()) {
+                        (Segment::Param(name), Some(re)) => Some((name.clone(), String::from(re))),
+                        _ => None,
+                    })
+                    .collect(),
+                info: node.info.clone(),
+                data: node.data.as_ref().expect("data node"),
+            })
+            .collect();
+        let mut fallbacks = vec![];
+        self.parsed_fallbacks(vec![], &mut fallbacks);
+        let fallbacks = fallbacks
+            .into_iter()
+            .map(|(prefix, data)| FallbackEntry {
+                prefix: render_route(&(prefix, false)),
+                data,
+            })
+            .collect();
+        Table {
+            options: Options::clone(&self.options),
+            routes,
+            fallbacks,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Router<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Router<T>, D::Error> {
+        let table = Table::<T>::deserialize(deserializer)?;
+        // routes are stored normalized, the options apply to later routes
+        // except the collation ordering the nodes
+        let mut router = Router::default();
+        router.options_mut().collation = table.options.collation;
+        for entry in table.routes {
+            let error = |e: Error| de::Error::custom(format!("{}: {}", entry.route, e));
+            let (mut segments, optional) = router.parse_route(&entry.route, None).map_err(error)?;
+            for (name, re) in entry.regexes.iter() {
+                let param = segments
+                    .iter_mut()
+                    .find(|(s, _)| matches!(s, Segment::Param(n) if n == name));
+                match param {
+                    Some((_, c)) => *c = Some(regex_constraint(re).map_err(de::Error::custom)?),
+                    None => {
+                        let error =
+                            format!("{}: no parameter {} for regex {:?}", entry.route, name, re);
+                        return Err(de::Error::custom(error));
+                    }
+                }
+            }
+            let node = router
+                .add_parsed(&entry.route, (segments, optional))
+                .map_err(error)?;
+            node.info = entry.info;
+            node.set_data(entry.data);
+        }
+        for entry in table.fallbacks {
+            let node = router
+                .sub_route(&entry.prefix)
+                .map_err(|e| de::Error::custom(format!("fallback {}: {}", entry.prefix, e)))?;
+            if node.fallback.is_some() {
+                let error = format!("fallback {} set twice", entry.prefix);
+                return Err(de::Error::custom(error));
+            }
+            node.fallback = Some(entry.data);
+        }
+        router.options = Arc::new(table.options);
+        Ok(router)
+    }
+}
+
+#[cfg(feature = "regex")]
+fn regex_constraint(re: &str) -> Result<Constraint, String> {
+    Constraint::regex(re).map_err(|_| format!("invalid regex {:?}", re))
+}
+
+#[cfg(not(feature = "regex"))]
+fn regex_constraint(re: &str) -> Result<Constraint, String> {
+    Err(format!("regex {:?} needs feature `regex`", re))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collation, PercentDecode};
+
+    #[test]
+    fn json_round_trip() {
+        let mut router = Router::default();
+        router.options_mut().percent_decode = PercentDecode::Strict;
+        router.add("/", 0).unwrap();
+        router.add("/user/:id<u32>", 1).unwrap();
+        router.add("/user/:name/:tab?", 2).unwrap();
+        router
+            .add_with_info(
+                "/files/a%20b/*path",
+                RouteInfo {
+                    request_schema: Some(String::from("#/File")),
+                    ..RouteInfo::default()
+                },
+                3,
+            )
+            .unwrap();
+
+        let json = serde_json::to_string(&router).unwrap();
+        assert!(json.contains(
+            r#"{"route":"/user/:id<u32>","request_schema":null,"response_schema":null,"summary":null,"tags":[],"annotations":{},"compression":null,"catch_all_slash":null,"id_template":null,"max_body_bytes":null,"data":1}"#
+        ));
+        let restored: Router<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.list_routes(), router.list_routes());
+        assert_eq!(restored.options().percent_decode, PercentDecode::Strict);
+        assert_eq!(*restored.recognize("/user/7").unwrap().data, 1);
+        assert_eq!(*restored.recognize("/user/bob").unwrap().data, 2);
+        let m = restored.recognize("/files/a%20b/x/y").unwrap();
+        assert_eq!((*m.data, m.params["path"].as_str()), (3, "x/y"));
+        assert_eq!(m.route.request_schema.as_deref(), Some("#/File"));
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let mut with_fallbacks = router.clone();
+        with_fallbacks.set_fallback("/user", 9).unwrap();
+        with_fallbacks.set_fallback("/", 8).unwrap();
+        let json = serde_json::to_string(&with_fallbacks).unwrap();
+        assert!(
+            json.ends_with(r#""fallbacks":[{"prefix":"/","data":8},{"prefix":"/user","data":9}]}"#)
+        );
+        let restored: Router<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.recognize("/user/7/a/b").unwrap().data, 9);
+        assert_eq!(*restored.recognize("/other/x").unwrap().data, 8);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let mut folded = Router::with_options(Options {
+            collation: Collation::ascii_case_insensitive(),
+            ..Options::default()
+        });
+        folded.add("/B", 1).unwrap();
+        folded.add("/a", 2).unwrap();
+        let json = serde_json::to_string(&folded).unwrap();
+        assert!(json.contains(r#""collation":"ascii_case_insensitive""#));
+        let restored: Router<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.recognize("/A").unwrap().data, 2);
+        assert_eq!(*restored.recognize("/b").unwrap().data, 1);
+        folded.options_mut().collation = Collation::new("reverse", |a, b| b.cmp(a));
+        let err = serde_json::to_string(&folded).unwrap_err();
+        assert!(err.to_string().contains("collation reverse isn't built in"));
+        let json = r#"{"options": {"collation": "reverse"}, "routes": []}"#;
+        let err = serde_json::from_str::<Router<usize>>(json).err().unwrap();
+        assert!(err.to_string().starts_with("unknown collation reverse"));
+
+        let json = r#"{"routes": [{"route": "/a/:x", "data": 1}, {"route": "/a/:y", "data": 2}]}"#;
+        let err = serde_json::from_str::<Router<usize>>(json).err().unwrap();
+        assert!(err.to_string().starts_with("/a/:y: "));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn regex_round_trip() {
+        let mut router = Router::default();
+        router
+            .add_with_constraint("/v/:ver", Constraint::regex("v[0-9]+").unwrap(), 1)
+            .unwrap();
+        router.add("/v/:name", 2).unwrap();
+
+        let json = serde_json::to_string(&router).unwrap();
+        let restored: Router<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.recognize("/v/v2").unwrap().data, 1);
+        assert_eq!(*restored.recognize("/v/latest").unwrap().data, 2);
+
+        let mut router = Router::default();
+        router.add_braced("/p/{id:\\d+}/{page:[a-z]+}", 1).unwrap();
+        let json = serde_json::to_string(&router).unwrap();
+        assert!(json.contains(r#""regexes":{"id":"\\d+","page":"[a-z]+"}"#));
+        let restored: Router<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(*restored.recognize("/p/12/intro").unwrap().data, 1);
+        assert!(restored.recognize("/p/12/3").is_none());
+        assert!(restored.recognize("/p/x/intro").is_none());
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let json = r#"{"routes": [{"route": "/a/:x", "regexes": {"y": "b"}, "data": 1}]}"#;
+        let err = serde_json::from_str::<Router<usize>>(json).err().unwrap();
+        assert!(err.to_string().starts_with("/a/:x: no parameter y"));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{NodeKind, Router};
+
+/// sizes of the trie of a router, see `Router::stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouterStats {
+    /// routes with data, like `Router::len`
+    pub routes: usize,
+    /// fallbacks, see `Router::set_fallback`
+    pub fallbacks: usize,
+    /// static nodes, the root included, a chain of static segments merged
+    /// into one node counts once
+    pub static_nodes: usize,
+    pub param_nodes: usize,
+    pub catch_all_nodes: usize,
+    /// the number of nodes above the deepest node, 0 for a router without
+    /// routes, like `RouteNodeInfo::depth`
+    pub max_depth: usize,
+}
+
+impl RouterStats {
+    /// all nodes of the trie
+    pub fn nodes(&self) -> usize {
+        self.static_nodes + self.param_nodes + self.catch_all_nodes
+    }
+}
+
+impl<T, K> Router<T, K> {
+    /// number of routes with data
+    pub fn len(&self) -> usize {
+        self.static_children
+            .iter()
+            .chain(self.param_children.iter())
+            .chain(self.catch_all_child.as_deref())
+            .map(Router::len)
+            .sum::<usize>()
+            + self.data.is_some() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// count the routes and the nodes of the trie by kind
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/users/:id", 1).unwrap();
+    /// router.add("/users/:id/files/*path", 2).unwrap();
+    ///
+    /// let stats = router.stats();
+    /// assert_eq!((stats.routes, stats.nodes(), stats.max_depth), (2, 5, 4));
+    /// assert_eq!((stats.param_nodes, stats.catch_all_nodes), (1, 1));
+    /// ```
+    pub fn stats(&self) -> RouterStats {
+        let mut stats = RouterStats::default();
+        self.count_nodes(0, &mut stats);
+        stats
+    }
+
+    fn count_nodes(&self, depth: usize, stats: &mut RouterStats) {
+        match self.kind {
+            NodeKind::Static => stats.static_nodes += 1,
+            NodeKind::Param => stats.param_nodes += 1,
+            NodeKind::CatchAll => stats.catch_all_nodes += 1,
+        }
+        stats.routes += self.data.is_some() as usize;
+        stats.fallbacks += self.fallback.is_some() as usize;
+        stats.max_depth = stats.max_depth.max(depth);
+        let children = self
+            .static_children
+            .iter()
+            .chain(self.param_children.iter())
+            .chain(self.catch_all_child.as_deref());
+        for node in children {
+            node.count_nodes(depth + 1, stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+    use crate::Segment;
+
+    #[test]
+    fn stats() {
+        let mut router = Router::default();
+        assert!(router.is_empty());
+        assert_eq!(
+            router.stats(),
+            RouterStats {
+                static_nodes: 1,
+                ..RouterStats::default()
+            }
+        );
+
+        router.add("/", 0).unwrap();
+        router.add("/a/b/c", 1).unwrap();
+        router.add("/a/:x?", 2).unwrap();
+        router.add("/a/:n<u32>/*rest", 3).unwrap();
+        router.set_fallback("/a", 4).unwrap();
+        assert_eq!((router.len(), router.is_empty()), (4, false));
+        assert_eq!(
+            router.stats(),
+            RouterStats {
+                routes: 4,
+                fallbacks: 1,
+                static_nodes: 3,
+                param_nodes: 2,
+                catch_all_nodes: 1,
+                max_depth: 3,
+            }
+        );
+
+        let mut keys = Router::<usize, u32>::default();
+        keys.add_keys(vec![Segment::Static(1), Segment::Param("x".into())], 1)
+            .unwrap();
+        assert_eq!(keys.len(), 1);
+    }
+}
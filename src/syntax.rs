@@ -0,0 +1,228 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{check_route, Constraint, Error, Options, ParsedRoute, Reason, Segment};
+use alloc::collections::BTreeSet;
+use core::fmt;
+
+/// the syntax of route patterns, set it in `Options::parser`
+///
+/// the router normalizes a pattern with `Options::normalize` before parsing
+/// it and percent-decodes the static segments after, a parser only splits
+/// it; the parsed route must name its parameters once, have at most one
+/// catchall and only make a last parameter optional, or adding it fails
+/// with the `Reason` it breaks; errors of a parser needn't name the route,
+/// the router does
+///
+/// # Example
+///
+/// ```
+/// use path_router::{ColonSyntax, Error, Options, ParsedRoute, RouteParser, Router};
+/// use std::sync::Arc;
+///
+/// // `/users/<id>` for `/users/:id`
+/// struct Angle;
+///
+/// impl RouteParser for Angle {
+///     fn name(&self) -> &str {
+///         "angle"
+///     }
+///
+///     fn parse(&self, route: &str, options: &Options) -> Result<ParsedRoute, Error> {
+///         let route = route.replace('<', ":").replace('>', "");
+///         ColonSyntax.parse(&route, options)
+///     }
+/// }
+///
+/// let mut router = Router::default();
+/// router.options_mut().parser = Some(Arc::new(Angle));
+/// router.add("/users/<id>", 1).unwrap();
+/// assert_eq!(router.recognize("/users/7").unwrap().params["id"], "7");
+/// assert_eq!(router.list_routes(), ["/users/:id"]);
+/// ```
+pub trait RouteParser: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// split a pattern into its segments
+    fn parse(&self, route: &str, options: &Options) -> Result<ParsedRoute, Error>;
+}
+
+impl fmt::Debug for dyn RouteParser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RouteParser({:?})", self.name())
+    }
+}
+
+/// the syntax of `Router::add`, `:name` parameters and `*name` catchalls,
+/// the default parser
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColonSyntax;
+
+impl RouteParser for ColonSyntax {
+    fn name(&self) -> &str {
+        "colon"
+    }
+
+    fn parse(&self, route: &str, options: &Options) -> Result<ParsedRoute, Error> {
+        check_route(route)?;
+
+        // only the last parameter can be optional, it's checked by check_route
+        let last_segment = route.rsplit('/').next().unwrap_or("");
+        let optional = last_segment.starts_with(':') && last_segment.ends_with('?');
+        let route = if optional {
+            &route[..route.len() - 1]
+        } else {
+            route
+        };
+
+        let mut segments = vec![];
+        for (i, segment) in route[1..]
+            .split('/')
+            .take_while(|s| !s.is_empty())
+            .enumerate()
+        {
+            segments.push(Segment::parse(segment, &options.segment_matchers).map_err(|e| e.at(i))?);
+        }
+        Ok((segments, optional))
+    }
+}
+
+/// the brace syntax of `Router::add_braced`, like `/user/{id}`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BraceSyntax;
+
+impl RouteParser for BraceSyntax {
+    fn name(&self) -> &str {
+        "brace"
+    }
+
+    fn parse(&self, route: &str, options: &Options) -> Result<ParsedRoute, Error> {
+        let (route, regexes) = crate::braced::unbrace(route, options)?;
+        let (mut segments, optional) = ColonSyntax.parse(&route, options)?;
+        for (name, re) in regexes {
+            let param = segments
+                .iter_mut()
+                .enumerate()
+                .find(|(_, (s, _))| matches!(s, Segment::Param(n) if *n == name));
+            match param {
+                Some((i, (_, c))) => *c = Some(regex(&re).map_err(|e| e.at(i))?),
+                None => return Err(Error::new(Reason::MisplacedConstraint)),
+            }
+        }
+        Ok((segments, optional))
+    }
+}
+
+#[cfg(feature = "regex")]
+fn regex(re: &str) -> Result<Constraint, Error> {
+    Constraint::regex(re)
+}
+
+// regexes of braced routes need feature `regex`
+#[cfg(not(feature = "regex"))]
+fn regex(_: &str) -> Result<Constraint, Error> {
+    Err(Error::new(Reason::InvalidRegex))
+}
+
+// check parsed segments keep the rules every route follows
+pub(crate) fn check_pattern(
+    segments: &[(Segment<String>, Option<Constraint>)],
+    optional: bool,
+) -> Result<(), Error> {
+    let mut names = BTreeSet::new();
+    let mut catch_alls = 0;
+    for (i, (segment, constraint)) in segments.iter().enumerate() {
+        let fault = |reason| Err(Error::new(reason).at(i));
+        let named = match (segment, constraint) {
+            (Segment::Param(_), Some(Constraint::Template(t))) => t.names().iter().collect(),
+            (Segment::Param(name), _) => vec![name],
+            (Segment::CatchAll(name), _) => {
+                catch_alls += 1;
+                if catch_alls > 1 {
+                    return fault(Reason::SecondCatchAll);
+                }
+                vec![name]
+            }
+            (Segment::Static(text), _) if text.is_empty() => return fault(Reason::EmptySegment),
+            (Segment::Static(text), _) if text.contains('/') => {
+                return fault(Reason::InvalidFormat)
+            }
+            (Segment::Static(_), _) => vec![],
+        };
+        for name in named {
+            if name.is_empty() {
+                return fault(Reason::EmptyName);
+            }
+            if !names.insert(name.as_str()) {
+                return fault(Reason::DuplicateName(name.clone()));
+            }
+        }
+    }
+    match optional && !matches!(segments.last(), Some((Segment::Param(_), _))) {
+        true => Err(Error::new(Reason::MisplacedOptional)),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Router;
+    use alloc::sync::Arc;
+
+    // every segment of the path is a parameter named after its position
+    struct Positional;
+
+    impl RouteParser for Positional {
+        fn name(&self) -> &str {
+            "positional"
+        }
+
+        fn parse(&self, route: &str, _: &Options) -> Result<ParsedRoute, Error> {
+            let segments = route[1..]
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(|s| match s {
+                    "_" => (Segment::Param(String::from("p")), None),
+                    s => (Segment::Static(String::from(s)), None),
+                })
+                .collect();
+            Ok((segments, false))
+        }
+    }
+
+    #[test]
+    fn route_parsers() {
+        let mut router = Router::default();
+        router.options_mut().parser = Some(Arc::new(BraceSyntax));
+        router.add("/users/{id:u32}/files/{path:*}", 1).unwrap();
+        assert!(router.add("/users/:id", 2).is_err());
+        router.add_braced("/v{major}", 3).unwrap();
+        assert_eq!(
+            router.list_routes(),
+            ["/users/:id<u32>/files/*path", "/v:major"]
+        );
+        assert_eq!(*router.recognize("/v2").unwrap().data, 3);
+        assert!(format!("{:?}", router.options()).contains("RouteParser(\"brace\")"));
+
+        let mut router = Router::default();
+        router.options_mut().parser = Some(Arc::new(Positional));
+        router.add("/a/_", 1).unwrap();
+        assert!(router.add("/a/_/_", 2).is_err());
+        assert_eq!(router.recognize("/a/x").unwrap().params["p"], "x");
+        router.add_braced("/b/{id}", 3).unwrap();
+        assert_eq!(*router.recognize("/b/x").unwrap().data, 3);
+
+        let options = Options::default();
+        assert_eq!(
+            ColonSyntax.parse("/a/:b?", &options).unwrap(),
+            (
+                vec![
+                    (Segment::Static(String::from("a")), None),
+                    (Segment::Param(String::from("b")), None),
+                ],
+                true
+            )
+        );
+        assert!(ColonSyntax.parse("/a/", &options).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::normalize::has_dot_segments;
+use crate::{route_params, DotSegments, Landing, Params, RouteInfo, Router, Trail};
+use alloc::borrow::Cow;
+use core::ops::Range;
+
+/// match result of `Router::recognize_tail`
+#[derive(Debug)]
+pub struct TailMatch<'a, T> {
+    /// data set in the route
+    pub data: T,
+    /// extracted parameters from the path besides the catch all
+    pub params: Params,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+    /// bytes of the path caught by the catch all of the route, neither
+    /// decoded nor transformed
+    pub tail: Option<Range<usize>>,
+}
+
+// the byte offset in `path` of the offset `at` in its normalized form,
+// normalization maps characters one to one but for the encoded slashes
+// `EncodedSlash::Separator` turns into `/`
+fn original_offset(path: &str, normalized: &str, at: usize) -> usize {
+    let mut rest = path;
+    for c in normalized[..at].chars() {
+        let len = match rest.chars().next() {
+            Some('%') if c == '/' => 3,
+            Some(o) => o.len_utf8(),
+            None => break,
+        };
+        rest = &rest[len..];
+    }
+    path.len() - rest.len()
+}
+
+impl<T> Router<T> {
+    /// recognize a path like `recognize`, the value caught by the catch all
+    /// is given as its range in the path instead of a copy in `params`
+    ///
+    /// a path with dot segments misses unless `Options::dot_segments` keeps
+    /// them, a resolved path has no range in the path
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/proxy/:host/*rest", 1).unwrap();
+    ///
+    /// let path = "/proxy/example.com/a/b%20c";
+    /// let m = router.recognize_tail(path).unwrap();
+    /// assert_eq!(m.params["host"], "example.com");
+    /// assert_eq!(&path[m.tail.unwrap()], "a/b%20c");
+    /// ```
+    pub fn recognize_tail<'a>(&'a self, path: &str) -> Option<TailMatch<'a, &'a T>> {
+        if self.options.dot_segments != DotSegments::Keep
+            && has_dot_segments(self.options.normalize(path).as_bytes())
+        {
+            return None;
+        }
+        let normalized = self.options.normalize_path(path).ok()?;
+        let mut trail = Trail::new(false);
+        let node = self
+            .find(&normalized, &mut trail)
+            .and_then(Router::data_node);
+        let node = match (
+            self.landing(path, &normalized, node, trail.fallback.is_some()),
+            node,
+        ) {
+            (Landing::Route, Some(node)) => node,
+            (Landing::Fallback, _) => {
+                let (node, _) = trail.fallback?;
+                return Some(TailMatch {
+                    data: node.fallback.as_ref()?,
+                    params: Params::new(),
+                    route: &node.info,
+                    tail: None,
+                });
+            }
+            _ => return None,
+        };
+        let skip = trail.tail.as_ref().map(|(i, _)| *i);
+        let names = node
+            .params
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != skip);
+        let values = trail
+            .values
+            .into_iter()
+            .enumerate()
+            .filter(|&(i, _)| Some(i) != skip);
+        let params = route_params(names.map(|(_, n)| n), &node.info, values.map(|(_, v)| v));
+        let tail = trail.tail.map(|(_, range)| match normalized {
+            Cow::Borrowed(_) => range,
+            Cow::Owned(ref n) => {
+                original_offset(path, n, range.start)..original_offset(path, n, range.end)
+            }
+        });
+        Some(TailMatch {
+            data: node.data.as_ref()?,
+            params,
+            route: &node.info,
+            tail,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::prelude::*;
+    use crate::{EncodedSlash, Pick, TieBreaker, TrailingSlash};
+
+    fn tail(
+        router: &Router<usize>,
+        path: &'static str,
+    ) -> (usize, Vec<String>, Option<&'static str>) {
+        let m = router.recognize_tail(path).unwrap();
+        let params = m
+            .params
+            .iter()
+            .map(|(n, v)| format!("{}={}", n, v))
+            .collect();
+        (*m.data, params, m.tail.map(|r| &path[r]))
+    }
+
+    #[test]
+    fn recognize_tail() {
+        let mut router = Router::default();
+        router.add("/repo/:owner/*path/blob/:ref", 1).unwrap();
+        router.add("/files/*path", 2).unwrap();
+        router.add("/n/:id<u32>/*rest", 3).unwrap();
+        router.add("/n/:small<u8>/x", 4).unwrap();
+        router.add("/plain/:id", 5).unwrap();
+        router.set_fallback("/plain", 6).unwrap();
+        router.options_mut().tie_breaker = Some(TieBreaker::new(|_| Pick::Second));
+
+        assert_eq!(
+            tail(&router, "/repo/bob/src/lib/blob/main"),
+            (
+                1,
+                vec![String::from("owner=bob"), String::from("ref=main")],
+                Some("src/lib")
+            )
+        );
+        assert_eq!(
+            tail(&router, "/files/a%2Fb/c/"),
+            (2, vec![], Some("a%2Fb/c/"))
+        );
+        assert_eq!(
+            tail(&router, "/n/7/x"),
+            (4, vec![String::from("small=7")], None)
+        );
+        assert_eq!(
+            tail(&router, "/n/700/x"),
+            (3, vec![String::from("id=700")], Some("x"))
+        );
+        assert_eq!(
+            tail(&router, "/plain/1"),
+            (5, vec![String::from("id=1")], None)
+        );
+        assert_eq!(tail(&router, "/plain/1/2"), (6, vec![], None));
+
+        router.options_mut().fullwidth = true;
+        router.options_mut().catch_all_slash = TrailingSlash::Strip;
+        assert_eq!(
+            tail(&router, "／files／ａ／b//"),
+            (2, vec![], Some("ａ／b"))
+        );
+
+        router.options_mut().encoded_slash = EncodedSlash::Reject;
+        assert!(router.recognize("/files/a%2Fb").is_none());
+        assert!(router.recognize_tail("/files/a%2Fb").is_none());
+        router.options_mut().encoded_slash = EncodedSlash::Separator;
+        assert_eq!(
+            tail(&router, "/repo/a%2Fb/src/blob/main"),
+            (
+                1,
+                vec![String::from("owner=a"), String::from("ref=main")],
+                Some("b/src")
+            )
+        );
+        assert_eq!(tail(&router, "/files/ａ%2fb"), (2, vec![], Some("ａ%2fb")));
+    }
+
+    #[test]
+    fn max_catch_all() {
+        let mut router = Router::default();
+        router.add("/files/*path", 1).unwrap();
+        router.add("/repo/*path/manifest.json", 2).unwrap();
+        router.set_fallback("/repo", 3).unwrap();
+        router.options_mut().max_catch_all = Some(5);
+
+        assert_eq!(*router.recognize("/files/a/b/c").unwrap().data, 1);
+        assert!(router.recognize("/files/a/b/cd").is_none());
+        assert!(router.recognize_bytes(b"/files/a/b/cd").is_none());
+        assert_eq!(
+            *router.recognize("/repo/a/b/c/manifest.json").unwrap().data,
+            2
+        );
+        assert_eq!(
+            *router.recognize("/repo/a/b/cd/manifest.json").unwrap().data,
+            3
+        );
+
+        let frozen = router.freeze();
+        assert_eq!(*frozen.recognize("/files/a/b/c").unwrap().data, 1);
+        assert!(frozen.recognize("/files/a/b/cd").is_none());
+        assert_eq!(
+            *frozen.recognize("/repo/a/b/cd/manifest.json").unwrap().data,
+            3
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use crate::normalize::{normalize_fullwidth_bytes, percent_decode_bytes};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{normalize_fullwidth, Options, PercentDecode};
+use alloc::borrow::Cow;
+use core::ops::Range;
+
+// the text of a path looked up, a string or raw bytes which may not be valid
+// UTF-8, see `Router::recognize_bytes`; static segments and constrained
+// parameters only take segments which are valid UTF-8
+pub(crate) trait Text: ToOwned {
+    fn as_bytes(&self) -> &[u8];
+
+    // the part of the text in a range of its bytes, cut at '/'
+    fn slice(&self, range: Range<usize>) -> &Self;
+
+    // the text as a string, None if it isn't valid UTF-8
+    fn text(&self) -> Option<&str>;
+
+    // the text as a string with invalid UTF-8 replaced
+    fn lossy(&self) -> Cow<'_, str>;
+
+    fn into_text(text: Cow<'_, Self>) -> Option<Cow<'_, str>>;
+
+    fn from_text(text: Cow<'_, str>) -> Cow<'_, Self>;
+
+    // a text made of whole segments of texts of this kind
+    fn from_segments(bytes: Vec<u8>) -> Self::Owned;
+
+    // the text with its fullwidth forms mapped, see `normalize_fullwidth`
+    fn fullwidth(&self) -> Cow<'_, Self>;
+
+    // decode and transform a segment as the options say, None if it must be
+    // rejected
+    fn decode<'s>(&'s self, options: &Options) -> Option<Cow<'s, Self>>;
+}
+
+impl Text for str {
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    fn slice(&self, range: Range<usize>) -> &str {
+        &self[range]
+    }
+
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+
+    fn lossy(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+
+    fn into_text(text: Cow<'_, str>) -> Option<Cow<'_, str>> {
+        Some(text)
+    }
+
+    fn from_text(text: Cow<'_, str>) -> Cow<'_, str> {
+        text
+    }
+
+    fn from_segments(bytes: Vec<u8>) -> String {
+        String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into())
+    }
+
+    fn fullwidth(&self) -> Cow<'_, str> {
+        normalize_fullwidth(self)
+    }
+
+    fn decode<'s>(&'s self, options: &Options) -> Option<Cow<'s, str>> {
+        options.decode(self)
+    }
+}
+
+impl Text for [u8] {
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn slice(&self, range: Range<usize>) -> &[u8] {
+        &self[range]
+    }
+
+    fn text(&self) -> Option<&str> {
+        core::str::from_utf8(self).ok()
+    }
+
+    fn lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self)
+    }
+
+    fn into_text(text: Cow<'_, [u8]>) -> Option<Cow<'_, str>> {
+        match text {
+            Cow::Borrowed(b) => core::str::from_utf8(b).ok().map(Cow::Borrowed),
+            Cow::Owned(b) => String::from_utf8(b).ok().map(Cow::Owned),
+        }
+    }
+
+    fn from_text(text: Cow<'_, str>) -> Cow<'_, [u8]> {
+        match text {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        }
+    }
+
+    fn from_segments(bytes: Vec<u8>) -> Vec<u8> {
+        bytes
+    }
+
+    fn fullwidth(&self) -> Cow<'_, [u8]> {
+        normalize_fullwidth_bytes(self)
+    }
+
+    // escapes may decode to invalid UTF-8, segments which aren't valid UTF-8
+    // aren't transformed
+    fn decode<'s>(&'s self, options: &Options) -> Option<Cow<'s, [u8]>> {
+        let decoded = match options.percent_decode {
+            PercentDecode::Off => Cow::Borrowed(self),
+            PercentDecode::PassThrough => percent_decode_bytes(self).unwrap_or(Cow::Borrowed(self)),
+            PercentDecode::Strict => percent_decode_bytes(self)?,
+        };
+        if options.segment_transforms.is_empty() {
+            return Some(decoded);
+        }
+        match core::str::from_utf8(&decoded) {
+            Ok(s) => Some(Cow::Owned(
+                options
+                    .transform(Cow::Borrowed(s))
+                    .into_owned()
+                    .into_bytes(),
+            )),
+            Err(_) => Some(decoded),
+        }
+    }
+}
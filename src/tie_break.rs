@@ -0,0 +1,128 @@
+use crate::RouteInfo;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// two routes tied for a path, passed to a `TieBreaker`
+///
+/// routes tie when a segment passes the constraints of sibling parameters,
+/// like `:id<u32>` and a regex `[0-9]+`, and both go on to a route
+#[derive(Debug, Clone, Copy)]
+pub struct Tie<'a> {
+    pub path: &'a str,
+    /// the route whose parameter was added first
+    pub first: &'a RouteInfo,
+    pub second: &'a RouteInfo,
+}
+
+/// winner of a tie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pick {
+    First,
+    Second,
+}
+
+type Trace = dyn Fn(&Tie, Pick) + Send + Sync;
+
+/// callback picking the winner of tied routes, set in `Options::tie_breaker`
+///
+/// without it the parameter added first wins, with more than two tied
+/// routes the winner of a tie meets the next route
+///
+/// # Example
+///
+/// ```
+/// use path_router::{Pick, RouteInfo, Router, TieBreaker};
+/// let mut router = Router::default();
+/// router.options_mut().tie_breaker = Some(
+///     TieBreaker::new(|tie| match tie.second.summary {
+///         Some(_) => Pick::Second,
+///         None => Pick::First,
+///     })
+///     .with_trace(|tie, pick| println!("{}: {:?}", tie.path, pick)),
+/// );
+/// router.add("/n/:id<u32>", 1).unwrap();
+/// let info = RouteInfo {
+///     summary: Some(String::from("small numbers")),
+///     ..RouteInfo::default()
+/// };
+/// router.add_with_info("/n/:small<u8>", info, 2).unwrap();
+///
+/// assert_eq!(*router.recognize("/n/7").unwrap().data, 2);
+/// assert_eq!(*router.recognize("/n/700").unwrap().data, 1);
+/// ```
+#[derive(Clone)]
+pub struct TieBreaker {
+    pick: Arc<dyn Fn(&Tie) -> Pick + Send + Sync>,
+    trace: Option<Arc<Trace>>,
+}
+
+impl TieBreaker {
+    pub fn new<F: Fn(&Tie) -> Pick + Send + Sync + 'static>(f: F) -> TieBreaker {
+        TieBreaker {
+            pick: Arc::new(f),
+            trace: None,
+        }
+    }
+
+    /// also call `trace` with every tie and its winner, e.g. to log them
+    pub fn with_trace<F: Fn(&Tie, Pick) + Send + Sync + 'static>(mut self, trace: F) -> TieBreaker {
+        self.trace = Some(Arc::new(trace));
+        self
+    }
+
+    pub(crate) fn decide(&self, tie: &Tie) -> Pick {
+        let pick = (self.pick)(tie);
+        if let Some(ref trace) = self.trace {
+            trace(tie, pick);
+        }
+        pick
+    }
+}
+
+impl fmt::Debug for TieBreaker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TieBreaker")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::Router;
+    use std::sync::Mutex;
+
+    #[test]
+    fn tie_breaker() {
+        let ties = Arc::new(Mutex::new(vec![]));
+        let log = ties.clone();
+        let mut router = Router::default();
+        router.options_mut().tie_breaker = Some(
+            TieBreaker::new(|tie| match tie.path.len() {
+                n if n > 8 => Pick::Second,
+                _ => Pick::First,
+            })
+            .with_trace(move |tie, pick| log.lock().unwrap().push((tie.path.to_string(), pick))),
+        );
+        router.add("/n/:a<u32>/x", 1).unwrap();
+        router.add("/n/:b<u16>/x", 2).unwrap();
+        router.add("/n/:c<u8>/y", 3).unwrap();
+        router.add("/n/:d", 4).unwrap();
+
+        assert_eq!(*router.recognize("/n/7/x").unwrap().data, 1);
+        let m = router.recognize("/n/1000/x").unwrap();
+        assert_eq!((*m.data, m.params.get("b")), (2, Some("1000")));
+        assert_eq!(*router.recognize("/n/7/y").unwrap().data, 3);
+        assert_eq!(*router.recognize("/n/k").unwrap().data, 4);
+        *router.recognize_mut("/n/1000/x").unwrap().data = 5;
+        assert_eq!(*router.recognize("/n/1000/x").unwrap().data, 5);
+        assert_eq!(
+            *ties.lock().unwrap(),
+            [
+                (String::from("/n/7/x"), Pick::First),
+                (String::from("/n/1000/x"), Pick::Second),
+                (String::from("/n/1000/x"), Pick::Second),
+                (String::from("/n/1000/x"), Pick::Second),
+            ]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Params, RouteInfo, Values};
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// transformation of a parameter value before it lands in `Params`
+///
+/// set per parameter in `RouteInfo::transforms`, the transformations of a
+/// parameter run in order and OpenAPI exports list them in the parameter
+/// description
+///
+/// # Example
+///
+/// ```
+/// use path_router::{RouteInfo, Router, Transform};
+/// let mut router = Router::default();
+/// let info = RouteInfo {
+///     transforms: vec![
+///         (String::from("tag"), Transform::Trim),
+///         (String::from("tag"), Transform::Lowercase),
+///         (
+///             String::from("tag"),
+///             Transform::map("dashes for spaces", |v| v.replace(' ', "-")),
+///         ),
+///     ],
+///     ..RouteInfo::default()
+/// };
+/// router.add_with_info("/tags/:tag", info, 1).unwrap();
+///
+/// let m = router.recognize("/tags/ Rust Lang ").unwrap();
+/// assert_eq!(m.params["tag"], "rust-lang");
+/// ```
+#[derive(Clone)]
+pub enum Transform {
+    /// strip leading and trailing whitespace
+    Trim,
+    Lowercase,
+    /// a custom function, `name` tells the canonical form in docs
+    Map {
+        name: String,
+        f: Arc<dyn Fn(&str) -> String + Send + Sync>,
+    },
+}
+
+impl Transform {
+    pub fn map<F: Fn(&str) -> String + Send + Sync + 'static>(name: &str, f: F) -> Transform {
+        Transform::Map {
+            name: String::from(name),
+            f: Arc::new(f),
+        }
+    }
+
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Transform::Trim => String::from(value.trim()),
+            Transform::Lowercase => value.to_lowercase(),
+            Transform::Map { f, .. } => f(value),
+        }
+    }
+
+    /// what the transformation does, for docs
+    pub fn description(&self) -> &str {
+        match self {
+            Transform::Trim => "trimmed",
+            Transform::Lowercase => "lowercased",
+            Transform::Map { name, .. } => name,
+        }
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Transform) -> bool {
+        match (self, other) {
+            (Transform::Trim, Transform::Trim) => true,
+            (Transform::Lowercase, Transform::Lowercase) => true,
+            (Transform::Map { f: a, .. }, Transform::Map { f: b, .. }) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Transform::Trim => write!(f, "Trim"),
+            Transform::Lowercase => write!(f, "Lowercase"),
+            Transform::Map { name, .. } => write!(f, "Map({:?})", name),
+        }
+    }
+}
+
+// the parameters of a route from the values caught in the path, transformed
+// as the route says
+pub(crate) fn route_params<'n, 'p, N, V>(names: N, info: &RouteInfo, values: V) -> Params
+where
+    N: IntoIterator<Item = &'n Arc<str>>,
+    V: IntoIterator<Item = Cow<'p, str>>,
+{
+    let mut params = Params::new();
+    for (name, value) in names.into_iter().zip(values) {
+        let mut transforms = info.transforms.iter().filter(|(n, _)| **n == **name);
+        let value = match transforms.next() {
+            Some((_, first)) => transforms.fold(first.apply(&value), |v, (_, t)| t.apply(&v)),
+            None => value.into_owned(),
+        };
+        params.push(name.clone(), value);
+    }
+    params
+}
+
+// `route_params` of the values caught in a path of raw bytes, values which
+// aren't valid UTF-8 aren't transformed
+pub(crate) fn route_bytes_params<'p>(
+    names: &[Arc<str>],
+    info: &RouteInfo,
+    values: Values<'p, [u8]>,
+) -> BTreeMap<String, Cow<'p, [u8]>> {
+    let mut params = BTreeMap::new();
+    for (name, value) in names.iter().zip(values) {
+        let mut transforms = info.transforms.iter().filter(|(n, _)| **n == **name);
+        let value = match (transforms.next(), core::str::from_utf8(&value)) {
+            (Some((_, first)), Ok(text)) => {
+                let value = transforms.fold(first.apply(text), |v, (_, t)| t.apply(&v));
+                Cow::Owned(value.into_bytes())
+            }
+            _ => value,
+        };
+        params.insert(String::from(&**name), value);
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Router;
+
+    #[test]
+    fn transform_params() {
+        let mut router = Router::default();
+        let upper = Transform::map("uppercased", |v| v.to_uppercase());
+        let info = RouteInfo {
+            transforms: vec![
+                (String::from("b"), Transform::Lowercase),
+                (String::from("c"), upper.clone()),
+                (String::from("b"), Transform::Trim),
+            ],
+            ..RouteInfo::default()
+        };
+        router.add_with_info("/x/:a/:b/*c", info, 1).unwrap();
+        router.add("/y/:b", 2).unwrap();
+
+        let m = router.recognize("/x/ A / B /p/q").unwrap();
+        assert_eq!(
+            m.params.iter().collect::<Vec<_>>(),
+            [("a", " A "), ("b", "b"), ("c", "P/Q")]
+        );
+        assert_eq!(router.recognize_mut("/x/A/B/q").unwrap().params["b"], "b");
+        assert_eq!(router.recognize("/y/ B ").unwrap().params["b"], " B ");
+        assert_eq!(upper, upper.clone());
+        assert_ne!(upper, Transform::map("uppercased", |v| v.to_uppercase()));
+        assert_eq!(format!("{:?}", upper), "Map(\"uppercased\")");
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::Router;
+use core::fmt;
+
+/// render the trie as an ASCII tree, one node per line
+///
+/// nodes show their segments like `Router::walk`, nodes holding data show
+/// the route ending there after `=>` and nodes with a fallback are marked;
+/// unlike `Debug` the data isn't printed, so any router can be displayed
+///
+/// # Example
+///
+/// ```
+/// use path_router::Router;
+/// let mut router = Router::default();
+/// router.add("/", 0).unwrap();
+/// router.add("/user/:id", 1).unwrap();
+/// router.add("/user/:id<u32>/repos", 2).unwrap();
+/// router.add("/files/*path", 3).unwrap();
+///
+/// assert_eq!(
+///     router.to_string(),
+///     "/ => /
+/// |-- files
+/// |   `-- * => /files/*path
+/// `-- user
+///     |-- :<u32>
+///     |   `-- repos => /user/:id<u32>/repos
+///     `-- : => /user/:id
+/// "
+/// );
+/// ```
+impl<T> fmt::Display for Router<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_ascii(f, &mut vec![], &mut String::new(), None)
+    }
+}
+
+impl<T> Router<T> {
+    // `last` is whether the node is the last child of its parent, `None` for
+    // the root
+    fn fmt_ascii(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        parts: &mut Vec<String>,
+        prefix: &mut String,
+        last: Option<bool>,
+    ) -> fmt::Result {
+        let mark = parts.len();
+        self.push_route_parts(parts);
+        let branch = match last {
+            None => "",
+            Some(true) => "`-- ",
+            Some(false) => "|-- ",
+        };
+        match last {
+            None => write!(f, "/")?,
+            Some(_) => write!(f, "{}{}{}", prefix, branch, parts[mark..].join("/"))?,
+        }
+        if self.data.is_some() {
+            let mut route = self.combine_route_parts(parts, &self.params);
+            if self.optional {
+                route.push('?');
+            }
+            write!(f, " => {}", route)?;
+        }
+        if self.fallback.is_some() {
+            write!(f, " (fallback)")?;
+        }
+        writeln!(f)?;
+
+        let indent = prefix.len();
+        match last {
+            None => {}
+            Some(true) => prefix.push_str("    "),
+            Some(false) => prefix.push_str("|   "),
+        }
+        let children = self
+            .static_children
+            .iter()
+            .chain(self.param_children.iter())
+            .chain(self.catch_all_child.as_deref())
+            .collect::<Vec<_>>();
+        for (i, node) in children.iter().enumerate() {
+            node.fmt_ascii(f, parts, prefix, Some(i + 1 == children.len()))?;
+        }
+        prefix.truncate(indent);
+        parts.truncate(mark);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_tree() {
+        let mut router = Router::<()>::default();
+        assert_eq!(router.to_string(), "/\n");
+
+        router.add("/a/b/c", ()).unwrap();
+        router.add("/a/b/d/:x?", ()).unwrap();
+        router.add("/a/:y/*z/end", ()).unwrap();
+        router.set_fallback("/a", ()).unwrap();
+        router.add("/x/y/z", ()).unwrap();
+        assert_eq!(
+            router.to_string(),
+            "/\n\
+             |-- a (fallback)\n\
+             |   |-- b\n\
+             |   |   |-- c => /a/b/c\n\
+             |   |   `-- d\n\
+             |   |       `-- : => /a/b/d/:x?\n\
+             |   `-- :\n\
+             |       `-- *\n\
+             |           `-- end => /a/:y/*z/end\n\
+             `-- x/y/z => /x/y/z\n"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{percent_decode, Params, RouteInfo, Router};
+
+/// match result of `Router::recognize_uri`
+#[derive(Debug)]
+pub struct UriMatch<'a, T> {
+    /// data set in the route
+    pub data: T,
+    /// extracted parameters from the path
+    pub params: Params,
+    /// information attached to the route
+    pub route: &'a RouteInfo,
+    /// pairs of the query in order, `+` and escapes decoded, a pair without
+    /// `=` has an empty value
+    pub query: Vec<(String, String)>,
+}
+
+// `+` is a space in a query, a value with invalid escapes is kept as is
+fn decode_query(s: &str) -> String {
+    let s = s.replace('+', " ");
+    match percent_decode(&s) {
+        Some(decoded) => decoded.into_owned(),
+        None => s,
+    }
+}
+
+fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_query(name), decode_query(value))
+        })
+        .collect()
+}
+
+impl<T> Router<T> {
+    /// recognize the path of a request target with a query or a fragment
+    ///
+    /// the fragment is dropped, the path before `?` is recognized like
+    /// `recognize` and the query is parsed into `UriMatch::query`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::Router;
+    /// let mut router = Router::default();
+    /// router.add("/user/:id", 1).unwrap();
+    ///
+    /// let m = router.recognize_uri("/user/42?full=1&tag=a+b#top").unwrap();
+    /// assert_eq!(m.params["id"], "42");
+    /// assert_eq!(m.query[0], (String::from("full"), String::from("1")));
+    /// assert_eq!(m.query[1], (String::from("tag"), String::from("a b")));
+    /// ```
+    pub fn recognize_uri<'a>(&'a self, uri: &str) -> Option<UriMatch<'a, &'a T>> {
+        let uri = uri.split('#').next().unwrap_or(uri);
+        let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let m = self.recognize(path)?;
+        Some(UriMatch {
+            data: m.data,
+            params: m.params,
+            route: m.route,
+            query: query_pairs(query),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|&(n, v)| (String::from(n), String::from(v)))
+            .collect()
+    }
+
+    #[test]
+    fn recognize_uris() {
+        let mut router = Router::default();
+        router.add("/user/:id", 1).unwrap();
+        router.add("/", 2).unwrap();
+
+        let m = router.recognize_uri("/user/42").unwrap();
+        assert_eq!((*m.data, &m.params["id"][..]), (1, "42"));
+        assert!(m.query.is_empty());
+
+        let m = router
+            .recognize_uri("/user/7?a=%20x&&flag&b=1=2&bad=100%#a?c=d")
+            .unwrap();
+        assert_eq!(m.params["id"], "7");
+        assert_eq!(
+            m.query,
+            pairs(&[("a", " x"), ("flag", ""), ("b", "1=2"), ("bad", "100%")])
+        );
+
+        assert_eq!(*router.recognize_uri("/?q=1").unwrap().data, 2);
+        assert_eq!(*router.recognize_uri("#top").unwrap().data, 2);
+        assert!(router.recognize_uri("/users?id=1").is_none());
+    }
+}
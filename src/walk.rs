@@ -0,0 +1,136 @@
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{Constraint, NodeKind, RouteInfo, Router};
+use alloc::sync::Arc;
+
+/// the kind of a node of the trie
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteNodeKind {
+    /// one or more static segments, merged when they form a single chain
+    Static,
+    /// a parameter segment
+    Param,
+    /// a catchall taking the rest of the path
+    CatchAll,
+}
+
+/// a node of the trie as seen by `Router::walk`
+#[derive(Debug)]
+pub struct RouteNodeInfo<'a> {
+    /// the number of nodes above, 0 for the root
+    pub depth: usize,
+    pub kind: RouteNodeKind,
+    /// the segments of the node as `list_routes` renders them without the
+    /// names, like `users/all`, `:`, `:<u32>` or `*`, empty for the root
+    pub text: String,
+    /// the constraint of a parameter node
+    pub constraint: Option<&'a Constraint>,
+    /// whether a route ends at the node
+    pub has_data: bool,
+    /// whether the node has a fallback, see `Router::set_fallback`
+    pub has_fallback: bool,
+    /// the parameter names of the route ending at the node
+    pub params: &'a [Arc<str>],
+    /// the information of the route ending at the node
+    pub info: &'a RouteInfo,
+}
+
+impl<T> Router<T> {
+    /// call `visitor` with every node of the trie, depth first with a node
+    /// before its children, static children first, then parameters and the
+    /// catchall in the order they're tried
+    ///
+    /// it's meant for tools rendering or analyzing the trie, the nodes are
+    /// an implementation detail and may change between versions
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use path_router::{RouteNodeKind, Router};
+    /// let mut router = Router::default();
+    /// router.add("/users/:id<u32>", 1).unwrap();
+    /// router.add("/files/*path", 2).unwrap();
+    ///
+    /// let mut nodes = vec![];
+    /// router.walk(|node| nodes.push((node.depth, node.kind, node.text.clone(), node.has_data)));
+    /// assert_eq!(nodes[1], (1, RouteNodeKind::Static, String::from("files"), false));
+    /// assert_eq!(nodes[2], (2, RouteNodeKind::CatchAll, String::from("*"), true));
+    /// assert_eq!(nodes[4], (2, RouteNodeKind::Param, String::from(":<u32>"), true));
+    /// ```
+    pub fn walk<F: FnMut(&RouteNodeInfo)>(&self, mut visitor: F) {
+        self.walk_nodes(0, &mut visitor);
+    }
+
+    fn walk_nodes<F: FnMut(&RouteNodeInfo)>(&self, depth: usize, visitor: &mut F) {
+        let mut parts = vec![];
+        self.push_route_parts(&mut parts);
+        visitor(&RouteNodeInfo {
+            depth,
+            kind: match self.kind {
+                NodeKind::Static => RouteNodeKind::Static,
+                NodeKind::Param => RouteNodeKind::Param,
+                NodeKind::CatchAll => RouteNodeKind::CatchAll,
+            },
+            text: parts.join("/"),
+            constraint: self.constraint.as_ref(),
+            has_data: self.data.is_some(),
+            has_fallback: self.fallback.is_some(),
+            params: &self.params,
+            info: &self.info,
+        });
+        for node in self.static_children.iter() {
+            node.walk_nodes(depth + 1, visitor);
+        }
+        for node in self.param_children.iter() {
+            node.walk_nodes(depth + 1, visitor);
+        }
+        if let Some(ref node) = self.catch_all_child {
+            node.walk_nodes(depth + 1, visitor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn walk() {
+        let mut router = Router::default();
+        router.add("/", 0).unwrap();
+        router.add("/a/b/c", 1).unwrap();
+        router.add("/user/:name", 2).unwrap();
+        router.add("/user/:id<u32>/repos/*path", 3).unwrap();
+        router.set_fallback("/user", 4).unwrap();
+
+        let mut nodes = vec![];
+        router.walk(|node| {
+            let params = node
+                .params
+                .iter()
+                .map(|p| &**p)
+                .collect::<Vec<_>>()
+                .join(",");
+            nodes.push(format!(
+                "{}{} {}{}{}",
+                "  ".repeat(node.depth),
+                node.text,
+                params,
+                if node.has_data { " data" } else { "" },
+                if node.has_fallback { " fallback" } else { "" },
+            ));
+        });
+        assert_eq!(
+            nodes,
+            [
+                "  data",
+                "  a/b/c  data",
+                "  user  fallback",
+                "    :<u32> ",
+                "      repos ",
+                "        * id,path data",
+                "    : name data",
+            ]
+        );
+    }
+}